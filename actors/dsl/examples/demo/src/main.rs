@@ -1,7 +1,7 @@
 use std::{error::Error, ops::Deref};
 
 use gmt_dos_actors::actorscript;
-use interface::{Data, Read, Size, Update, Write, UID};
+use interface::{ClientHooks, Data, Read, Size, Update, Write, UID};
 use tracing::info;
 
 #[tokio::main]
@@ -38,10 +38,13 @@ impl Update for A {
         self.i += 1;
     }
 }
+impl ClientHooks for A {}
 pub struct B(u8);
 impl Update for B {}
+impl ClientHooks for B {}
 pub struct C(u8);
 impl Update for C {}
+impl ClientHooks for C {}
 
 #[derive(UID)]
 #[uid(data = Vec<u8>)]
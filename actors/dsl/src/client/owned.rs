@@ -144,7 +144,11 @@ impl Expand for Client {
             ),
             ClientKind::Sampler => {
                 let sampler_type = LitStr::new(
-                    if self.input_rate < self.output_rate {
+                    if self.output_rate % self.input_rate != 0
+                        && self.input_rate % self.output_rate != 0
+                    {
+                        "fractional"
+                    } else if self.input_rate < self.output_rate {
                         "downsampling"
                     } else {
                         "upsampling"
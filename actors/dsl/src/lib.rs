@@ -134,6 +134,16 @@ actorscript! {
 ```
 where `r` is the up sampling rate transition client [Sampler].
 
+The rates of the 2 **flows** do not have to be multiple of each other, e.g.
+```ignore
+actorscript! {
+    2: a[A2B] -> b
+    5: c[C2D] -> b
+};
+```
+where the rate transition client between `c` and `b` upsamples `C2D` from a rate of 5 to a rate of 2,
+with a cycle of 10 samples in which `C2D` is received twice and sent 5 times.
+
 ## Feedback loop
 
 An example of a feedback loop is a closed **chain** within a **flow** e.g.:
@@ -1,9 +1,10 @@
 use crate::FilterToDifferentiator;
-use interface::{Data, Read, Update, Write, UID};
+use interface::{ClientHooks, Data, Read, Update, Write, UID};
 
 #[derive(Default)]
 pub struct Differentiator(f64, f64);
 impl Update for Differentiator {}
+impl ClientHooks for Differentiator {}
 impl Read<FilterToDifferentiator> for Differentiator {
     fn read(&mut self, data: Data<FilterToDifferentiator>) {
         self.0 = *data;
@@ -40,6 +41,7 @@ impl Integrator {
     }
 }
 impl Update for Integrator {}
+impl ClientHooks for Integrator {}
 impl Read<DifferentiatorToIntegrator> for Integrator {
     fn read(&mut self, data: Data<DifferentiatorToIntegrator>) {
         self.mem[0] += *data * self.gain;
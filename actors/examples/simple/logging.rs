@@ -1,5 +1,5 @@
 use crate::{DifferentiatorToIntegrator, FilterToSink, SamplerToSink, SignalToFilter};
-use interface::{Data, Read, Update};
+use interface::{ClientHooks, Data, Read, Update};
 use std::ops::Deref;
 
 #[derive(Default)]
@@ -11,6 +11,7 @@ impl Deref for Logging {
     }
 }
 impl Update for Logging {}
+impl ClientHooks for Logging {}
 impl Read<SignalToFilter> for Logging {
     fn read(&mut self, data: Data<SignalToFilter>) {
         self.0.push(*data);
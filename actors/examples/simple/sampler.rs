@@ -1,9 +1,10 @@
 use crate::FilterToSampler;
-use interface::{Data, Read, Update, Write, UID};
+use interface::{ClientHooks, Data, Read, Update, Write, UID};
 
 #[derive(Default)]
 pub struct Sampler(f64);
 impl Update for Sampler {}
+impl ClientHooks for Sampler {}
 impl Read<FilterToSampler> for Sampler {
     fn read(&mut self, data: Data<FilterToSampler>) {
         self.0 = *data;
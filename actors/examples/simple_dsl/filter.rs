@@ -1,5 +1,5 @@
 use crate::SignalToFilter;
-use interface::{Data, Read, Update, Write, UID};
+use interface::{ClientHooks, Data, Read, Update, Write, UID};
 use rand_distr::{Distribution, Normal};
 
 pub struct Filter {
@@ -24,6 +24,7 @@ impl Update for Filter {
         self.step += 1;
     }
 }
+impl ClientHooks for Filter {}
 impl Read<SignalToFilter> for Filter {
     fn read(&mut self, data: Data<SignalToFilter>) {
         self.data = *data;
@@ -1,4 +1,4 @@
-use interface::{Data, Update, Write, UID};
+use interface::{ClientHooks, Data, Update, Write, UID};
 
 pub struct Signal {
     pub sampling_frequency: f64,
@@ -31,6 +31,7 @@ impl Update for Signal {
         };
     }
 }
+impl ClientHooks for Signal {}
 
 #[derive(UID)]
 #[uid(data = f64)]
@@ -50,6 +50,8 @@ let sink = Terminator::<_>::new(logging.clone());
 pub(crate) mod actor;
 #[cfg(feature = "serde")]
 mod serde_with;
+#[cfg(feature = "filing")]
+pub(crate) mod checkpoint;
 
 pub use actor::Actor;
 pub(crate) mod plain;
@@ -1,15 +1,25 @@
-use super::io::{Input, InputObject, OutputObject};
+use super::{
+    io::{Input, InputObject, OutputObject},
+    PlainActor,
+};
 use crate::{
     framework::network::{ActorOutput, ActorOutputBuilder, AddActorInput, AddActorOutput},
-    Result,
+    model::{
+        running::Attached,
+        stats::{ActorStats, OutputStats, Profiler},
+    },
+    ActorError, Result,
 };
 use futures::future::try_join_all;
 use interface::{Data, TryRead, TryUpdate, UniqueIdentifier, Who};
 use std::{
+    any::type_name,
     fmt::{self, Debug},
-    sync::Arc,
+    sync::{Arc, OnceLock},
+    time::Instant,
 };
 use tokio::sync::Mutex;
+use tokio_util::sync::CancellationToken;
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Actor model implementation
@@ -34,6 +44,15 @@ where
     pub(crate) client: Arc<Mutex<C>>,
     pub(crate) name: Option<String>,
     pub(crate) image: Option<String>,
+    #[cfg(feature = "filing")]
+    #[cfg_attr(feature = "serde", serde(skip, default = "Option::default"))]
+    pub(crate) codec: Option<super::checkpoint::ClientCodec<C>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) profiler: OnceLock<Profiler>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) updates: usize,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) retirement: Option<CancellationToken>,
 }
 
 /// Clone trait implementation
@@ -48,6 +67,11 @@ impl<C: TryUpdate, const NI: usize, const NO: usize> Clone for Actor<C, NI, NO>
             client: self.client.clone(),
             name: self.name.clone(),
             image: self.image.clone(),
+            #[cfg(feature = "filing")]
+            codec: self.codec,
+            profiler: OnceLock::new(),
+            updates: 0,
+            retirement: None,
         }
     }
 }
@@ -142,6 +166,11 @@ where
             client,
             name: None,
             image: None,
+            #[cfg(feature = "filing")]
+            codec: None,
+            profiler: OnceLock::new(),
+            updates: 0,
+            retirement: None,
         }
     }
     pub fn name<S: Into<String>>(self, name: S) -> Self {
@@ -156,6 +185,31 @@ where
             ..self
         }
     }
+    /// Enables the serialization of the client state in [Model] checkpoints
+    ///
+    /// [Model]: crate::model::Model
+    #[cfg(feature = "filing")]
+    pub fn checkpointable(self) -> Self
+    where
+        C: serde::Serialize + serde::de::DeserializeOwned + 'static,
+    {
+        Self {
+            codec: Some(super::checkpoint::ClientCodec::new()),
+            ..self
+        }
+    }
+    /// Returns a handle to retire the actor from a running [Model]
+    ///
+    /// The actor inputs should be connected with [Tap]s, so the upstream actors
+    /// keep running after the actor has been retired, and the actor can be replaced
+    /// using a [Plug] on its outputs
+    ///
+    /// [Model]: crate::model::Model
+    /// [Tap]: crate::framework::network::Tap
+    /// [Plug]: crate::framework::network::Plug
+    pub fn retire_handle(&mut self) -> Attached {
+        Attached(self.retirement.get_or_insert_with(Default::default).clone())
+    }
     /// Returns a pointer to the actor's client
     pub fn client(&self) -> Arc<Mutex<C>> {
         Arc::clone(&self.client)
@@ -163,20 +217,70 @@ where
     /// Gathers all the inputs from other [Actor] outputs
     pub(super) async fn collect(&mut self) -> Result<&mut Self> {
         if let Some(inputs) = &mut self.inputs {
+            let now = Instant::now();
             let futures = inputs.iter_mut().map(|input| input.recv());
             try_join_all(futures).await?;
+            if let Some(profiler) = self.profiler.get() {
+                profiler.lock().unwrap().collect.record(now.elapsed());
+            }
         }
         Ok(self)
     }
     /// Sends the outputs to other [Actor] inputs
     pub(super) async fn distribute(&mut self) -> Result<&mut Self> {
         if let Some(outputs) = &mut self.outputs {
+            if let Some(profiler) = self.profiler.get() {
+                let mut stats = profiler.lock().unwrap();
+                for (output, stats) in outputs.iter().zip(stats.outputs.iter_mut()) {
+                    stats.record(output.queue_depth());
+                }
+            }
+            let now = Instant::now();
             let futures = outputs.iter_mut().map(|output| output.send());
             try_join_all(futures).await?;
+            if let Some(profiler) = self.profiler.get() {
+                profiler.lock().unwrap().distribute.record(now.elapsed());
+            }
         }
         Ok(self)
     }
-    /// Invokes outputs senders
+    /// Returns the number of samples processed by the actor
+    pub(crate) fn step(&self) -> usize {
+        self.updates * if NI > 0 { NI } else { NO }
+    }
+    /// Returns the last data received by the inputs set with [Actor::dump_input]
+    pub(crate) fn dumps(&self) -> Vec<String> {
+        self.inputs
+            .iter()
+            .flatten()
+            .filter_map(|input| input.last())
+            .collect()
+    }
+    /// Starts recording the runtime statistics of the actor
+    pub(crate) fn profile(&self) -> Profiler {
+        self.profiler.get_or_init(|| self.new_profiler()).clone()
+    }
+    fn new_profiler(&self) -> Profiler {
+        let plain = PlainActor::from(self);
+        let hashes = plain
+            .inputs
+            .iter()
+            .chain(plain.outputs.iter())
+            .flatten()
+            .map(|io| io.hash())
+            .collect();
+        let outputs = self
+            .outputs
+            .iter()
+            .flatten()
+            .map(|output| OutputStats::new(output.who()))
+            .collect();
+        Arc::new(std::sync::Mutex::new(ActorStats::new(
+            plain.client,
+            hashes,
+            outputs,
+        )))
+    }
     /* pub(super) async fn bootstrap(&mut self) -> Result<bool> {
         if let Some(outputs) = &mut self.outputs {
             async fn inner(outputs: &mut Vec<Box<dyn OutputObject>>) -> Result<Vec<()>> {
@@ -209,40 +313,29 @@ where
             Ok(false)
         }
     } */
-    pub(super) async fn bootstrap(&mut self) -> Result<bool> {
-        if let Some(outputs) = &mut self.outputs {
-            async fn inner(outputs: &mut Vec<Box<dyn OutputObject>>) -> Result<bool> {
-                let futures: Vec<_> = outputs
-                    .iter_mut()
-                    .filter(|output| output.bootstrap())
-                    .inspect(|output| {
-                        interface::print_info(
-                            format!("{} bootstrapped", output.highlight()),
-                            None::<&dyn std::error::Error>,
-                        )
-                    })
-                    .map(|output| output.send())
-                    .collect();
-                if futures.is_empty() {
-                    return Ok(false);
-                }
-                try_join_all(futures).await?;
-                Ok(true)
-            }
-
-            if NO >= NI {
-                inner(outputs).await
-            } else {
-                for _ in 0..NI / NO {
-                    if !inner(outputs).await? {
-                        return Ok(false);
-                    }
-                }
-                Ok(true)
-            }
-        } else {
-            Ok(false)
+    /// Sends the bootstrapped outputs to other [Actor] inputs
+    ///
+    /// Returns `false` if there isn't any bootstrapped outputs
+    pub(super) async fn distribute_bootstrap(&mut self) -> Result<bool> {
+        let Some(outputs) = &mut self.outputs else {
+            return Ok(false);
+        };
+        let futures: Vec<_> = outputs
+            .iter_mut()
+            .filter(|output| output.bootstrap())
+            .inspect(|output| {
+                interface::print_info(
+                    format!("{} bootstrapped", output.highlight()),
+                    None::<&dyn std::error::Error>,
+                )
+            })
+            .map(|output| output.send())
+            .collect();
+        if futures.is_empty() {
+            return Ok(false);
         }
+        try_join_all(futures).await?;
+        Ok(true)
     }
 }
 
@@ -279,6 +372,30 @@ where
         }
     }
 }
+impl<C, const NI: usize, const NO: usize> Actor<C, NI, NO>
+where
+    C: TryUpdate + 'static,
+{
+    /// Keeps a copy of the last data received on the input `U`
+    ///
+    /// If the client fails, the data is reported in the
+    /// [ModelFailure](crate::model::ModelFailure) error
+    pub fn dump_input<U>(&mut self) -> Result<&mut Self>
+    where
+        C: TryRead<U>,
+        U: 'static + UniqueIdentifier,
+        Data<U>: Debug,
+    {
+        let who = self.who();
+        self.inputs
+            .iter_mut()
+            .flatten()
+            .find_map(|input| input.as_mut_any().downcast_mut::<Input<C, U, NI>>())
+            .ok_or_else(|| ActorError::NoInput(type_name::<U>().to_string(), who))?
+            .dump();
+        Ok(self)
+    }
+}
 /*
 impl<C, const NI: usize, const NO: usize> Drop for Actor<C, NI, NO>
 where
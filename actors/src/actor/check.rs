@@ -1,7 +1,7 @@
 use interface::{TryUpdate, Who};
 
 use crate::{
-    framework::model::{Check, CheckError},
+    framework::model::{Check, CheckError, Task},
     ActorError,
 };
 
@@ -53,4 +53,7 @@ where
     fn _as_plain(&self) -> PlainActor {
         self.into()
     }
+    fn as_task(&self) -> Option<&dyn Task> {
+        Some(self)
+    }
 }
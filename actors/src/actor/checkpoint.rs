@@ -0,0 +1,140 @@
+use interface::{
+    filing::{Codec, Result},
+    Data, TryUpdate, UniqueIdentifier,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Actor client state
+///
+/// The state is serialized from the locked client and deserialized into a new client
+enum ClientState<'a, C> {
+    Locked(&'a C),
+    Decoded(C),
+}
+#[derive(Serialize)]
+#[serde(rename = "ClientState")]
+struct Locked<'a, C>(&'a C);
+#[derive(Deserialize)]
+#[serde(rename = "ClientState")]
+struct Decoded<C>(C);
+impl<C: Serialize> Serialize for ClientState<'_, C> {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            Self::Locked(client) => Locked(*client).serialize(serializer),
+            Self::Decoded(client) => Locked(client).serialize(serializer),
+        }
+    }
+}
+impl<'de, C: Deserialize<'de>> Deserialize<'de> for ClientState<'_, C> {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Decoded::deserialize(deserializer).map(|Decoded(client)| Self::Decoded(client))
+    }
+}
+impl<C> Codec for ClientState<'_, C> where C: Serialize + DeserializeOwned {}
+
+/// Encoder and decoder of the state of an actor client
+pub(crate) struct ClientCodec<C> {
+    encode: fn(&Arc<Mutex<C>>) -> Result<Vec<u8>>,
+    decode: fn(&Arc<Mutex<C>>, &[u8]) -> Result<()>,
+}
+impl<C> Clone for ClientCodec<C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<C> Copy for ClientCodec<C> {}
+impl<C> ClientCodec<C>
+where
+    C: TryUpdate + Serialize + DeserializeOwned + 'static,
+{
+    pub fn new() -> Self {
+        Self {
+            encode: |client| {
+                let client = futures::executor::block_on(client.lock());
+                let mut buffer = Vec::new();
+                ClientState::Locked(&*client).encode(&mut buffer)?;
+                Ok(buffer)
+            },
+            decode: |client, mut bytes| {
+                let ClientState::Decoded(state) = ClientState::<C>::decode(&mut bytes)? else {
+                    unreachable!("a client state is always decoded into a new client")
+                };
+                *futures::executor::block_on(client.lock()) = state;
+                Ok(())
+            },
+        }
+    }
+}
+impl<C> ClientCodec<C> {
+    /// Encodes the client state
+    pub fn encode(&self, client: &Arc<Mutex<C>>) -> Result<Vec<u8>> {
+        (self.encode)(client)
+    }
+    /// Replaces the client state with the decoded state
+    pub fn decode(&self, client: &Arc<Mutex<C>>, bytes: &[u8]) -> Result<()> {
+        (self.decode)(client, bytes)
+    }
+}
+
+/// Actor output data
+#[derive(Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "<U as UniqueIdentifier>::DataType: Serialize",
+    deserialize = "<U as UniqueIdentifier>::DataType: DeserializeOwned"
+))]
+struct OutputData<U: UniqueIdentifier>(Data<U>);
+impl<U> Codec for OutputData<U>
+where
+    U: UniqueIdentifier,
+    <U as UniqueIdentifier>::DataType: Serialize + DeserializeOwned,
+{
+}
+
+/// Encoder and decoder of the data of an actor output
+pub(crate) struct DataCodec<U: UniqueIdentifier> {
+    encode: fn(&Data<U>) -> Result<Vec<u8>>,
+    decode: fn(&[u8]) -> Result<Data<U>>,
+}
+impl<U: UniqueIdentifier> Clone for DataCodec<U> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<U: UniqueIdentifier> Copy for DataCodec<U> {}
+impl<U> DataCodec<U>
+where
+    U: UniqueIdentifier + 'static,
+    <U as UniqueIdentifier>::DataType: Serialize + DeserializeOwned,
+{
+    pub fn new() -> Self {
+        Self {
+            encode: |data| {
+                let mut buffer = Vec::new();
+                OutputData(data.clone()).encode(&mut buffer)?;
+                Ok(buffer)
+            },
+            decode: |mut bytes| {
+                let OutputData(data) = OutputData::<U>::decode(&mut bytes)?;
+                Ok(data)
+            },
+        }
+    }
+}
+impl<U: UniqueIdentifier> DataCodec<U> {
+    /// Encodes the output data
+    pub fn encode(&self, data: &Data<U>) -> Result<Vec<u8>> {
+        (self.encode)(data)
+    }
+    /// Decodes the output data
+    pub fn decode(&self, bytes: &[u8]) -> Result<Data<U>> {
+        (self.decode)(bytes)
+    }
+}
@@ -3,7 +3,7 @@ use crate::{ActorError, Result};
 use async_trait::async_trait;
 use flume::Receiver;
 use interface::{TryRead, UniqueIdentifier, Who};
-use std::any::type_name;
+use std::any::{type_name, Any};
 use std::fmt::Debug;
 use std::{fmt::Display, sync::Arc};
 use tokio::sync::Mutex;
@@ -18,6 +18,8 @@ where
     rx: Receiver<S<U>>,
     client: Arc<Mutex<C>>,
     hash: u64,
+    last: Option<S<U>>,
+    dump: Option<fn(&S<U>) -> String>,
 }
 impl<C, U, const N: usize> Input<C, U, N>
 where
@@ -26,7 +28,20 @@ where
 {
     /// Creates a new intput from a [Receiver], an [Actor] client and an identifier [hash]
     pub fn new(rx: Receiver<S<U>>, client: Arc<Mutex<C>>, hash: u64) -> Self {
-        Self { rx, client, hash }
+        Self {
+            rx,
+            client,
+            hash,
+            last: None,
+            dump: None,
+        }
+    }
+    /// Keeps a copy of the last data received by the client
+    pub fn dump(&mut self)
+    where
+        S<U>: Debug,
+    {
+        self.dump = Some(|data| format!("{data:?}"));
     }
 }
 impl<C, U, const N: usize> Who<U> for Input<C, U, N>
@@ -67,6 +82,11 @@ pub(crate) trait InputObject: Display + Send + Sync {
     /// Gets the input hash
     fn get_hash(&self) -> u64;
     fn capacity(&self) -> Option<usize>;
+    /// Checks if some data is waiting or if the channel is disconnected
+    fn is_ready(&self) -> bool;
+    /// Formats the last data received by the client if the input is dumped
+    fn last(&self) -> Option<String>;
+    fn as_mut_any(&mut self) -> &mut dyn Any;
 }
 
 impl Debug for Box<dyn InputObject> {
@@ -78,9 +98,9 @@ impl Debug for Box<dyn InputObject> {
 #[async_trait]
 impl<'a, C, U, const N: usize> InputObject for Input<C, U, N>
 where
-    C: TryRead<U>,
+    C: TryRead<U> + 'static,
     <C as TryRead<U>>::Error: 'static,
-    U: UniqueIdentifier,
+    U: UniqueIdentifier + 'static,
 {
     async fn recv(&mut self) -> Result<()> {
         // log::debug!("{} receiving", Who::highlight(self));
@@ -91,9 +111,12 @@ where
                 source: e,
             }
         })?;
+        if self.dump.is_some() {
+            self.last = Some(data.clone());
+        }
         let mut client = self.client.lock().await;
         // log::debug!("{} receiving (client locked)", Who::who(self));
-        (*client).boxed_try_read(data)?;
+        client.async_try_read(data).await?;
         log::debug!(
             "{} RECV@{N}: {} - {}",
             self.hash,
@@ -111,4 +134,24 @@ where
     fn capacity(&self) -> Option<usize> {
         self.rx.capacity()
     }
+    fn is_ready(&self) -> bool {
+        !self.rx.is_empty() || self.rx.is_disconnected()
+    }
+    fn last(&self) -> Option<String> {
+        self.dump.zip(self.last.as_ref()).map(|(dump, data)| {
+            let mut data = dump(data);
+            if data.len() > 1024 {
+                let end = (0..=1024)
+                    .rev()
+                    .find(|&i| data.is_char_boundary(i))
+                    .unwrap_or(0);
+                data.truncate(end);
+                data.push_str("...");
+            }
+            format!("{}: {}", Who::who(self), data)
+        })
+    }
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
 }
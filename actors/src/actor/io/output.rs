@@ -1,8 +1,11 @@
 use super::S;
-use crate::{ActorError, Result};
+use crate::{
+    framework::network::{Subscribers, Taps},
+    ActorError, Result,
+};
 use async_trait::async_trait;
 use flume::Sender;
-use futures::future::try_join_all;
+use futures::future::{join_all, try_join_all};
 use interface::{Assoc, TryWrite, UniqueIdentifier, Who};
 use std::any::{type_name, Any};
 use std::fmt::Debug;
@@ -17,6 +20,7 @@ where
     tx: Vec<Sender<S<U>>>,
     client: Arc<Mutex<C>>,
     bootstrap: bool,
+    capacity: usize,
 }
 impl<C, U, const N: usize> OutputBuilder<C, U, N>
 where
@@ -28,6 +32,7 @@ where
             tx: Vec::new(),
             client,
             bootstrap: false,
+            capacity: 1,
         }
     }
     pub fn senders(self, tx: Vec<Sender<S<U>>>) -> Self {
@@ -36,6 +41,10 @@ where
     pub fn bootstrap(self, bootstrap: bool) -> Self {
         Self { bootstrap, ..self }
     }
+    /// Sets the capacity of the channels of the inputs connected through a [Tap](crate::framework::network::Tap)
+    pub fn capacity(self, capacity: usize) -> Self {
+        Self { capacity, ..self }
+    }
     pub fn build(self) -> Output<C, U, N> {
         Output {
             data: None,
@@ -43,6 +52,10 @@ where
             client: self.client,
             bootstrap: self.bootstrap,
             hash: 0,
+            capacity: self.capacity,
+            taps: None,
+            #[cfg(feature = "filing")]
+            codec: None,
         }
     }
 }
@@ -59,6 +72,10 @@ where
     client: Arc<Mutex<C>>,
     bootstrap: bool,
     hash: u64,
+    capacity: usize,
+    taps: Option<Taps<U>>,
+    #[cfg(feature = "filing")]
+    codec: Option<crate::actor::checkpoint::DataCodec<U>>,
 }
 impl<C, U, const N: usize> Output<C, U, N>
 where
@@ -73,6 +90,27 @@ where
         self.tx.append(&mut tx);
         self
     }
+    /// Returns the channels to the inputs
+    pub fn senders(&self) -> Vec<Sender<S<U>>> {
+        self.tx.clone()
+    }
+    /// Returns the inputs subscribed to the output through a [Tap](crate::framework::network::Tap)
+    pub fn taps(&mut self) -> Taps<U> {
+        let capacity = self.capacity;
+        self.taps
+            .get_or_insert_with(|| Arc::new(std::sync::Mutex::new(Subscribers::new(capacity))))
+            .clone()
+    }
+    /// Enables the serialization of the output data in [Model](crate::model::Model) checkpoints
+    #[cfg(feature = "filing")]
+    pub fn checkpointable(&mut self) -> &mut Self
+    where
+        U: 'static,
+        <U as UniqueIdentifier>::DataType: serde::Serialize + serde::de::DeserializeOwned,
+    {
+        self.codec = Some(crate::actor::checkpoint::DataCodec::new());
+        self
+    }
 }
 impl<C, U, const N: usize> Who<U> for Output<C, U, N>
 where
@@ -122,6 +160,21 @@ pub(crate) trait OutputObject: Any + Display + Send + Sync {
     fn highlight(&self) -> String;
     fn set_hash(&mut self, hash: u64);
     fn get_hash(&self) -> u64;
+    /// Checks if all the channels can accept some data or if any is disconnected
+    fn is_ready(&self) -> bool;
+    /// Returns the largest number of data waiting in a channel
+    fn queue_depth(&self) -> usize;
+    /// Flags the channels holding data that has not been received yet
+    #[cfg(feature = "filing")]
+    fn pending(&self) -> Vec<bool>;
+    /// Encodes the data last sent by the output
+    ///
+    /// Returns [None] if there is no data or if the data cannot be serialized
+    #[cfg(feature = "filing")]
+    fn encode_data(&self) -> Option<interface::filing::Result<Vec<u8>>>;
+    /// Decodes the data and sends it to the flagged channels
+    #[cfg(feature = "filing")]
+    async fn resend(&mut self, pending: &[bool], bytes: &[u8]) -> Result<()>;
     // fn as_any(&self) -> &dyn Any;
     fn as_mut_any(&mut self) -> &mut dyn Any;
 }
@@ -141,7 +194,7 @@ where
 {
     /// Sends output data
     async fn send(&mut self) -> Result<()> {
-        self.data = (*self.client.lock().await).boxed_try_write()?;
+        self.data = self.client.lock().await.async_try_write().await?;
         if let Some(data) = &self.data {
             // log::debug!("{} sending", Who::highlight(self));
             // let futures: FuturesUnordered<_> = self
@@ -157,6 +210,13 @@ where
                     msg: format!("output {} from {}", type_name::<U>(), type_name::<C>()), //Who::lite(self),
                     source: flume::SendError(()),
                 })?;
+            if let Some(taps) = &self.taps {
+                let senders = taps.lock().unwrap().senders();
+                let results = join_all(senders.iter().map(|tx| tx.send_async(data.clone()))).await;
+                if results.iter().any(|result| result.is_err()) {
+                    taps.lock().unwrap().prune();
+                }
+            }
             log::debug!(
                 "{} SEND@{N}: {} - {}",
                 self.hash,
@@ -174,6 +234,9 @@ where
             for tx in std::mem::take(&mut self.tx) {
                 drop(tx);
             }
+            if let Some(taps) = &self.taps {
+                taps.lock().unwrap().close();
+            }
             Err(ActorError::Disconnected(format!(
                 "output {} from {}",
                 type_name::<U>(),
@@ -193,6 +256,10 @@ where
     }
     fn len(&self) -> usize {
         self.tx.len()
+            + self
+                .taps
+                .as_ref()
+                .map_or(0, |taps| taps.lock().unwrap().len())
     }
     fn set_hash(&mut self, hash: u64) {
         self.hash = hash;
@@ -200,6 +267,58 @@ where
     fn get_hash(&self) -> u64 {
         self.hash
     }
+    fn is_ready(&self) -> bool {
+        self.tx
+            .iter()
+            .all(|tx| !tx.is_full() || tx.is_disconnected())
+            && self
+                .taps
+                .as_ref()
+                .is_none_or(|taps| taps.lock().unwrap().is_ready())
+    }
+    fn queue_depth(&self) -> usize {
+        self.tx.iter().map(|tx| tx.len()).max().unwrap_or_default()
+    }
+    #[cfg(feature = "filing")]
+    fn pending(&self) -> Vec<bool> {
+        self.tx.iter().map(|tx| !tx.is_empty()).collect()
+    }
+    #[cfg(feature = "filing")]
+    fn encode_data(&self) -> Option<interface::filing::Result<Vec<u8>>> {
+        self.codec
+            .zip(self.data.as_ref())
+            .map(|(codec, data)| codec.encode(data))
+    }
+    #[cfg(feature = "filing")]
+    async fn resend(&mut self, pending: &[bool], bytes: &[u8]) -> Result<()> {
+        let codec = self.codec.ok_or_else(|| {
+            ActorError::Other(
+                format!(
+                    "output {} from {} data cannot be deserialized",
+                    type_name::<U>(),
+                    type_name::<C>()
+                )
+                .into(),
+            )
+        })?;
+        let data = codec
+            .decode(bytes)
+            .map_err(|e| ActorError::Other(Box::new(e)))?;
+        let data = &*self.data.insert(data);
+        let futures = self
+            .tx
+            .iter()
+            .zip(pending)
+            .filter(|(_, &pending)| pending)
+            .map(|(tx, _)| tx.send_async(data.clone()));
+        try_join_all(futures)
+            .await
+            .map_err(|_| ActorError::DropSend {
+                msg: format!("output {} from {}", type_name::<U>(), type_name::<C>()),
+                source: flume::SendError(()),
+            })?;
+        Ok(())
+    }
     // fn as_any(&self) -> &dyn Any {
     //     self
     // }
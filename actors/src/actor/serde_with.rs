@@ -1,13 +1,20 @@
 use interface::TryUpdate;
-use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::{ser::Error, Deserialize, Deserializer, Serialize, Serializer};
 use std::sync::Arc;
-use tokio::{sync::Mutex, task};
+use tokio::sync::Mutex;
 
+/// Serializes the client
+///
+/// The client must not be locked, e.g. by its running actor,
+/// otherwise the serialization fails instead of blocking
 pub fn serialize<S, C: TryUpdate + Serialize>(client: &Arc<Mutex<C>>, s: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
-    task::block_in_place(move || client.blocking_lock().serialize(s))
+    client
+        .try_lock()
+        .map_err(|_| S::Error::custom("the actor client is locked"))?
+        .serialize(s)
 }
 
 pub fn deserialize<'de, D, C: TryUpdate + Deserialize<'de>>(
@@ -27,6 +34,7 @@ mod tests {
     #[derive(Debug, Serialize, Deserialize)]
     pub struct Client();
     impl interface::Update for Client {}
+    impl interface::ClientHooks for Client {}
 
     #[test]
     fn serde() {
@@ -1,9 +1,14 @@
-use std::any::type_name;
+use std::{any::type_name, time::Instant};
 
 use async_trait::async_trait;
-use interface::TryUpdate;
+use futures::executor::block_on;
+use interface::{ClientHooks, TryUpdate, Who};
 
-use crate::framework::model::{Task, TaskError};
+use crate::{
+    framework::model::{Cancellation, ClientFailure, Operation, Sequence, Task, TaskError},
+    model::stats::Profiler,
+    ActorError,
+};
 
 use super::{Actor, PlainActor};
 
@@ -40,79 +45,334 @@ where
 
     /// Starts the actor infinite loop
     async fn async_run(&mut self) -> Result<()> {
-        log::debug!("ACTOR LOOP ({NI}/{NO}): {}", type_name::<C>());
-        let bootstrap = self.bootstrap().await?;
+        let result = self.run(&Cancellation::default()).await;
+        result.map_err(|e| self.failure(e))
+    }
+
+    async fn cancellable_task(mut self: Box<Self>, cancellation: Cancellation) -> Result<()> {
+        let name = Who::who(&*self);
+        let retirement = self.retirement.take().unwrap_or_default();
+        let result = tokio::select! {
+            result = self.run(&cancellation) => result.map_err(|e| self.failure(e)),
+            _ = cancellation.token().cancelled() => Err(TaskError::Cancelled(name.clone())),
+            _ = retirement.cancelled() => Err(TaskError::Retired(name.clone())),
+        };
+        // a channel may be dropped by another cancelled actor before this one sees the cancellation
+        let result = match result {
+            Err(TaskError::FromActor(
+                ActorError::Disconnected(_)
+                | ActorError::DropRecv { .. }
+                | ActorError::DropSend { .. },
+            )) if cancellation.token().is_cancelled() => Err(TaskError::Cancelled(name)),
+            result => result,
+        };
+        match result {
+            Ok(_)
+            | Err(TaskError::FromActor(
+                ActorError::Disconnected(_)
+                | ActorError::DropRecv { .. }
+                | ActorError::DropSend { .. },
+            )) => (),
+            _ => self.client.lock().await.on_shutdown(),
+        }
+        result
+    }
+
+    fn as_plain(&self) -> PlainActor {
+        self.into()
+    }
+    fn name(&self) -> &'static str {
+        type_name::<C>()
+    }
+    /// Unrolls the actor loop of [Task::async_run]
+    fn sequence(&self) -> Sequence {
+        use Operation::*;
+        let bootstrap = self
+            .outputs
+            .as_ref()
+            .is_some_and(|outputs| outputs.iter().any(|output| output.bootstrap()));
         match (self.inputs.as_ref(), self.outputs.as_ref()) {
             (Some(_), Some(_)) => {
-                if NO >= NI {
+                if NO.is_multiple_of(NI) {
                     // Decimation
-                    if !bootstrap {
-                        // bootstrap is applied when decimation is used
-                        // in conjunction with averaging
-                        // When averaging there is a delay of `NO` samples
-                        // to account for the time to iterate and a default
-                        // values is used for the 1st output
-                        // For decimation of the input signal there is no delay
-                        // and the 1st sample goes through unimpeded
-                        self.collect()
-                            .await?
-                            .client
-                            .lock()
-                            .await
-                            .boxed_try_update()?;
-                        self.distribute().await?;
+                    // bootstrap is applied when decimation is used
+                    // in conjunction with averaging
+                    // When averaging there is a delay of `NO` samples
+                    // to account for the time to iterate and a default
+                    // values is used for the 1st output
+                    // For decimation of the input signal there is no delay
+                    // and the 1st sample goes through unimpeded
+                    let (prologue, offset) = if bootstrap {
+                        (vec![(Bootstrap, 0)], 0)
+                    } else {
+                        (vec![(Collect, 0), (Update, 0), (Distribute, 0)], NI)
+                    };
+                    let mut cycle: Vec<_> = (0..NO / NI)
+                        .flat_map(|i| [(Collect, offset + i * NI), (Update, offset + i * NI)])
+                        .collect();
+                    cycle.push((Distribute, offset + NO - NI));
+                    Sequence {
+                        prologue,
+                        cycle,
+                        period: NO,
                     }
-                    loop {
-                        for _ in 0..NO / NI {
-                            self.collect()
-                                .await?
-                                .client
-                                .lock()
-                                .await
-                                .boxed_try_update()?;
-                        }
-                        self.distribute().await?;
-                    }
-                } else {
+                } else if NI.is_multiple_of(NO) {
                     // Upsampling
-                    loop {
-                        self.collect()
-                            .await?
-                            .client
-                            .lock()
-                            .await
-                            .boxed_try_update()?;
-                        for _ in 0..NI / NO {
-                            self.distribute().await?;
-                        }
+                    let prologue = if bootstrap {
+                        (0..NI / NO).map(|i| (Bootstrap, i * NO)).collect()
+                    } else {
+                        vec![]
+                    };
+                    let mut cycle = vec![(Collect, 0), (Update, 0)];
+                    cycle.extend((0..NI / NO).map(|i| (Distribute, i * NO)));
+                    Sequence {
+                        prologue,
+                        cycle,
+                        period: NI,
                     }
+                } else {
+                    Self::rate_transition(bootstrap)
                 }
             }
-            (None, Some(_)) => {
-                // Initiator
-                tokio::task::yield_now().await; // at least cooperates with other tasks
-                loop {
-                    self.client.lock().await.boxed_try_update()?;
-                    self.distribute().await?;
-                }
-            }
-            (Some(_), None) => loop {
-                // Terminator
-                self.collect()
-                    .await?
-                    .client
-                    .lock()
-                    .await
-                    .boxed_try_update()?;
+            // Initiator
+            (None, Some(_)) => Sequence {
+                prologue: if bootstrap {
+                    vec![(Bootstrap, 0)]
+                } else {
+                    vec![]
+                },
+                cycle: vec![(Update, 0), (Distribute, 0)],
+                period: NO.max(1),
             },
-            (None, None) => Ok(()),
+            // Terminator
+            (Some(_), None) => Sequence {
+                cycle: vec![(Collect, 0), (Update, 0)],
+                period: NI.max(1),
+                ..Default::default()
+            },
+            (None, None) => Default::default(),
+        }
+    }
+    fn is_ready(&self, operation: Operation) -> bool {
+        match operation {
+            Operation::Bootstrap => self.outputs.as_ref().is_none_or(|outputs| {
+                outputs
+                    .iter()
+                    .filter(|output| output.bootstrap())
+                    .all(|output| output.is_ready())
+            }),
+            Operation::Collect => self
+                .inputs
+                .as_ref()
+                .is_none_or(|inputs| inputs.iter().all(|input| input.is_ready())),
+            Operation::Update => true,
+            Operation::Distribute => self
+                .outputs
+                .as_ref()
+                .is_none_or(|outputs| outputs.iter().all(|output| output.is_ready())),
+        }
+    }
+    fn execute(&mut self, operation: Operation, runtime: &tokio::runtime::Runtime) -> Result<()> {
+        runtime
+            .block_on(self.perform(operation))
+            .map_err(|e| self.failure(e.into()))
+    }
+    fn profile(&self) -> Vec<Profiler> {
+        vec![Actor::profile(self)]
+    }
+    fn provenance(&self) -> Vec<(String, String)> {
+        block_on(self.client.lock()).provenance()
+    }
+    fn set_provenance(&self, provenance: &[(String, String)]) {
+        block_on(self.client.lock()).set_provenance(provenance);
+    }
+    fn set_inputs_rate(&self) {
+        block_on(self.client.lock()).set_inputs_rate(NI);
+    }
+    #[cfg(feature = "filing")]
+    fn encode_client(&self) -> Option<interface::filing::Result<Vec<u8>>> {
+        self.codec.map(|codec| codec.encode(&self.client))
+    }
+    #[cfg(feature = "filing")]
+    fn decode_client(&mut self, bytes: &[u8]) -> Option<interface::filing::Result<()>> {
+        self.codec.map(|codec| codec.decode(&self.client, bytes))
+    }
+    #[cfg(feature = "filing")]
+    fn pending_outputs(
+        &self,
+    ) -> interface::filing::Result<Vec<crate::model::checkpoint::PendingOutput>> {
+        self.outputs
+            .iter()
+            .flatten()
+            .map(|output| (output, output.pending()))
+            .filter(|(_, channels)| channels.iter().any(|&pending| pending))
+            .map(|(output, channels)| {
+                Ok(crate::model::checkpoint::PendingOutput {
+                    name: output.who(),
+                    hash: output.get_hash(),
+                    channels,
+                    data: output.encode_data().transpose()?,
+                })
+            })
+            .collect()
+    }
+    #[cfg(feature = "filing")]
+    fn resend(&mut self, pending: &[crate::model::checkpoint::PendingOutput]) -> Result<()> {
+        let who = self.who();
+        for pending in pending {
+            let hash = pending.hash;
+            let output = self
+                .outputs
+                .iter_mut()
+                .flatten()
+                .find(|output| output.get_hash() == hash)
+                .ok_or_else(|| ActorError::OrphanOutput(format!("#{hash}"), who.clone()))?;
+            let Some(bytes) = pending.data.as_deref() else {
+                return Err(ActorError::Other(
+                    format!("no data saved for output {} in {who}", pending.name).into(),
+                )
+                .into());
+            };
+            block_on(output.resend(&pending.channels, bytes))?;
         }
+        Ok(())
     }
+}
 
-    fn as_plain(&self) -> PlainActor {
-        self.into()
+impl<C, const NI: usize, const NO: usize> Actor<C, NI, NO>
+where
+    C: 'static + TryUpdate,
+{
+    /// Updates the client state
+    pub(super) async fn update(&mut self) -> std::result::Result<&mut Self, ActorError> {
+        let now = Instant::now();
+        self.client.lock().await.async_try_update().await?;
+        self.updates += 1;
+        if let Some(profiler) = self.profiler.get() {
+            profiler.lock().unwrap().update.record(now.elapsed());
+        }
+        Ok(self)
     }
-    fn name(&self) -> &'static str {
-        type_name::<C>()
+    /// Attaches the actor name, the current step and the dumped inputs to a client error
+    fn failure(&self, error: TaskError) -> TaskError {
+        match error {
+            TaskError::FromActor(source @ ActorError::Other(_)) => {
+                TaskError::Client(Box::new(ClientFailure {
+                    actor: Who::who(self),
+                    step: self.step(),
+                    inputs: self.dumps(),
+                    source,
+                }))
+            }
+            error => error,
+        }
     }
+    /// Performs one [Operation] of the actor loop
+    async fn perform(&mut self, operation: Operation) -> std::result::Result<(), ActorError> {
+        match operation {
+            Operation::Bootstrap => {
+                self.distribute_bootstrap().await?;
+            }
+            Operation::Collect => {
+                self.collect().await?;
+            }
+            Operation::Update => {
+                self.update().await?;
+            }
+            Operation::Distribute => {
+                self.distribute().await?;
+            }
+        }
+        Ok(())
+    }
+    /// Unrolls the loop of an actor which inputs and outputs rates are not multiple of each other
+    ///
+    /// The loop cycle spans the least common multiple `L` of the inputs and outputs rates:
+    /// the inputs are collected every `NI` samples and the outputs are distributed every `NO` samples,
+    /// an output sample being written after the inputs received at the same sample have been read.
+    /// If some outputs are bootstrapped, the outputs are written before the inputs received at the same sample
+    /// and the 1st output is the bootstrapped output.
+    fn rate_transition(bootstrap: bool) -> Sequence {
+        use Operation::*;
+        let period = lcm(NI, NO);
+        let samples = (0..=period).step_by(gcd(NI, NO));
+        if bootstrap {
+            let cycle = samples
+                .flat_map(|i| {
+                    let distribute = (i > 0 && i.is_multiple_of(NO)).then_some((Distribute, i));
+                    let collect =
+                        (i < period && i.is_multiple_of(NI)).then_some([(Collect, i), (Update, i)]);
+                    distribute.into_iter().chain(collect.into_iter().flatten())
+                })
+                .collect();
+            Sequence {
+                prologue: vec![(Bootstrap, 0)],
+                cycle,
+                period,
+            }
+        } else {
+            let cycle = samples
+                .skip(1)
+                .flat_map(|i| {
+                    let collect = i.is_multiple_of(NI).then_some([(Collect, i), (Update, i)]);
+                    let distribute = i.is_multiple_of(NO).then_some((Distribute, i));
+                    collect.into_iter().flatten().chain(distribute)
+                })
+                .collect();
+            Sequence {
+                prologue: vec![(Collect, 0), (Update, 0), (Distribute, 0)],
+                cycle,
+                period,
+            }
+        }
+    }
+    /// Runs the actor loop
+    ///
+    /// The actor performs the [Operation]s of its [Sequence]:
+    /// an initiator stops after the number of samples of the [Cancellation], if any,
+    /// and, with the `filing` feature, the actor pauses in between 2 operations
+    /// when a checkpoint of the model is requested
+    pub(super) async fn run(&mut self, cancellation: &Cancellation) -> Result<()> {
+        log::debug!("ACTOR LOOP ({NI}/{NO}): {}", type_name::<C>());
+        let sequence = Task::sequence(self);
+        let initiator = self.inputs.is_none();
+        if initiator {
+            tokio::task::yield_now().await; // at least cooperates with other tasks
+        }
+        let n_sample = cancellation
+            .n_sample()
+            .filter(|_| initiator)
+            .map(|n| n.div_ceil(NO.max(1)) * NO.max(1));
+        #[cfg(feature = "filing")]
+        let member = cancellation
+            .rendezvous()
+            .and_then(|rendezvous| rendezvous.join(&self.as_plain()));
+        let mut cursor = 0;
+        while let Some((operation, i)) = sequence.get(cursor) {
+            if n_sample.is_some_and(|n| i >= n) {
+                log::debug!("{} stopped after {} samples", Who::who(self), i);
+                break;
+            }
+            #[cfg(feature = "filing")]
+            if let Some(member) = member.as_ref() {
+                member.pause(i, cursor, &*self).await;
+            }
+            self.perform(operation).await?;
+            cursor += 1;
+        }
+        Ok(())
+    }
+}
+
+/// Greatest common divisor
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+/// Least common multiple
+fn lcm(a: usize, b: usize) -> usize {
+    a / gcd(a, b) * b
 }
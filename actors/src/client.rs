@@ -63,7 +63,7 @@ impl<'a, T: ArcMutex> From<Client<'a, T>> for Option<T> {
 
 #[cfg(test)]
 mod tests {
-    use interface::Update;
+    use interface::{ClientHooks, Update};
 
     #[test]
     fn client() {
@@ -72,6 +72,7 @@ mod tests {
         struct TestClient;
 
         impl Update for TestClient {}
+        impl ClientHooks for TestClient {}
 
         let test_client = TestClient;
 
@@ -8,6 +8,8 @@
 
 use std::path::PathBuf;
 
+use tokio_util::sync::CancellationToken;
+
 use crate::graph::GraphError;
 use crate::model::{Model, UnknownOrReady};
 use crate::system::System;
@@ -51,6 +53,10 @@ pub trait Check {
     fn is_system(&self) -> bool {
         false
     }
+    /// Returns the [Task] interface of the actor or of the system
+    fn as_task(&self) -> Option<&dyn Task> {
+        None
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -59,13 +65,142 @@ pub enum TaskError {
     FromActor(#[from] ActorError),
     #[error("error in Task from Model")]
     FromModel(#[from] model::ModelError),
+    #[error("{0} has been cancelled")]
+    Cancelled(String),
+    #[error("{0} has been retired")]
+    Retired(String),
+    #[error(transparent)]
+    Client(Box<ClientFailure>),
     #[error(transparent)]
     Other(#[from] Box<dyn std::error::Error + Send + Sync>),
 }
 
+/// Error returned by the client of an [Actor](crate::actor::Actor)
+#[derive(Debug, thiserror::Error)]
+#[error("{actor} failed at step {step}")]
+pub struct ClientFailure {
+    /// Actor name
+    pub actor: String,
+    /// Sample index of the failing update, at the simulation sampling frequency
+    pub step: usize,
+    /// Last inputs received by the client, see [Actor::dump_input](crate::actor::Actor::dump_input)
+    pub inputs: Vec<String>,
+    /// Client error
+    #[source]
+    pub source: ActorError,
+}
+
+/// Early termination conditions of a [Task]
+///
+/// A [Task] stops when the cancellation token is triggered or,
+/// for an [Initiator](crate::actor::Initiator), after a given number of samples.
+///
+/// With the `filing` feature, the [Cancellation] also carries the
+/// checkpoint requests of a running [Model](crate::model::Model)
+#[derive(Debug, Clone, Default)]
+pub struct Cancellation {
+    token: CancellationToken,
+    n_sample: Option<usize>,
+    #[cfg(feature = "filing")]
+    rendezvous: Option<crate::model::checkpoint::Rendezvous>,
+}
+impl Cancellation {
+    /// Creates a new [Cancellation] from a token and an optional number of samples
+    pub fn new(token: CancellationToken, n_sample: Option<usize>) -> Self {
+        Self {
+            token,
+            n_sample,
+            ..Default::default()
+        }
+    }
+    /// Returns the cancellation token
+    pub fn token(&self) -> &CancellationToken {
+        &self.token
+    }
+    /// Returns the number of samples after which initiators stop
+    pub fn n_sample(&self) -> Option<usize> {
+        self.n_sample
+    }
+    /// Returns a [Cancellation] with a child token
+    ///
+    /// Cancelling the child token does not cancel the parent token
+    pub fn child(&self) -> Self {
+        Self {
+            token: self.token.child_token(),
+            ..self.clone()
+        }
+    }
+    /// Returns the checkpoint requests
+    #[cfg(feature = "filing")]
+    pub(crate) fn rendezvous(&self) -> Option<&crate::model::checkpoint::Rendezvous> {
+        self.rendezvous.as_ref()
+    }
+    /// Sets the checkpoint requests
+    #[cfg(feature = "filing")]
+    pub(crate) fn with_rendezvous(
+        mut self,
+        rendezvous: crate::model::checkpoint::Rendezvous,
+    ) -> Self {
+        self.rendezvous = Some(rendezvous);
+        self
+    }
+}
+
+/// Elementary operations of an [Actor](crate::actor::Actor) loop
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operation {
+    /// Sends the bootstrapped outputs
+    Bootstrap,
+    /// Receives all the inputs
+    Collect,
+    /// Updates the client state
+    Update,
+    /// Sends all the outputs
+    Distribute,
+}
+
+/// Sequence of [Operation]s performed by a [Task]
+///
+/// Each [Operation] is paired with the sample index, at the simulation sampling frequency,
+/// from which it is allowed to run.
+/// The `prologue` runs once and is followed by the `cycle` repeated every `period` samples.
+#[derive(Debug, Clone, Default)]
+pub struct Sequence {
+    pub prologue: Vec<(Operation, usize)>,
+    pub cycle: Vec<(Operation, usize)>,
+    pub period: usize,
+}
+impl Sequence {
+    /// Returns the `n`th [Operation] of the sequence and its sample index
+    pub fn get(&self, n: usize) -> Option<(Operation, usize)> {
+        if let Some(op) = self.prologue.get(n) {
+            return Some(*op);
+        }
+        if self.cycle.is_empty() {
+            return None;
+        }
+        let n = n - self.prologue.len();
+        let (op, i) = self.cycle[n % self.cycle.len()];
+        Some((op, i + (n / self.cycle.len()) * self.period))
+    }
+}
+
+/// Conversion of a [Task] into a trait object
+///
+/// The trait is implemented for all the types implementing [Task]
+pub trait IntoTask {
+    /// Returns the task as a [Task] trait object
+    fn into_task(self: Box<Self>) -> Box<dyn Task>;
+}
+impl<T: Task + 'static> IntoTask for T {
+    fn into_task(self: Box<Self>) -> Box<dyn Task> {
+        self
+    }
+}
+
 /// Interface for running model components
 #[async_trait::async_trait]
-pub trait Task: Check + std::fmt::Display + Send + Sync {
+pub trait Task: Check + IntoTask + std::fmt::Display + Send + Sync {
     /// Runs the [Actor](crate::actor::Actor) infinite loop
     ///
     /// The loop ends when the client data is [None] or when either the sending of receiving
@@ -80,10 +215,94 @@ pub trait Task: Check + std::fmt::Display + Send + Sync {
     }
     /// Run the actor loop
     async fn task(self: Box<Self>) -> std::result::Result<(), TaskError>;
+    /// Run the actor loop until completion or [Cancellation]
+    ///
+    /// The default implementation races [Task::task] against the cancellation token
+    async fn cancellable_task(
+        self: Box<Self>,
+        cancellation: Cancellation,
+    ) -> std::result::Result<(), TaskError> {
+        let name = self.name().to_string();
+        tokio::select! {
+            result = self.task() => result,
+            _ = cancellation.token().cancelled() => Err(TaskError::Cancelled(name)),
+        }
+    }
     fn as_plain(&self) -> PlainActor;
+    /// Returns the actors the task is made of
+    ///
+    /// The default implementation returns the task itself
+    fn as_plains(&self) -> Vec<PlainActor> {
+        vec![self.as_plain()]
+    }
     fn name(&self) -> &'static str {
         "dos-actors task"
     }
+    /// Returns the actors the task is made of
+    ///
+    /// The default implementation returns the task itself
+    fn into_tasks(self: Box<Self>) -> Vec<Box<dyn Task>> {
+        vec![self.into_task()]
+    }
+    /// Returns the [Sequence] of [Operation]s of the actor loop
+    fn sequence(&self) -> Sequence {
+        Default::default()
+    }
+    /// Checks if an [Operation] can be performed without waiting on a channel
+    fn is_ready(&self, _operation: Operation) -> bool {
+        true
+    }
+    /// Performs an [Operation] on the current thread
+    ///
+    /// The asynchronous clients are driven by the given single-threaded runtime
+    fn execute(
+        &mut self,
+        _operation: Operation,
+        _runtime: &tokio::runtime::Runtime,
+    ) -> std::result::Result<(), TaskError> {
+        Ok(())
+    }
+    /// Starts recording the runtime statistics of the actors the task is made of
+    fn profile(&self) -> Vec<crate::model::stats::Profiler> {
+        vec![]
+    }
+    /// Returns the provenance of the client of the task
+    fn provenance(&self) -> Vec<(String, String)> {
+        vec![]
+    }
+    /// Passes the provenance of the model to the client of the task
+    fn set_provenance(&self, _provenance: &[(String, String)]) {}
+    /// Passes the rate of the inputs of the task to the client of the task
+    fn set_inputs_rate(&self) {}
+    /// Encodes the state of the client
+    ///
+    /// Returns [None] if the client state cannot be serialized
+    #[cfg(feature = "filing")]
+    fn encode_client(&self) -> Option<interface::filing::Result<Vec<u8>>> {
+        None
+    }
+    /// Replaces the state of the client with the decoded state
+    ///
+    /// Returns [None] if the client state cannot be deserialized
+    #[cfg(feature = "filing")]
+    fn decode_client(&mut self, _bytes: &[u8]) -> Option<interface::filing::Result<()>> {
+        None
+    }
+    /// Returns the outputs with data waiting in a channel
+    #[cfg(feature = "filing")]
+    fn pending_outputs(
+        &self,
+    ) -> interface::filing::Result<Vec<crate::model::checkpoint::PendingOutput>> {
+        Ok(vec![])
+    }
+    /// Sends the data of the [Task::pending_outputs] again
+    #[cfg(feature = "filing")]
+    fn resend(
+        &mut self,
+        _pending: &[crate::model::checkpoint::PendingOutput],
+    ) -> std::result::Result<(), TaskError> {
+        Ok(())
+    }
 }
 
 /// Flowchart name
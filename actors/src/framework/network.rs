@@ -29,6 +29,15 @@ pub use outputs::{ActorOutput, ActorOutputBuilder, AddActorOutput, AddOuput};
 mod output_rx;
 pub use output_rx::{ActorOutputsError, OutputRx};
 
+/// Actors outputs taps
+mod tap;
+pub use tap::Tap;
+pub(crate) use tap::{Subscribers, Taps};
+
+/// Actors outputs plugs
+mod plug;
+pub use plug::Plug;
+
 /// Interface for actors log outputs
 mod logs;
 pub use logs::{IntoLogs, IntoLogsN};
@@ -104,14 +104,12 @@ where
 
         let mut output: Output<C, U, NO> = Output::builder(actor.client.clone())
             .bootstrap(builder.is_bootstrap())
+            .capacity(builder.capacity().first().copied().unwrap_or(1))
             .senders(txs)
             .build();
 
-        let mut hasher = DefaultHasher::new();
-        actor.who().hash(&mut hasher);
         let output_name = Who::who(&output);
-        <U as Quote>::quote().hash(&mut hasher);
-        let hash = hasher.finish();
+        let hash = output_hash::<U>(&actor.who());
         <Output<C, U, NO> as OutputObject>::set_hash(&mut output, hash);
 
         if let Some(ref mut outputs) = actor.outputs {
@@ -130,6 +128,14 @@ where
     }
 }
 
+/// Returns the hash of the output `U` of an actor
+pub(crate) fn output_hash<U: UniqueIdentifier>(actor: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    actor.hash(&mut hasher);
+    <U as Quote>::quote().hash(&mut hasher);
+    hasher.finish()
+}
+
 /* impl<'a, C, const NI: usize, const NO: usize> AddOuput<'a, C, NI, NO>
     for (&'a mut Actor<C, NI, NO>, ActorOutputBuilder)
 where
@@ -1,8 +1,15 @@
-use interface::{TryUpdate, TryWrite, UniqueIdentifier};
+use interface::{TryUpdate, TryWrite, UniqueIdentifier, Who};
 
-use crate::actor::Actor;
+use crate::{
+    actor::{
+        io::{Output, OutputObject},
+        Actor,
+    },
+    framework::network::Tap,
+    Result,
+};
 
-use super::{ActorOutputBuilder, AddOuput, OutputBuilder, OutputRx};
+use super::{output_hash, ActorOutputBuilder, AddOuput, OutputBuilder, OutputRx};
 
 /// A combination of an [Actor] with an [ActorOutputBuilder]
 ///
@@ -38,3 +45,71 @@ where
         ActorOutput::build_output(actor, builder)
     }
 }
+
+impl<C, const NI: usize, const NO: usize> ActorOutput<'_, Actor<C, NI, NO>>
+where
+    C: 'static + TryUpdate,
+{
+    /// Builds a new output which inputs are only connected through a [Tap]
+    ///
+    /// The inputs connected through the [Tap] have the channel capacity of the output,
+    /// e.g. set with [unbounded](AddOuput::unbounded)
+    pub fn tap<U>(self) -> Result<Tap<U, NO>>
+    where
+        C: TryWrite<U>,
+        U: 'static + UniqueIdentifier,
+    {
+        let Self { actor, builder } = self;
+        if !actor
+            .outputs
+            .iter_mut()
+            .flatten()
+            .any(|output| output.as_mut_any().is::<Output<C, U, NO>>())
+        {
+            let mut output: Output<C, U, NO> = Output::builder(actor.client())
+                .bootstrap(builder.is_bootstrap())
+                .capacity(builder.capacity().first().copied().unwrap_or(1))
+                .build();
+            <Output<C, U, NO> as OutputObject>::set_hash(
+                &mut output,
+                output_hash::<U>(&actor.who()),
+            );
+            actor
+                .outputs
+                .get_or_insert_with(Vec::new)
+                .push(Box::new(output));
+        }
+        actor.tap::<U>()
+    }
+}
+
+#[cfg(feature = "filing")]
+impl<C, const NI: usize, const NO: usize> ActorOutput<'_, Actor<C, NI, NO>>
+where
+    C: 'static + TryUpdate,
+{
+    /// Try to build a new output which data is saved in [Model](crate::model::Model) checkpoints
+    ///
+    /// The data sent by the output but not yet received at the time of a checkpoint is restored
+    /// into the channels of the output
+    pub fn build_checkpointable<U>(self) -> std::result::Result<(), OutputRx<U, C, NI, NO>>
+    where
+        C: TryWrite<U>,
+        U: 'static + UniqueIdentifier,
+        <U as UniqueIdentifier>::DataType: serde::Serialize + serde::de::DeserializeOwned,
+    {
+        let Self { actor, builder } = self;
+        let result = <ActorOutput<'_, Actor<C, NI, NO>> as AddOuput<'_, C, NI, NO>>::build_output(
+            &mut *actor,
+            builder,
+        );
+        if let Some(output) = actor.outputs.iter_mut().flatten().find_map(|output| {
+            output
+                .as_mut_any()
+                .downcast_mut::<crate::actor::io::Output<C, U, NO>>()
+        }) {
+            output.checkpointable();
+        }
+        result
+    }
+}
@@ -0,0 +1,184 @@
+use std::{any::type_name, marker::PhantomData};
+
+use flume::Sender;
+use interface::{TryUpdate, TryWrite, UniqueIdentifier, Who};
+
+use crate::{
+    actor::{
+        io::{Output, OutputObject, S},
+        Actor,
+    },
+    ActorError, Result,
+};
+
+/// Output plug
+///
+/// A [Plug] holds the channels of an [Actor] output to the inputs of the downstream actors.
+/// The channels are handed over to another actor output with [Plug::into_output],
+/// so the actor can be replaced with [Actor::retire_handle] while the
+/// [Model](crate::model::Model) is running,
+/// the downstream inputs staying connected as long as the [Plug] or its new output exists.
+///
+/// The inputs of a replaceable actor must be connected with [Tap](super::Tap)s,
+/// so the upstream outputs are not disconnected when the actor is retired.
+///
+/// ```
+/// use gmt_dos_actors::prelude::*;
+/// use gmt_dos_clients::{logging::Logging, sampler::Sampler, signals::Signals};
+/// use interface::UID;
+///
+/// #[derive(UID)]
+/// enum Sig {}
+/// #[derive(UID)]
+/// enum Rec {}
+///
+/// # tokio_test::block_on(async {
+/// let mut source: Initiator<_> = Signals::new(1, usize::MAX).into();
+/// let mut reconstructor: Actor<_> = Sampler::<Vec<f64>, Sig, Rec>::default().into();
+/// let logging = Logging::<f64>::default().into_arcx();
+/// let mut sink = Terminator::<_>::new(logging.clone());
+/// // the source output is only connected through taps
+/// let tap = source.add_output().tap::<Sig>()?;
+/// tap.into_input(&mut reconstructor)?;
+/// reconstructor.add_output().build::<Rec>().into_input(&mut sink)?;
+/// let plug = reconstructor.plug::<Rec>()?;
+/// let retire = reconstructor.retire_handle();
+///
+/// let mut model = model!(source, reconstructor, sink).quiet().check()?.run();
+///
+/// let mut new_reconstructor: Actor<_> = Sampler::<Vec<f64>, Sig, Rec>::default().into();
+/// tap.into_input(&mut new_reconstructor)?;
+/// plug.into_output(&mut new_reconstructor)?;
+/// retire.retire();
+/// model.attach(new_reconstructor);
+/// // ...
+/// model.cancel_handle().cancel();
+/// model.await?;
+/// # Ok::<(), anyhow::Error>(())
+/// # });
+/// ```
+pub struct Plug<U: UniqueIdentifier, const NO: usize> {
+    hash: u64,
+    output: String,
+    senders: Vec<Sender<S<U>>>,
+    rate: PhantomData<[(); NO]>,
+}
+impl<U, const NO: usize> Plug<U, NO>
+where
+    U: 'static + UniqueIdentifier,
+{
+    /// Connects the plugged inputs to the output `U` of `actor`
+    ///
+    /// The output is created if `actor` does not have one
+    pub fn into_output<C, const NI: usize>(self, actor: &mut Actor<C, NI, NO>) -> Result<()>
+    where
+        C: 'static + TryUpdate + TryWrite<U>,
+    {
+        if self.senders.iter().all(|tx| tx.is_disconnected()) {
+            return Err(ActorError::Disconnected(self.output));
+        }
+        if let Some(output) = actor
+            .outputs
+            .iter_mut()
+            .flatten()
+            .find_map(|output| output.as_mut_any().downcast_mut::<Output<C, U, NO>>())
+        {
+            output.tx_push(self.senders);
+            return Ok(());
+        }
+        let mut output: Output<C, U, NO> = Output::builder(actor.client())
+            .senders(self.senders)
+            .build();
+        <Output<C, U, NO> as OutputObject>::set_hash(&mut output, self.hash);
+        actor
+            .outputs
+            .get_or_insert_with(Vec::new)
+            .push(Box::new(output));
+        Ok(())
+    }
+}
+
+impl<C, const NI: usize, const NO: usize> Actor<C, NI, NO>
+where
+    C: 'static + TryUpdate,
+{
+    /// Returns a [Plug] on the output `U`
+    pub fn plug<U>(&mut self) -> Result<Plug<U, NO>>
+    where
+        C: TryWrite<U>,
+        U: 'static + UniqueIdentifier,
+    {
+        let who = self.who();
+        let output = self
+            .outputs
+            .iter_mut()
+            .flatten()
+            .find_map(|output| output.as_mut_any().downcast_mut::<Output<C, U, NO>>())
+            .ok_or_else(|| ActorError::NoOutput(type_name::<U>().to_string(), who))?;
+        Ok(Plug {
+            hash: output.get_hash(),
+            output: Who::who(output),
+            senders: output.senders(),
+            rate: PhantomData,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use gmt_dos_clients::{
+        logging::Logging,
+        sampler::Sampler,
+        signals::{Signal, Signals},
+    };
+    use interface::UID;
+    use std::time::Duration;
+
+    #[derive(UID)]
+    enum Ramp {}
+    #[derive(UID)]
+    enum Rec {}
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn replace() -> anyhow::Result<()> {
+        let mut source: Initiator<_> = Signals::new(1, usize::MAX)
+            .channels(Signal::Ramp { a: 1., b: 0. })
+            .into();
+        let mut reconstructor: Actor<_> = Sampler::<Vec<f64>, Ramp, Rec>::default().into();
+        let logging = Logging::<f64>::default().into_arcx();
+        let mut sink = Terminator::<_>::new(logging.clone());
+        let tap = source.add_output().tap::<Ramp>()?;
+        tap.into_input(&mut reconstructor)?;
+        reconstructor
+            .add_output()
+            .build::<Rec>()
+            .into_input(&mut sink)?;
+        let plug = reconstructor.plug::<Rec>()?;
+        let retire = reconstructor.retire_handle();
+
+        let mut model = model!(source, reconstructor, sink).quiet().check()?.run();
+
+        while logging.lock().await.len() < 10 {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+        let mut new_reconstructor: Actor<_> = Sampler::<Vec<f64>, Ramp, Rec>::default().into();
+        tap.into_input(&mut new_reconstructor)?;
+        plug.into_output(&mut new_reconstructor)?;
+        retire.retire();
+        model.attach(new_reconstructor);
+        let n = logging.lock().await.len();
+        while logging.lock().await.len() < n + 10 {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+        model.cancel_handle().cancel();
+        let model = model.await?;
+
+        let report = model.report().unwrap();
+        assert_eq!(report.retired().count(), 1);
+        assert_eq!(report.cancelled().count(), 3);
+        let data = logging.lock().await.as_slice().to_vec();
+        assert!(data.windows(2).all(|x| x[1] > x[0]));
+        Ok(())
+    }
+}
@@ -0,0 +1,228 @@
+use std::{
+    any::type_name,
+    marker::PhantomData,
+    sync::{Arc, Mutex},
+};
+
+use flume::Sender;
+use interface::{TryRead, TryUpdate, TryWrite, UniqueIdentifier, Who};
+
+use super::AddActorInput;
+use crate::{
+    actor::{
+        io::{Output, OutputObject, S},
+        Actor,
+    },
+    ActorError, Result,
+};
+
+/// Inputs subscribed to an output through a [Tap]
+pub(crate) struct Subscribers<U: UniqueIdentifier> {
+    senders: Vec<Sender<S<U>>>,
+    capacity: usize,
+    closed: bool,
+}
+impl<U: UniqueIdentifier> Subscribers<U> {
+    /// Creates an empty list of inputs which channels have the given capacity
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            senders: Vec::new(),
+            capacity,
+            closed: false,
+        }
+    }
+    /// Returns the channels of the subscribed inputs
+    pub fn senders(&self) -> Vec<Sender<S<U>>> {
+        self.senders.clone()
+    }
+    /// Returns the number of subscribed inputs
+    pub fn len(&self) -> usize {
+        self.senders.len()
+    }
+    /// Removes the inputs that have been dropped
+    pub fn prune(&mut self) {
+        self.senders.retain(|tx| !tx.is_disconnected());
+    }
+    /// Disconnects all the inputs and rejects new ones
+    pub fn close(&mut self) {
+        self.senders.clear();
+        self.closed = true;
+    }
+    /// Checks if all the channels can accept some data or if any is disconnected
+    pub fn is_ready(&self) -> bool {
+        self.senders
+            .iter()
+            .all(|tx| !tx.is_full() || tx.is_disconnected())
+    }
+}
+
+pub(crate) type Taps<U> = Arc<Mutex<Subscribers<U>>>;
+
+/// Output tap
+///
+/// A [Tap] is a handle on an [Actor] output that accepts new inputs
+/// while the [Model](crate::model::Model) is running.
+/// A new input receives the output data from the next time the output is sent,
+/// and it is removed from the output when the receiving actor is dropped.
+/// The channel of a new input has the same capacity than the channels of the output.
+///
+/// An output only connected through taps is built with [ActorOutput::tap](super::ActorOutput::tap).
+///
+/// ```
+/// use gmt_dos_actors::prelude::*;
+/// use gmt_dos_clients::{logging::Logging, signals::Signals};
+/// use interface::UID;
+///
+/// #[derive(UID)]
+/// enum Sig {}
+///
+/// # tokio_test::block_on(async {
+/// let mut source: Initiator<_> = Signals::new(1, 1000).into();
+/// let logging = Logging::<f64>::default().into_arcx();
+/// let mut sink = Terminator::<_>::new(logging.clone());
+/// source.add_output().build::<Sig>().into_input(&mut sink)?;
+/// let tap = source.tap::<Sig>()?;
+///
+/// let mut model = model!(source, sink).quiet().check()?.run();
+///
+/// let scope = Logging::<f64>::default().into_arcx();
+/// let mut probe = Terminator::<_>::new(scope.clone());
+/// tap.into_input(&mut probe)?;
+/// let probe = model.attach(probe);
+/// // ...
+/// probe.retire();
+/// model.await?;
+/// # Ok::<(), anyhow::Error>(())
+/// # });
+/// ```
+pub struct Tap<U: UniqueIdentifier, const NO: usize> {
+    hash: u64,
+    output: String,
+    subscribers: Taps<U>,
+    rate: PhantomData<[(); NO]>,
+}
+impl<U: UniqueIdentifier, const NO: usize> Clone for Tap<U, NO> {
+    fn clone(&self) -> Self {
+        Self {
+            hash: self.hash,
+            output: self.output.clone(),
+            subscribers: self.subscribers.clone(),
+            rate: PhantomData,
+        }
+    }
+}
+impl<U, const NO: usize> Tap<U, NO>
+where
+    U: 'static + UniqueIdentifier,
+{
+    /// Creates a new input for `actor` connected to the tapped output
+    ///
+    /// The input channel has the capacity of the output channels
+    pub fn into_input<CI, const N: usize>(
+        &self,
+        actor: &mut impl AddActorInput<U, CI, NO, N>,
+    ) -> Result<()>
+    where
+        CI: 'static + TryRead<U>,
+    {
+        let mut subscribers = self.subscribers.lock().unwrap();
+        if subscribers.closed {
+            return Err(ActorError::Disconnected(self.output.clone()));
+        }
+        let (tx, rx) = if subscribers.capacity == usize::MAX {
+            flume::unbounded::<S<U>>()
+        } else {
+            flume::bounded::<S<U>>(subscribers.capacity)
+        };
+        subscribers.senders.push(tx);
+        actor.add_input(rx, self.hash);
+        Ok(())
+    }
+}
+
+impl<C, const NI: usize, const NO: usize> Actor<C, NI, NO>
+where
+    C: 'static + TryUpdate,
+{
+    /// Returns a [Tap] on the output `U`
+    pub fn tap<U>(&mut self) -> Result<Tap<U, NO>>
+    where
+        C: TryWrite<U>,
+        U: 'static + UniqueIdentifier,
+    {
+        let who = self.who();
+        let output = self
+            .outputs
+            .iter_mut()
+            .flatten()
+            .find_map(|output| output.as_mut_any().downcast_mut::<Output<C, U, NO>>())
+            .ok_or_else(|| ActorError::NoOutput(type_name::<U>().to_string(), who))?;
+        Ok(Tap {
+            hash: output.get_hash(),
+            output: Who::who(output),
+            subscribers: output.taps(),
+            rate: PhantomData,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use gmt_dos_clients::{
+        logging::Logging,
+        signals::{Signal, Signals},
+    };
+    use interface::UID;
+    use std::time::Duration;
+
+    #[derive(UID)]
+    enum Ramp {}
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn attach_and_retire() -> anyhow::Result<()> {
+        let mut source: Initiator<_> = Signals::new(1, usize::MAX)
+            .channels(Signal::Ramp { a: 1., b: 0. })
+            .into();
+        let mut sink = Terminator::<_>::new(Logging::<f64>::default().into_arcx());
+        source.add_output().build::<Ramp>().into_input(&mut sink)?;
+        let tap = source.tap::<Ramp>()?;
+
+        let mut model = model!(source, sink).quiet().check()?.run();
+
+        let logging = Logging::<f64>::default().into_arcx();
+        let mut probe = Terminator::<_>::new(logging.clone());
+        tap.into_input(&mut probe)?;
+        let probe = model.attach(probe);
+        while logging.lock().await.len() < 10 {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+        probe.retire();
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        model.cancel_handle().cancel();
+        let model = model.await?;
+
+        let report = model.report().unwrap();
+        assert_eq!(report.retired().count(), 1);
+        assert_eq!(report.cancelled().count(), 2);
+        let data = logging.lock().await.as_slice().to_vec();
+        assert!(data.windows(2).all(|x| x[1] - x[0] == 1.));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn tapped_output() -> anyhow::Result<()> {
+        let mut source: Initiator<_> = Signals::new(1, 10)
+            .channels(Signal::Ramp { a: 1., b: 0. })
+            .into();
+        let logging = Logging::<f64>::default().into_arcx();
+        let mut sink = Terminator::<_>::new(logging.clone());
+        let tap = source.add_output().unbounded().tap::<Ramp>()?;
+        tap.into_input(&mut sink)?;
+
+        model!(source, sink).quiet().check()?.run().await?;
+
+        assert_eq!(logging.lock().await.len(), 10);
+        Ok(())
+    }
+}
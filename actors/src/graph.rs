@@ -10,7 +10,10 @@ use std::{
     sync::{LazyLock, Mutex},
 };
 
-use crate::{model::PlainModel, trim};
+use crate::{
+    model::{Diagnostics, ModelStats, PlainModel},
+    trim,
+};
 mod render;
 pub use render::{Render, RenderError};
 
@@ -136,6 +139,16 @@ pub struct Graph {
     pub(crate) name: String,
     actors: PlainModel,
     to_dot: bool,
+    annotations: Vec<Annotation>,
+    highlights: Vec<u64>,
+}
+
+/// Runtime statistics of an actor displayed on the [Graph]
+#[derive(Debug, Hash, Default, Clone)]
+struct Annotation {
+    hash: u64,
+    label: String,
+    heat: usize,
 }
 impl Graph {
     pub fn new(name: String, actors: impl Into<PlainModel>) -> Self {
@@ -150,6 +163,68 @@ impl Graph {
             name,
             actors,
             to_dot: env::var("TO_DOT").is_ok(),
+            annotations: Vec::new(),
+            highlights: Vec::new(),
+        }
+    }
+    /// Annotates the actors with their runtime statistics
+    ///
+    /// The mean time spent updating the client is added to the actor label
+    /// and the actor is colored according to the total time spent updating the client,
+    /// relative to the actor with the largest update time
+    pub fn annotate(mut self, stats: &ModelStats) -> Self {
+        let max_update = stats
+            .iter()
+            .map(|stats| stats.update.total)
+            .max()
+            .unwrap_or_default();
+        self.annotations = self
+            .actors
+            .iter()
+            .filter_map(|actor| {
+                let mut hashes: Vec<_> = actor
+                    .inputs
+                    .iter()
+                    .chain(actor.outputs.iter())
+                    .flatten()
+                    .map(|io| io.hash())
+                    .collect();
+                hashes.sort_unstable();
+                stats
+                    .iter()
+                    .find(|stats| {
+                        let mut stats_hashes = stats.hashes.clone();
+                        stats_hashes.sort_unstable();
+                        trim(&stats.name) == actor.client && stats_hashes == hashes
+                    })
+                    .map(|stats| Annotation {
+                        hash: actor.hash,
+                        label: format!("{:.1?}", stats.update.mean()),
+                        heat: if max_update.is_zero() {
+                            1
+                        } else {
+                            1 + (8. * stats.update.total.as_secs_f64() / max_update.as_secs_f64())
+                                .round() as usize
+                        },
+                    })
+            })
+            .collect();
+        self
+    }
+    /// Highlights the connections involved in the issues found by the model [Diagnostics]
+    pub fn highlight(mut self, diagnostics: &Diagnostics) -> Self {
+        self.highlights = diagnostics
+            .iter()
+            .flat_map(|diagnostic| diagnostic.hashes())
+            .collect();
+        self
+    }
+    /// Formats a connection, in red if it is highlighted
+    fn connection(&self, hash: u64, edge: String) -> String {
+        if self.highlights.contains(&hash) {
+            edge.replacen("];", r#", color=red, penwidth=2];"#, 1)
+        } else {
+            edge
         }
     }
     /// Returns the diagram in the [Graphviz](https://www.graphviz.org/) dot language
@@ -164,7 +239,10 @@ impl Graph {
                         .iter()
                         .map(|input| {
                             let color = color_map.lock().unwrap().get(input.rate());
-                            input.as_formatted_input(actor.hash, color)
+                            self.connection(
+                                input.hash(),
+                                input.as_formatted_input(actor.hash, color),
+                            )
                         })
                         .collect::<Vec<String>>()
                 })
@@ -180,7 +258,10 @@ impl Graph {
                         .iter()
                         .map(|output| {
                             let color = color_map.lock().unwrap().get(output.rate());
-                            output.as_formatted_output(actor.hash, color)
+                            self.connection(
+                                output.hash(),
+                                output.as_formatted_output(actor.hash, color),
+                            )
                         })
                         .collect::<Vec<String>>()
                 })
@@ -191,13 +272,26 @@ impl Graph {
             self.actors
                 .iter()
                 .map(|actor| {
+                    let (label, heat) = self
+                        .annotations
+                        .iter()
+                        .find(|annotation| annotation.hash == actor.hash)
+                        .map_or_else(
+                            || (actor.client.clone(), String::new()),
+                            |annotation| {
+                                (
+                                    format!(r#"{}\n{}"#, actor.client, annotation.label),
+                                    format!(", colorscheme=reds9, fillcolor={}", annotation.heat),
+                                )
+                            },
+                        );
                     if let Some(image) = actor.image.as_ref() {
                         format!(
-                            r#"{} [label="{}", labelloc=t, image="{}"]"#,
-                            actor.hash, actor.client, image
+                            r#"{} [label="{}", labelloc=t, image="{}"{}]"#,
+                            actor.hash, label, image, heat
                         )
                     } else {
-                        format!(r#"{} [label="{}"]"#, actor.hash, actor.client)
+                        format!(r#"{} [label="{}"{}]"#, actor.hash, label, heat)
                     }
                 })
                 .collect::<Vec<String>>()
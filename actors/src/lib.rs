@@ -117,6 +117,10 @@ pub enum ActorError {
     NoOutputsPositiveRate(String),
     #[error(r#"Orphan output "{0}" in "{1}" actor"#)]
     OrphanOutput(String, String),
+    #[error(r#"no output "{0}" in "{1}" actor"#)]
+    NoOutput(String, String),
+    #[error(r#"no input "{0}" in "{1}" actor"#)]
+    NoInput(String, String),
     #[error(transparent)]
     Other(#[from] Box<dyn std::error::Error + Send + Sync>),
     #[error(transparent)]
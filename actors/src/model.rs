@@ -10,6 +10,11 @@ The model has 4 states:
  3. [Running]: model state while all the actors are performing their respective tasks, the model can move to the [Running] state only from the [Ready] state
  4. [Completed]: model state after the succesful completion of the tasks of all the actors, the model can move to the [Completed] state only from the [Running] state
 
+Alternatively, a [Ready] model can move to the [Stepping] state where the actors are executed in a deterministic order
+on the current thread, one step at a time (see [stepping]).
+With the `filing` feature, a [Stepping] or a [Running] model can be saved to a [checkpoint](mod@checkpoint)
+and a [Stepping] model can be restored from it.
+
 # Example
 
 A 3 actors model with [Signals], [Sampler] and [Logging] clients is build with:
@@ -167,7 +172,7 @@ let data: &[f64]  = &logging.lock().await;
 [Logging]: https://docs.rs/gmt_dos-clients/latest/gmt_dos_clients/logging/struct.Logging.html
 */
 
-use crate::framework::model::{CheckError, Task, TaskError};
+use crate::framework::model::{Cancellation, CheckError, Task, TaskError};
 use std::{fmt::Display, marker::PhantomData, time::Instant};
 
 mod flowchart;
@@ -185,6 +190,24 @@ pub enum ModelError {
     Task(#[from] Box<TaskError>),
     #[error("error in Check implementation")]
     Check(#[from] Box<CheckError>),
+    #[error("{0} output rate ({1}) does not match {2} input rate ({3})")]
+    RateMismatch(String, usize, String, usize),
+    #[error("model configuration errors:\n{0}")]
+    Diagnostics(Diagnostics),
+    #[error("model stalled at step {0}, actors waiting on each other: {1}")]
+    Stalled(usize, String),
+    #[error(transparent)]
+    Failure(Box<ModelFailure>),
+    #[error("failed to write the model provenance")]
+    Provenance(#[from] std::io::Error),
+    #[error("failed to build the runtime of the stepping model")]
+    Runtime(#[source] std::io::Error),
+    #[cfg(feature = "filing")]
+    #[error("checkpoint filing failed")]
+    Filing(#[from] interface::filing::FilingError),
+    #[cfg(feature = "filing")]
+    #[error("checkpoint does not match the model: {0}")]
+    Checkpoint(String),
 }
 
 type Result<T> = std::result::Result<T, ModelError>;
@@ -197,6 +220,8 @@ pub enum Ready {}
 pub enum Running {}
 /// [Model] final state
 pub enum Completed {}
+/// [Model] deterministic execution state
+pub enum Stepping {}
 
 type Actors = Vec<Box<dyn Task>>;
 
@@ -204,7 +229,11 @@ type Actors = Vec<Box<dyn Task>>;
 pub struct Model<State> {
     pub(crate) name: Option<String>,
     pub(crate) actors: Option<Actors>,
-    pub(crate) task_set: Option<JoinSet<std::result::Result<(), TaskError>>>,
+    pub(crate) task_set: Option<JoinSet<(String, std::result::Result<(), TaskError>)>>,
+    pub(crate) schedule: Option<stepping::Schedule>,
+    pub(crate) token: Option<Cancellation>,
+    pub(crate) report: Option<Report>,
+    pub(crate) stats: Option<Vec<stats::Profiler>>,
     pub(crate) state: PhantomData<State>,
     pub(crate) start: Instant,
     pub(crate) verbose: bool,
@@ -271,6 +300,19 @@ impl UnknownOrReady for Ready {}
 mod plain;
 pub mod ready;
 pub mod running;
+pub mod stepping;
+#[cfg(feature = "filing")]
+pub mod checkpoint;
+mod report;
+pub use report::{Outcome, Report};
+pub mod stats;
+pub use stats::ModelStats;
+pub mod diagnostics;
+pub use diagnostics::{Diagnostic, Diagnostics, Severity};
+mod failure;
+pub use failure::ModelFailure;
+pub mod provenance;
+pub use provenance::Provenance;
 pub mod unknown;
 pub use plain::PlainModel;
 
@@ -0,0 +1,522 @@
+/*!
+# Model checkpoint
+
+A [Model]`<`[Stepping]`>` can be saved to a file in between 2 steps with [checkpoint](Model::checkpoint)
+and an identically wired model can resume from that point with [restore](Model::restore).
+
+The checkpoint records the number of steps, the position of each actor in its loop
+and the state of the clients of the actors that have been made [checkpointable](crate::actor::Actor::checkpointable).
+The clients of the other actors are left in their initial state when the model is restored.
+
+The data sent by an actor but not yet received by another actor at the time of the checkpoint,
+e.g. the data of the bootstrapped outputs, is saved if the output has been built with
+[build_checkpointable](crate::framework::network::ActorOutput::build_checkpointable),
+and it is sent again to the same inputs when the model is restored.
+The checkpoint fails if some data is waiting in the channel of any other output.
+
+A [Running] model is saved with the asynchronous `checkpoint` method:
+the actors are paused at the same sample, where a [Stepping] model would have been paused in between 2 steps,
+and they resume once their state has been saved.
+Either way, the checkpoint is restored into a [Stepping] model.
+
+```
+use gmt_dos_actors::prelude::*;
+use gmt_dos_clients::{integrator::Integrator, logging::Logging, signals::Signals};
+use interface::UID;
+
+#[derive(UID)]
+enum U {}
+#[derive(UID)]
+enum Y {}
+
+fn build(logging: &std::sync::Arc<tokio::sync::Mutex<Logging<f64>>>)
+    -> anyhow::Result<Model<model::Ready>> {
+    let mut source: Initiator<_> = Actor::from(Signals::new(1, 20)).checkpointable();
+    let mut integrator: Actor<_> = Actor::from(Integrator::<U>::new(1).gain(0.5)).checkpointable();
+    let mut sink = Terminator::<_>::new(logging.clone());
+    source.add_output().build_checkpointable::<U>().into_input(&mut integrator)?;
+    integrator.add_output().build_checkpointable::<Y>().into_input(&mut sink)?;
+    Ok(model!(source, integrator, sink).quiet().check()?)
+}
+
+let path = std::env::temp_dir().join("checkpoint.bin");
+let logging = Logging::<f64>::default().into_arcx();
+build(&logging)?
+    .stepping()?
+    .step_n(10)?
+    .checkpoint(&path)?;
+
+let logging = Logging::<f64>::default().into_arcx();
+let mut model = build(&logging)?.stepping()?;
+model.restore(&path)?;
+model.wait()?;
+# Ok::<(), anyhow::Error>(())
+```
+*/
+
+use super::{stepping, Model, ModelError, Result, Running, Stepping};
+use crate::{actor::PlainActor, framework::model::Task};
+use interface::filing::{Codec, Filing};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::Debug,
+    hash::{DefaultHasher, Hash, Hasher},
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+};
+use tokio::sync::{oneshot, watch};
+
+/// Actor state in a [Checkpoint]
+#[derive(Debug, Serialize, Deserialize)]
+struct ActorState {
+    name: String,
+    completed: bool,
+    cursor: usize,
+    client: Option<Vec<u8>>,
+    pending: Vec<PendingOutput>,
+}
+
+impl ActorState {
+    fn completed(name: &str, cursor: usize) -> Self {
+        Self {
+            name: name.to_string(),
+            completed: true,
+            cursor,
+            client: None,
+            pending: vec![],
+        }
+    }
+    fn new(name: &str, cursor: usize, task: &dyn Task) -> Result<Self> {
+        let client = task.encode_client().transpose()?;
+        if client.is_none() {
+            log::warn!("{} client state is not saved to checkpoint", name);
+        }
+        let pending = task.pending_outputs()?;
+        if let Some(output) = pending.iter().find(|output| output.data.is_none()) {
+            return Err(ModelError::Checkpoint(format!(
+                "{} output {} has pending data that cannot be saved",
+                name, output.name
+            )));
+        }
+        Ok(Self {
+            name: name.to_string(),
+            completed: false,
+            cursor,
+            client,
+            pending,
+        })
+    }
+}
+
+/// Data sent by an output and waiting in some channels
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PendingOutput {
+    pub(crate) name: String,
+    pub(crate) hash: u64,
+    pub(crate) channels: Vec<bool>,
+    pub(crate) data: Option<Vec<u8>>,
+}
+
+/// [Model] checkpoint
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Checkpoint {
+    step: usize,
+    actors: Vec<ActorState>,
+}
+impl Codec for Checkpoint {}
+impl Checkpoint {
+    /// Returns the number of steps performed by the model before the checkpoint
+    pub fn step(&self) -> usize {
+        self.step
+    }
+}
+
+impl Model<Stepping> {
+    /// Saves the state of the model to a file
+    pub fn checkpoint<P>(&mut self, path: P) -> Result<&mut Self>
+    where
+        P: AsRef<Path> + Debug,
+    {
+        let schedule = self.schedule();
+        let actors = schedule
+            .steppers
+            .iter()
+            .map(|stepper| {
+                let Some(task) = stepper.task.as_ref() else {
+                    return Ok(ActorState::completed(&stepper.name, stepper.cursor));
+                };
+                ActorState::new(&stepper.name, stepper.cursor, task.as_ref())
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Checkpoint {
+            step: schedule.step,
+            actors,
+        }
+        .to_path(path)?;
+        Ok(self)
+    }
+    /// Restores the state of the model from a file
+    ///
+    /// The model must be wired identically to the model the checkpoint has been saved from
+    /// and it must not have been stepped yet
+    pub fn restore<P>(&mut self, path: P) -> Result<&mut Self>
+    where
+        P: AsRef<Path> + Debug,
+    {
+        if self.step_count() > 0 {
+            return Err(ModelError::Checkpoint(format!(
+                "the model has already performed {} steps",
+                self.step_count()
+            )));
+        }
+        let checkpoint = Checkpoint::from_path(path)?;
+        let schedule = self.schedule();
+        if schedule.steppers.len() != checkpoint.actors.len() {
+            return Err(ModelError::Checkpoint(format!(
+                "expected {} actors, found {}",
+                checkpoint.actors.len(),
+                schedule.steppers.len()
+            )));
+        }
+        for (stepper, state) in schedule.steppers.iter_mut().zip(&checkpoint.actors) {
+            if stepper.name != state.name {
+                return Err(ModelError::Checkpoint(format!(
+                    "expected actor {}, found {}",
+                    state.name, stepper.name
+                )));
+            }
+            stepper.cursor = state.cursor;
+            let (Some(task), Some(bytes)) = (stepper.task.as_mut(), state.client.as_ref()) else {
+                continue;
+            };
+            if task.decode_client(bytes).transpose()?.is_none() {
+                log::warn!(
+                    "{} client state is not restored from checkpoint",
+                    stepper.name
+                );
+            }
+        }
+        for (stepper, state) in schedule.steppers.iter_mut().zip(&checkpoint.actors) {
+            if let Some(task) = stepper.task.as_mut() {
+                task.resend(&state.pending).map_err(Box::new)?;
+            }
+        }
+        // dropping the completed tasks disconnects their inputs and outputs
+        for (stepper, state) in schedule.steppers.iter_mut().zip(&checkpoint.actors) {
+            if state.completed {
+                stepper.task = None;
+            }
+        }
+        schedule.step = checkpoint.step;
+        Ok(self)
+    }
+}
+
+impl Model<Running> {
+    /// Saves the state of the running model to a file
+    ///
+    /// The actors are paused before the first sample that none of them has reached yet,
+    /// their state is saved and they resume.
+    /// The checkpoint is restored into a [Stepping] model with [restore](Model::restore).
+    ///
+    /// The actors [attached](Model::attach) to the running model are not part of the checkpoint
+    pub async fn checkpoint<P>(&mut self, path: P) -> Result<&mut Self>
+    where
+        P: AsRef<Path> + Debug,
+    {
+        let cancellation = self.token.clone().unwrap_or_default();
+        let Some(rendezvous) = cancellation.rendezvous() else {
+            return Err(ModelError::Checkpoint(
+                "the model has not been spawned for checkpointing".into(),
+            ));
+        };
+        let checkpoint = tokio::select! {
+            checkpoint = rendezvous.request() => checkpoint?,
+            _ = cancellation.token().cancelled() => {
+                return Err(ModelError::Checkpoint("the model has been cancelled".into()))
+            }
+        };
+        checkpoint.to_path(path)?;
+        Ok(self)
+    }
+}
+
+/// Identifies an actor by its client and by its inputs and outputs
+fn identity(actor: &PlainActor) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    actor.client.hash(&mut hasher);
+    for io in actor.inputs.iter().chain(actor.outputs.iter()).flatten() {
+        io.hash().hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Checkpoint requests to the actors of a running [Model]
+///
+/// Each actor records the sample index of the operation it is about to perform.
+/// When a checkpoint is requested, the checkpoint sample is set just after the largest recorded index,
+/// so every operation in progress completes before the checkpoint,
+/// and each actor pauses before its first operation at or after the checkpoint sample,
+/// where a [Stepping] model would have paused.
+/// The actors resume once all the actors still running have paused.
+#[derive(Debug, Clone)]
+pub(crate) struct Rendezvous(Arc<Shared>);
+#[derive(Debug)]
+struct Shared {
+    // name and identity of the actors in the order of the stepping schedule
+    actors: Vec<(String, u64)>,
+    progress: Vec<AtomicUsize>,
+    joined: AtomicUsize,
+    requested: AtomicBool,
+    pending: std::sync::Mutex<Pending>,
+    changes: watch::Sender<()>,
+}
+#[derive(Debug)]
+struct Pending {
+    live: usize,
+    request: Option<Request>,
+}
+#[derive(Debug)]
+struct Request {
+    step: usize,
+    // slot and identity of the paused actors with their state
+    states: Vec<(usize, u64, Result<ActorState>)>,
+    reply: Option<oneshot::Sender<(usize, Vec<(usize, u64, Result<ActorState>)>)>>,
+}
+impl Rendezvous {
+    /// Creates the rendezvous of the given actors
+    pub(crate) fn new(actors: &[PlainActor]) -> Self {
+        let actors: Vec<_> = stepping::order(actors)
+            .into_iter()
+            .map(|i| (actors[i].client.clone(), identity(&actors[i])))
+            .collect();
+        Self(Arc::new(Shared {
+            progress: actors.iter().map(|_| AtomicUsize::new(0)).collect(),
+            joined: AtomicUsize::new(0),
+            requested: AtomicBool::new(false),
+            pending: std::sync::Mutex::new(Pending {
+                live: actors.len(),
+                request: None,
+            }),
+            changes: watch::channel(()).0,
+            actors,
+        }))
+    }
+    /// Registers an actor
+    ///
+    /// Returns [None] if all the actors the rendezvous has been created for are already registered
+    pub(crate) fn join(&self, actor: &PlainActor) -> Option<Member> {
+        let slot = self.0.joined.fetch_add(1, Ordering::SeqCst);
+        (slot < self.0.progress.len()).then(|| Member {
+            shared: self.0.clone(),
+            slot,
+            identity: identity(actor),
+        })
+    }
+    /// Requests a checkpoint and waits for all the actors to pause
+    async fn request(&self) -> Result<Checkpoint> {
+        let (reply, states) = oneshot::channel();
+        {
+            let mut pending = self.0.pending.lock().unwrap();
+            if pending.request.is_some() {
+                return Err(ModelError::Checkpoint(
+                    "a checkpoint is already in progress".into(),
+                ));
+            }
+            self.0.requested.store(true, Ordering::SeqCst);
+            let step = self
+                .0
+                .progress
+                .iter()
+                .map(|progress| progress.load(Ordering::SeqCst))
+                .max()
+                .map_or(0, |i| i + 1);
+            pending.request = Some(Request {
+                step,
+                states: vec![],
+                reply: Some(reply),
+            });
+            self.0.complete(&mut pending);
+        }
+        self.0.changes.send_replace(());
+        let (step, mut states) = states
+            .await
+            .map_err(|_| ModelError::Checkpoint("the checkpoint has been abandoned".into()))?;
+        let actors = self
+            .0
+            .actors
+            .iter()
+            .map(|(name, identity)| {
+                match states.iter().position(|(_, other, _)| other == identity) {
+                    Some(i) => states.remove(i).2,
+                    None => Ok(ActorState::completed(name, 0)),
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Checkpoint { step, actors })
+    }
+}
+impl Shared {
+    /// Replies to the request once all the running actors have paused
+    fn complete(&self, pending: &mut Pending) -> bool {
+        if pending
+            .request
+            .as_ref()
+            .is_none_or(|request| request.states.len() < pending.live)
+        {
+            return false;
+        }
+        let mut request = pending.request.take().unwrap();
+        self.requested.store(false, Ordering::SeqCst);
+        if let Some(reply) = request.reply.take() {
+            let _ = reply.send((request.step, request.states));
+        }
+        true
+    }
+}
+
+/// Actor registered to a [Rendezvous]
+#[derive(Debug)]
+pub(crate) struct Member {
+    shared: Arc<Shared>,
+    slot: usize,
+    identity: u64,
+}
+impl Member {
+    /// Pauses the actor before an operation at sample `index` if a checkpoint is requested
+    ///
+    /// The state of the actor is saved with the `cursor` of the operation in the actor [Sequence](crate::framework::model::Sequence)
+    pub(crate) async fn pause(&self, index: usize, cursor: usize, task: &dyn Task) {
+        let shared = &self.shared;
+        shared.progress[self.slot].store(index, Ordering::SeqCst);
+        if !shared.requested.load(Ordering::SeqCst) {
+            return;
+        }
+        let mut changes = shared.changes.subscribe();
+        loop {
+            {
+                let mut pending = shared.pending.lock().unwrap();
+                let Some(request) = pending.request.as_mut() else {
+                    return;
+                };
+                if index < request.step {
+                    return;
+                }
+                if request.states.iter().all(|(slot, ..)| *slot != self.slot) {
+                    let state = ActorState::new(&task.as_plain().client, cursor, task);
+                    request.states.push((self.slot, self.identity, state));
+                }
+                if shared.complete(&mut pending) {
+                    shared.changes.send_replace(());
+                    return;
+                }
+            }
+            let _ = changes.changed().await;
+        }
+    }
+}
+impl Drop for Member {
+    fn drop(&mut self) {
+        self.shared.progress[self.slot].store(0, Ordering::SeqCst);
+        let mut pending = self.shared.pending.lock().unwrap();
+        pending.live -= 1;
+        if let Some(request) = pending.request.as_mut() {
+            request.states.retain(|(slot, ..)| *slot != self.slot);
+        }
+        if self.shared.complete(&mut pending) {
+            self.shared.changes.send_replace(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use gmt_dos_clients::{
+        integrator::Integrator,
+        logging::Logging,
+        signals::{Signal, Signals},
+    };
+    use interface::UID;
+    use std::sync::Arc;
+    use tokio::sync::Mutex;
+
+    #[derive(UID)]
+    enum U {}
+    #[derive(UID)]
+    enum Y {}
+
+    fn build(logging: &Arc<Mutex<Logging<f64>>>) -> anyhow::Result<Model<model::Ready>> {
+        let mut source: Initiator<_> =
+            Actor::from(Signals::new(1, 20).channels(Signal::Ramp { a: 1., b: 0. }))
+                .checkpointable();
+        let mut integrator: Actor<_> =
+            Actor::from(Integrator::<U>::new(1).gain(0.5)).checkpointable();
+        let mut sink = Terminator::<_>::new(logging.clone());
+        source
+            .add_output()
+            .build_checkpointable::<U>()
+            .into_input(&mut integrator)?;
+        integrator
+            .add_output()
+            .bootstrap()
+            .build_checkpointable::<Y>()
+            .into_input(&mut sink)?;
+        Ok(model!(source, integrator, sink).quiet().check()?)
+    }
+
+    #[test]
+    fn checkpoint() -> anyhow::Result<()> {
+        let path = std::env::temp_dir().join("gmt_dos-actors_checkpoint.bin");
+
+        let logging = Logging::<f64>::default().into_arcx();
+        let mut model = build(&logging)?.stepping()?;
+        model.step_n(7)?.checkpoint(&path)?;
+        let n = logging.blocking_lock().len();
+        model.wait()?;
+        let expected = logging.blocking_lock().as_slice()[n..].to_vec();
+
+        let logging = Logging::<f64>::default().into_arcx();
+        let mut model = build(&logging)?.stepping()?;
+        model.restore(&path)?;
+        assert_eq!(model.step_count(), 7);
+        model.wait()?;
+        assert_eq!(*logging.blocking_lock().as_slice(), expected);
+
+        std::fs::remove_file(path)?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn running_checkpoint() -> anyhow::Result<()> {
+        let path = std::env::temp_dir().join("gmt_dos-actors_running-checkpoint.bin");
+
+        let logging = Logging::<f64>::default().into_arcx();
+        let mut model = build(&logging)?.run();
+        model.checkpoint(&path).await?;
+        model.await?;
+        let expected = logging.lock().await.as_slice().to_vec();
+
+        let logging = Logging::<f64>::default().into_arcx();
+        let model = build(&logging)?;
+        let checkpoint = path.clone();
+        let step = tokio::task::spawn_blocking(move || {
+            let mut model = model.stepping()?;
+            model.restore(&checkpoint)?;
+            let step = model.step_count();
+            model.wait()?;
+            anyhow::Ok(step)
+        })
+        .await??;
+        assert!(step > 0);
+        let restored = logging.lock().await.as_slice().to_vec();
+        assert!(!restored.is_empty());
+        assert_eq!(restored, expected[expected.len() - restored.len()..]);
+
+        std::fs::remove_file(path)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,453 @@
+/*!
+# Model static analysis
+
+The [diagnose](PlainModel::diagnose) method walks the graph of a [PlainModel]
+and reports the configuration issues that would either fail or hang the model at run time:
+ - feedback loops without any bootstrapped output,
+ - outputs and inputs which sampling rates do not match,
+ - outputs without inputs and inputs without outputs,
+ - unbounded channels within a feedback loop.
+
+A [Model](super::Model) is diagnosed with [diagnostics](super::Model::diagnostics)
+and the connections involved in the issues can be highlighted in the model [flowchart](crate::graph::Graph::highlight).
+
+```
+use gmt_dos_actors::prelude::*;
+use gmt_dos_clients::{logging::Logging, signals::Signals};
+use interface::UID;
+
+#[derive(UID)]
+enum Sig {}
+
+let mut source: Initiator<_> = Signals::new(1, 10).into();
+let mut sink = Terminator::<_>::new(Logging::<f64>::default().into_arcx());
+source.add_output().build::<Sig>().into_input(&mut sink)?;
+
+let model = model!(source, sink);
+let diagnostics = model.diagnostics();
+assert!(diagnostics.is_empty());
+println!("{diagnostics}");
+# Ok::<(), anyhow::Error>(())
+```
+*/
+
+use std::{collections::HashMap, fmt::Display};
+
+use super::{Model, PlainModel, UnknownOrReady};
+use crate::actor::plain::IO;
+
+/// Severity of a [Diagnostic]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// The model may not behave as expected
+    Warning,
+    /// The model will fail or hang
+    Error,
+}
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// Model configuration issue
+#[derive(Debug, Clone, PartialEq)]
+pub enum Diagnostic {
+    /// Feedback loop without any bootstrapped output
+    ///
+    /// The actors in the loop are waiting on each other forever
+    Deadlock {
+        /// Actors in the loop
+        actors: Vec<String>,
+        /// Hashes of the connections in the loop
+        hashes: Vec<u64>,
+    },
+    /// Output and input sampling rates mismatch
+    RateMismatch {
+        /// Output actor and name
+        output: String,
+        /// Output sampling rate
+        output_rate: usize,
+        /// Input actor and name
+        input: String,
+        /// Input sampling rate
+        input_rate: usize,
+        /// Connection hash
+        hash: u64,
+    },
+    /// Output without any input
+    OrphanOutput {
+        /// Output actor and name
+        output: String,
+        /// Output hash
+        hash: u64,
+    },
+    /// Input without any output
+    OrphanInput {
+        /// Input actor and name
+        input: String,
+        /// Input hash
+        hash: u64,
+    },
+    /// Unbounded channel within a feedback loop
+    ///
+    /// The channel buffer grows as long as the sender is ahead of the receiver
+    UnboundedLoop {
+        /// Input actor and name
+        input: String,
+        /// Connection hash
+        hash: u64,
+    },
+}
+impl Diagnostic {
+    /// Returns the [Severity] of the issue
+    pub fn severity(&self) -> Severity {
+        match self {
+            Diagnostic::Deadlock { .. }
+            | Diagnostic::RateMismatch { .. }
+            | Diagnostic::OrphanInput { .. } => Severity::Error,
+            Diagnostic::OrphanOutput { .. } | Diagnostic::UnboundedLoop { .. } => Severity::Warning,
+        }
+    }
+    /// Returns the hashes of the connections involved in the issue
+    pub fn hashes(&self) -> Vec<u64> {
+        match self {
+            Diagnostic::Deadlock { hashes, .. } => hashes.clone(),
+            Diagnostic::RateMismatch { hash, .. }
+            | Diagnostic::OrphanOutput { hash, .. }
+            | Diagnostic::OrphanInput { hash, .. }
+            | Diagnostic::UnboundedLoop { hash, .. } => vec![*hash],
+        }
+    }
+}
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: ", self.severity())?;
+        match self {
+            Diagnostic::Deadlock { actors, .. } => write!(
+                f,
+                "feedback loop without bootstrapped output between {}",
+                actors.join(", ")
+            ),
+            Diagnostic::RateMismatch {
+                output,
+                output_rate,
+                input,
+                input_rate,
+                ..
+            } => write!(
+                f,
+                "{output} rate ({output_rate}) does not match {input} rate ({input_rate})"
+            ),
+            Diagnostic::OrphanOutput { output, .. } => write!(f, "{output} is not connected"),
+            Diagnostic::OrphanInput { input, .. } => write!(f, "{input} is not connected"),
+            Diagnostic::UnboundedLoop { input, .. } => {
+                write!(f, "{input} is an unbounded channel within a feedback loop")
+            }
+        }
+    }
+}
+
+/// List of [Diagnostic]s of a model
+#[derive(Debug, Clone, Default)]
+pub struct Diagnostics(Vec<Diagnostic>);
+impl Diagnostics {
+    /// Iterates over the diagnostics
+    pub fn iter(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.0.iter()
+    }
+    /// Checks if no issue has been found
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    /// Iterates over the diagnostics with [Severity::Error]
+    pub fn errors(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.0
+            .iter()
+            .filter(|diagnostic| diagnostic.severity() == Severity::Error)
+    }
+    /// Checks if any issue has [Severity::Error]
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+}
+impl IntoIterator for Diagnostics {
+    type Item = Diagnostic;
+    type IntoIter = std::vec::IntoIter<Diagnostic>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+impl Display for Diagnostics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for diagnostic in &self.0 {
+            writeln!(f, "{diagnostic}")?;
+        }
+        Ok(())
+    }
+}
+
+impl PlainModel {
+    /// Analyzes the model graph for configuration issues
+    pub fn diagnose(&self) -> Diagnostics {
+        let label = |actor: usize, io: &IO| format!("{}/{}", self[actor].client, io.name());
+        let outputs: HashMap<u64, (usize, &IO)> = self
+            .iter()
+            .enumerate()
+            .flat_map(|(i, actor)| {
+                actor
+                    .outputs
+                    .iter()
+                    .flatten()
+                    .map(move |io| (io.hash(), (i, io)))
+            })
+            .collect();
+        let inputs: Vec<(usize, &IO)> = self
+            .iter()
+            .enumerate()
+            .flat_map(|(i, actor)| actor.inputs.iter().flatten().map(move |io| (i, io)))
+            .collect();
+
+        let mut diagnostics = vec![];
+        for (&hash, &(i, output)) in &outputs {
+            if !inputs.iter().any(|(_, input)| input.hash() == hash) {
+                diagnostics.push(Diagnostic::OrphanOutput {
+                    output: label(i, output),
+                    hash,
+                });
+            }
+        }
+        for &(j, input) in &inputs {
+            let hash = input.hash();
+            match outputs.get(&hash) {
+                None => diagnostics.push(Diagnostic::OrphanInput {
+                    input: label(j, input),
+                    hash,
+                }),
+                Some(&(i, output)) if output.rate() != input.rate() => {
+                    diagnostics.push(Diagnostic::RateMismatch {
+                        output: label(i, output),
+                        output_rate: output.rate(),
+                        input: label(j, input),
+                        input_rate: input.rate(),
+                        hash,
+                    })
+                }
+                _ => (),
+            }
+        }
+
+        // connections (producer, consumer, hash, bootstrap)
+        let edges: Vec<_> = inputs
+            .iter()
+            .filter_map(|&(j, input)| {
+                outputs
+                    .get(&input.hash())
+                    .map(|&(i, output)| (i, j, input.hash(), matches!(output, IO::Bootstrap(_))))
+            })
+            .collect();
+        // loops that are not broken by a bootstrapped output
+        let blocking: Vec<_> = edges
+            .iter()
+            .filter(|(.., bootstrap)| !bootstrap)
+            .map(|&(i, j, ..)| (i, j))
+            .collect();
+        for component in loops(self.len(), &blocking) {
+            diagnostics.push(Diagnostic::Deadlock {
+                actors: component.iter().map(|&i| self[i].client.clone()).collect(),
+                hashes: edges
+                    .iter()
+                    .filter(|(i, j, ..)| component.contains(i) && component.contains(j))
+                    .map(|&(_, _, hash, _)| hash)
+                    .collect(),
+            });
+        }
+        let all: Vec<_> = edges.iter().map(|&(i, j, ..)| (i, j)).collect();
+        for component in loops(self.len(), &all) {
+            for &(j, input) in &inputs {
+                if !matches!(input, IO::Unbounded(_)) || !component.contains(&j) {
+                    continue;
+                }
+                if outputs
+                    .get(&input.hash())
+                    .is_some_and(|(i, _)| component.contains(i))
+                {
+                    diagnostics.push(Diagnostic::UnboundedLoop {
+                        input: label(j, input),
+                        hash: input.hash(),
+                    });
+                }
+            }
+        }
+
+        // the errors first, in a deterministic order
+        diagnostics.sort_by_cached_key(|diagnostic| {
+            (
+                std::cmp::Reverse(diagnostic.severity()),
+                diagnostic.to_string(),
+            )
+        });
+        Diagnostics(diagnostics)
+    }
+}
+
+impl<S: UnknownOrReady> Model<S> {
+    /// Analyzes the model graph for configuration issues
+    pub fn diagnostics(&self) -> Diagnostics {
+        PlainModel::from_iter(self).diagnose()
+    }
+}
+
+/// Returns the strongly connected components of a directed graph that contain a loop
+///
+/// The graph has `n` nodes and `edges` are pairs of (source,target) node indices
+fn loops(n: usize, edges: &[(usize, usize)]) -> Vec<Vec<usize>> {
+    struct Tarjan<'a> {
+        successors: &'a [Vec<usize>],
+        index: Vec<Option<usize>>,
+        low: Vec<usize>,
+        on_stack: Vec<bool>,
+        stack: Vec<usize>,
+        count: usize,
+        components: Vec<Vec<usize>>,
+    }
+    impl Tarjan<'_> {
+        fn visit(&mut self, v: usize) {
+            self.index[v] = Some(self.count);
+            self.low[v] = self.count;
+            self.count += 1;
+            self.stack.push(v);
+            self.on_stack[v] = true;
+            for &w in &self.successors[v] {
+                match self.index[w] {
+                    None => {
+                        self.visit(w);
+                        self.low[v] = self.low[v].min(self.low[w]);
+                    }
+                    Some(index) if self.on_stack[w] => self.low[v] = self.low[v].min(index),
+                    _ => (),
+                }
+            }
+            if Some(self.low[v]) == self.index[v] {
+                let mut component = vec![];
+                while let Some(w) = self.stack.pop() {
+                    self.on_stack[w] = false;
+                    component.push(w);
+                    if w == v {
+                        break;
+                    }
+                }
+                component.sort_unstable();
+                self.components.push(component);
+            }
+        }
+    }
+
+    let mut successors = vec![vec![]; n];
+    for &(i, j) in edges {
+        successors[i].push(j);
+    }
+    let mut tarjan = Tarjan {
+        successors: &successors,
+        index: vec![None; n],
+        low: vec![0; n],
+        on_stack: vec![false; n],
+        stack: vec![],
+        count: 0,
+        components: vec![],
+    };
+    for v in 0..n {
+        if tarjan.index[v].is_none() {
+            tarjan.visit(v);
+        }
+    }
+    tarjan
+        .components
+        .into_iter()
+        .filter(|component| component.len() > 1 || successors[component[0]].contains(&component[0]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use gmt_dos_clients::{logging::Logging, sampler::Sampler, signals::Signals};
+    use interface::{ClientHooks, Data, Read, Update, Write, UID};
+
+    #[derive(UID)]
+    enum U {}
+    #[derive(UID)]
+    enum Y {}
+    #[derive(UID)]
+    enum E {}
+
+    #[derive(Default)]
+    struct Sum(f64);
+    impl Update for Sum {}
+    impl ClientHooks for Sum {}
+    impl Read<U> for Sum {
+        fn read(&mut self, data: Data<U>) {
+            self.0 = data[0];
+        }
+    }
+    impl Read<E> for Sum {
+        fn read(&mut self, data: Data<E>) {
+            self.0 -= data[0];
+        }
+    }
+    impl Write<Y> for Sum {
+        fn write(&mut self) -> Option<Data<Y>> {
+            Some(vec![self.0].into())
+        }
+    }
+
+    fn model(bootstrap: bool) -> anyhow::Result<Model<model::Unknown>> {
+        let mut source: Initiator<_> = Signals::new(1, 10).into();
+        let mut sum: Actor<_> = Sum::default().into();
+        let mut feedback: Actor<_> = Sampler::<Vec<f64>, Y, E>::default().into();
+        let mut sink = Terminator::<_>::new(Logging::<f64>::default().into_arcx());
+        source.add_output().build::<U>().into_input(&mut sum)?;
+        sum.add_output()
+            .multiplex(2)
+            .build::<Y>()
+            .into_input(&mut feedback)
+            .into_input(&mut sink)?;
+        if bootstrap {
+            feedback
+                .add_output()
+                .bootstrap()
+                .build::<E>()
+                .into_input(&mut sum)?;
+        } else {
+            feedback.add_output().build::<E>().into_input(&mut sum)?;
+        }
+        Ok(model!(source, sum, feedback, sink))
+    }
+
+    #[test]
+    fn deadlock() -> anyhow::Result<()> {
+        let diagnostics = model(false)?.diagnostics();
+        let deadlocks: Vec<_> = diagnostics
+            .iter()
+            .filter(|diagnostic| matches!(diagnostic, Diagnostic::Deadlock { .. }))
+            .collect();
+        assert_eq!(deadlocks.len(), 1);
+        assert_eq!(deadlocks[0].hashes().len(), 2);
+        assert!(diagnostics.has_errors());
+        let graph = model(false)?.graph().unwrap().highlight(&diagnostics);
+        assert_eq!(graph.to_string().matches("color=red").count(), 5);
+        assert!(matches!(
+            model(false)?.check(),
+            Err(crate::model::ModelError::Diagnostics(_))
+        ));
+        assert!(model(false)?.lenient_check().is_ok());
+
+        assert!(model(true)?.diagnostics().is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+use std::fmt::Display;
+
+use crate::{framework::model::ClientFailure, ActorError};
+
+/// Failure of a [Model](super::Model) originating from an actor client
+///
+/// The error returned by the client is kept apart from the disconnections
+/// of the other actors that follow from the failure
+#[derive(Debug, thiserror::Error)]
+pub struct ModelFailure {
+    /// Name of the actor which client failed
+    pub actor: String,
+    /// Sample index of the failing update, at the simulation sampling frequency
+    pub step: usize,
+    /// Last inputs received by the client, see [Actor::dump_input](crate::actor::Actor::dump_input)
+    pub inputs: Vec<String>,
+    /// Client error
+    #[source]
+    pub error: ActorError,
+    /// Actors that have been disconnected or cancelled as a consequence of the failure
+    pub disconnected: Vec<String>,
+}
+impl From<ClientFailure> for ModelFailure {
+    fn from(value: ClientFailure) -> Self {
+        let ClientFailure {
+            actor,
+            step,
+            inputs,
+            source,
+        } = value;
+        Self {
+            actor,
+            step,
+            inputs,
+            error: source,
+            disconnected: Vec::new(),
+        }
+    }
+}
+impl Display for ModelFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} failed at step {}: {}",
+            self.actor, self.step, self.error
+        )?;
+        for input in &self.inputs {
+            write!(f, "\n - {input}")?;
+        }
+        if !self.disconnected.is_empty() {
+            write!(f, "\n disconnected: {}", self.disconnected.join(", "))?;
+        }
+        Ok(())
+    }
+}
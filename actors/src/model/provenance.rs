@@ -0,0 +1,240 @@
+/*!
+# Model provenance
+
+The [Provenance] collector gathers the information needed to trace the data saved by a model
+back to the model that produced them:
+ - the model name, the date and the git revision of the current directory,
+ - the simulation sampling frequency given by the user, the base of the rates of the inputs and outputs,
+ - the actors of the [PlainModel] with the UIDs and the rates of their inputs and outputs,
+ - the key/value pairs returned by the [provenance](interface::ClientHooks::provenance) method of each client,
+   e.g. the FEM identifiers of the `gmt_dos-clients_fem` state space model;
+   for a [System](crate::system::System), the keys are prefixed with the name of the system actor: `<actor>/<key>`,
+ - the key/value pairs given by the user.
+
+A model that passes its [check](super::Model::check) collects the default provenance,
+without user key/values, and passes it to each client with [set_provenance](interface::ClientHooks::set_provenance),
+the data loggers adding it to the metadata of their files.
+The key/values of the user and the sampling frequency are added with the [provenance](super::Model::provenance)
+method of the [Ready](super::Ready) model, that passes the provenance to the clients again
+and writes it to a JSON sidecar file.
+
+```
+use gmt_dos_actors::{model::Provenance, prelude::*};
+use gmt_dos_clients::{logging::Logging, signals::Signals};
+use interface::UID;
+
+#[derive(UID)]
+enum Sig {}
+
+let mut source: Initiator<_> = Signals::new(1, 10).into();
+let mut sink = Terminator::<_>::new(Logging::<f64>::default().into_arcx());
+source.add_output().build::<Sig>().into_input(&mut sink)?;
+
+let path = std::env::temp_dir().join("provenance.json");
+model!(source, sink).check()?.provenance(
+    Provenance::new()
+        .sampling_frequency(1000.)
+        .key_value("CFD case", "zen30az000_OS7")
+        .sidecar(&path),
+)?;
+# std::fs::remove_file(path)?;
+# Ok::<(), anyhow::Error>(())
+```
+*/
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use chrono::{Local, SecondsFormat};
+use serde_json::{json, Map, Value};
+
+use super::{Model, PlainModel, Ready, Result};
+use crate::actor::plain::IO;
+
+/// Metadata key of the provenance JSON record
+pub const PROVENANCE_KEY: &str = "provenance";
+
+/// Model provenance collector
+#[derive(Debug, Default, Clone)]
+pub struct Provenance {
+    key_values: Vec<(String, String)>,
+    sampling_frequency: Option<f64>,
+    sidecar: Option<PathBuf>,
+}
+impl Provenance {
+    /// Creates a new provenance collector
+    pub fn new() -> Self {
+        Default::default()
+    }
+    /// Adds a key/value pair to the provenance
+    pub fn key_value(mut self, key: impl ToString, value: impl ToString) -> Self {
+        self.key_values.push((key.to_string(), value.to_string()));
+        self
+    }
+    /// Sets the simulation sampling frequency in Hz
+    ///
+    /// The rates of the actors inputs and outputs are given with respect to this sampling frequency
+    pub fn sampling_frequency(mut self, sampling_frequency: f64) -> Self {
+        self.sampling_frequency = Some(sampling_frequency);
+        self
+    }
+    /// Sets the path to the JSON sidecar file
+    ///
+    /// Per default, the file is `<model name>-provenance.json`, written in the current directory
+    /// or in the directory given by the environment variable `DATA_REPO`
+    pub fn sidecar<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.sidecar = Some(path.as_ref().to_path_buf());
+        self
+    }
+    /// Returns the provenance JSON record of the model
+    pub fn collect(&self, model: &Model<Ready>) -> Value {
+        let tasks: Vec<_> = model.actors.iter().flatten().collect();
+        let actors: PlainModel = tasks.iter().map(|task| task.as_plain()).collect();
+        let actors: Vec<_> = actors
+            .iter()
+            .zip(&tasks)
+            .map(|(actor, task)| {
+                json!({
+                    "name": actor.client,
+                    "inputs": ios(actor.inputs.as_deref()),
+                    "outputs": ios(actor.outputs.as_deref()),
+                    "client": key_values(&task.provenance()),
+                })
+            })
+            .collect();
+        json!({
+            "model": model.get_name(),
+            "date": Local::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+            "git": git_revision(),
+            "sampling frequency": self.sampling_frequency,
+            "actors": actors,
+            "user": key_values(&self.key_values),
+        })
+    }
+    /// Passes the provenance to the clients and returns the provenance JSON record
+    pub(super) fn inject(&self, model: &Model<Ready>) -> Value {
+        let record = self.collect(model);
+        let metadata: Vec<_> = std::iter::once((PROVENANCE_KEY.to_string(), record.to_string()))
+            .chain(self.key_values.iter().cloned())
+            .collect();
+        model
+            .actors
+            .iter()
+            .flatten()
+            .for_each(|actor| actor.set_provenance(&metadata));
+        record
+    }
+}
+
+fn ios(ios: Option<&[IO]>) -> Value {
+    ios.into_iter()
+        .flatten()
+        .map(|io| json!({"uid": io.name(), "rate": io.rate()}))
+        .collect()
+}
+
+fn key_values(key_values: &[(String, String)]) -> Value {
+    Value::Object(
+        key_values
+            .iter()
+            .map(|(key, value)| (key.clone(), Value::String(value.clone())))
+            .collect::<Map<_, _>>(),
+    )
+}
+
+/// Returns the git commit of the current directory
+fn git_revision() -> Option<String> {
+    Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|revision| revision.trim().to_string())
+}
+
+impl Model<Ready> {
+    /// Gathers the model [Provenance]
+    ///
+    /// The provenance JSON record is written to the sidecar file and passed to each client,
+    /// including the clients of the actors within a [System](crate::system::System),
+    /// under the [PROVENANCE_KEY] key, with the key/values given by the user,
+    /// replacing the default provenance passed to the clients by [check](Model::check).
+    pub fn provenance(self, provenance: Provenance) -> Result<Self> {
+        let record = provenance.inject(&self);
+        let path = provenance.sidecar.clone().unwrap_or_else(|| {
+            Path::new(&env::var("DATA_REPO").unwrap_or(".".into()))
+                .join(format!("{}-provenance.json", self.get_name()))
+        });
+        fs::write(&path, format!("{record:#}"))?;
+        log::info!("model provenance written to {path:?}");
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use gmt_dos_clients::signals::Signals;
+    use interface::{ClientHooks, Data, Read, Update, UID};
+
+    #[derive(UID)]
+    enum Sig {}
+
+    #[derive(Default)]
+    struct Sink(Vec<(String, String)>);
+    impl Update for Sink {}
+    impl ClientHooks for Sink {
+        fn provenance(&self) -> Vec<(String, String)> {
+            vec![("mode".into(), "test".into())]
+        }
+        fn set_provenance(&mut self, provenance: &[(String, String)]) {
+            self.0 = provenance.to_vec();
+        }
+    }
+    impl Read<Sig> for Sink {
+        fn read(&mut self, _data: Data<Sig>) {}
+    }
+
+    #[test]
+    fn provenance() -> anyhow::Result<()> {
+        let mut source: Initiator<_, 10> = Signals::new(1, 10).into();
+        let sink = Sink::default().into_arcx();
+        let mut logger = Terminator::<_, 10>::new(sink.clone()).name("logger");
+        source.add_output().build::<Sig>().into_input(&mut logger)?;
+        let path = env::temp_dir().join("gmt_dos-actors-provenance.json");
+        let model = model!(source, logger).name("test").check()?;
+        {
+            let metadata = &sink.try_lock()?.0;
+            assert_eq!(metadata.len(), 1);
+            let record: Value = serde_json::from_str(&metadata[0].1)?;
+            assert_eq!(record["model"], "test");
+            assert_eq!(record["sampling frequency"], Value::Null);
+        }
+        model.provenance(
+            Provenance::new()
+                .sampling_frequency(1000.)
+                .key_value("CFD case", "zen30az000_OS7")
+                .sidecar(&path),
+        )?;
+
+        let record: Value = serde_json::from_str(&fs::read_to_string(&path)?)?;
+        fs::remove_file(path)?;
+        assert_eq!(record["model"], "test");
+        assert_eq!(record["sampling frequency"], 1000.);
+        assert_eq!(record["user"]["CFD case"], "zen30az000_OS7");
+        let logger = &record["actors"][1];
+        assert_eq!(logger["name"], "logger");
+        assert_eq!(logger["inputs"][0]["rate"], 10);
+        assert_eq!(logger["client"]["mode"], "test");
+
+        let metadata = &sink.try_lock()?.0;
+        assert_eq!(metadata[0].0, PROVENANCE_KEY);
+        assert_eq!(metadata[1], ("CFD case".into(), "zen30az000_OS7".into()));
+        Ok(())
+    }
+}
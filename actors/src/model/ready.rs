@@ -1,11 +1,47 @@
 use super::{Model, Ready, Running};
+use crate::framework::model::Cancellation;
 use chrono::{DateTime, Local, SecondsFormat};
-use tokio::task::JoinSet;
 use std::{marker::PhantomData, time::Instant};
+use tokio::task::JoinSet;
 
 impl Model<Ready> {
+    /// Records the runtime statistics of the actors
+    ///
+    /// The statistics are available from the completed model with [stats](Model::stats),
+    /// including the statistics of the actors within a [System](crate::system::System)
+    pub fn profile(mut self) -> Self {
+        self.stats = Some(
+            self.actors
+                .iter()
+                .flatten()
+                .flat_map(|actor| actor.profile())
+                .collect(),
+        );
+        self
+    }
     /// Spawns each actor task
     pub fn run(self) -> Model<Running> {
+        self.spawn(Default::default())
+    }
+    /// Spawns each actor task, the initiators stopping after `n_sample` samples
+    pub fn run_for(self, n_sample: usize) -> Model<Running> {
+        self.spawn(Cancellation::new(Default::default(), Some(n_sample)))
+    }
+    /// Spawns each actor task with the given [Cancellation]
+    pub fn spawn(self, cancellation: Cancellation) -> Model<Running> {
+        // the actors within a system join the rendezvous of the model the system belongs to
+        #[cfg(feature = "filing")]
+        let cancellation = if cancellation.rendezvous().is_some() {
+            cancellation
+        } else {
+            let actors: Vec<_> = self
+                .actors
+                .iter()
+                .flatten()
+                .flat_map(|actor| actor.as_plains())
+                .collect();
+            cancellation.with_rendezvous(super::checkpoint::Rendezvous::new(&actors))
+        };
         let now: DateTime<Local> = Local::now();
         self.verbose.then(|| {
             eprintln!(
@@ -30,21 +66,27 @@ impl Model<Ready> {
             .into_iter()
             .flatten()
             .map(|actor| {
+                let name = actor.as_plain().client;
+                let cancellation = cancellation.child();
                 #[cfg(tokio_unstable)]
                 {
                     set.build_task()
                         .name(actor.name())
-                        .spawn(async move { actor.task().await })
+                        .spawn(async move { (name, actor.cancellable_task(cancellation).await) })
                         .unwrap()
                 }
                 #[cfg(not(tokio_unstable))]
-                set.spawn(async move { actor.task().await })
+                set.spawn(async move { (name, actor.cancellable_task(cancellation).await) })
             })
             .collect();
         Model::<Running> {
             name: self.name,
             actors: None,
             task_set: Some(set),
+            schedule: None,
+            token: Some(cancellation),
+            report: None,
+            stats: self.stats,
             state: PhantomData,
             start: Instant::now(),
             verbose: self.verbose,
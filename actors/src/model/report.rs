@@ -0,0 +1,70 @@
+use std::fmt::Display;
+
+/// Actor task outcome
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    /// The task ended normally
+    Completed,
+    /// The task has been cancelled
+    Cancelled,
+    /// The task has been retired from the running model
+    Retired,
+    /// The task failed with the given error message
+    Failed(String),
+}
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Completed => write!(f, "completed"),
+            Outcome::Cancelled => write!(f, "cancelled"),
+            Outcome::Retired => write!(f, "retired"),
+            Outcome::Failed(msg) => write!(f, "failed: {msg}"),
+        }
+    }
+}
+
+/// Outcomes of the actor tasks of a [Model](super::Model)
+#[derive(Debug, Clone, Default)]
+pub struct Report(Vec<(String, Outcome)>);
+impl Report {
+    pub(crate) fn push(&mut self, actor: impl Into<String>, outcome: Outcome) {
+        self.0.push((actor.into(), outcome));
+    }
+    fn filter(&self, pred: impl Fn(&Outcome) -> bool) -> impl Iterator<Item = &str> {
+        self.0
+            .iter()
+            .filter(move |(_, outcome)| pred(outcome))
+            .map(|(actor, _)| actor.as_str())
+    }
+    /// Returns the actors which tasks ended normally
+    pub fn completed(&self) -> impl Iterator<Item = &str> {
+        self.filter(|outcome| *outcome == Outcome::Completed)
+    }
+    /// Returns the actors which tasks have been cancelled
+    pub fn cancelled(&self) -> impl Iterator<Item = &str> {
+        self.filter(|outcome| *outcome == Outcome::Cancelled)
+    }
+    /// Returns the actors which tasks failed and the error messages
+    pub fn failed(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0.iter().filter_map(|(actor, outcome)| match outcome {
+            Outcome::Failed(msg) => Some((actor.as_str(), msg.as_str())),
+            _ => None,
+        })
+    }
+    /// Returns the actors which tasks have been retired
+    pub fn retired(&self) -> impl Iterator<Item = &str> {
+        self.filter(|outcome| *outcome == Outcome::Retired)
+    }
+    /// Checks if any task has been cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled().next().is_some()
+    }
+}
+impl Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (actor, outcome) in &self.0 {
+            writeln!(f, " - {actor}: {outcome}")?;
+        }
+        Ok(())
+    }
+}
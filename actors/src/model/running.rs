@@ -1,6 +1,11 @@
-use super::{Completed, Model, ModelError, Result, Running};
+use super::{
+    Completed, Model, ModelError, ModelFailure, ModelStats, Outcome, Report, Result, Running,
+};
 use crate::{
-    framework::model::TaskError::FromActor,
+    framework::model::{
+        Cancellation, Task,
+        TaskError::{self, Cancelled, Client, FromActor, FromModel, Retired},
+    },
     ActorError::{Disconnected, DropRecv, DropSend},
 };
 use chrono::{DateTime, Local, SecondsFormat};
@@ -8,50 +13,221 @@ use std::{
     future::{Future, IntoFuture},
     marker::PhantomData,
     pin::Pin,
-    time::Instant,
+    time::{Duration, Instant},
 };
+use tokio_util::sync::CancellationToken;
+
+/// Handle to an actor attached to a running [Model]
+///
+/// The handle is returned by [Model::attach] or by
+/// [Actor::retire_handle](crate::actor::Actor::retire_handle)
+#[derive(Debug, Clone)]
+pub struct Attached(pub(crate) CancellationToken);
+impl Attached {
+    /// Stops the actor and removes it from the model
+    ///
+    /// The actor client is given the opportunity to flush its data with
+    /// [on_shutdown](interface::ClientHooks::on_shutdown)
+    pub fn retire(&self) {
+        self.0.cancel();
+    }
+    /// Checks if the actor has been retired
+    pub fn is_retired(&self) -> bool {
+        self.0.is_cancelled()
+    }
+}
+
+/// Handle to cancel a running [Model]
+///
+/// Cancelling the model stops all the actors, the clients of the actors that
+/// haven't completed yet are given the opportunity to flush their data with
+/// [on_shutdown](interface::ClientHooks::on_shutdown)
+#[derive(Debug, Clone)]
+pub struct CancelHandle(CancellationToken);
+impl CancelHandle {
+    /// Cancels the model
+    pub fn cancel(&self) {
+        self.0.cancel();
+    }
+    /// Checks if the model has been cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.0.is_cancelled()
+    }
+}
 
 impl Model<Running> {
+    fn token(&self) -> CancellationToken {
+        self.token
+            .as_ref()
+            .map(|cancellation| cancellation.token().clone())
+            .unwrap_or_default()
+    }
+    /// Returns a handle to cancel the model
+    pub fn cancel_handle(&self) -> CancelHandle {
+        CancelHandle(self.token())
+    }
+    /// Cancels the model after the given duration
+    pub fn timeout(self, duration: Duration) -> Self {
+        let token = self.token();
+        tokio::spawn(async move {
+            tokio::select! {
+                _ = tokio::time::sleep(duration) => {
+                    log::info!("model timed out after {}", humantime::format_duration(duration));
+                    token.cancel();
+                },
+                _ = token.cancelled() => (),
+            }
+        });
+        self
+    }
+    /// Cancels the model when receiving Ctrl-C
+    pub fn cancel_on_ctrl_c(self) -> Self {
+        let token = self.token();
+        tokio::spawn(async move {
+            tokio::select! {
+                Ok(_) = tokio::signal::ctrl_c() => {
+                    log::info!("model cancelled with Ctrl-C");
+                    token.cancel();
+                },
+                _ = token.cancelled() => (),
+            }
+        });
+        self
+    }
+    /// Spawns the task of an actor next to the tasks of the running actors
+    ///
+    /// The actor inputs are connected to the outputs of the running actors with [Tap]s,
+    /// the actor is stopped either with the model or with [Attached::retire]
+    ///
+    /// [Tap]: crate::framework::network::Tap
+    pub fn attach<T>(&mut self, actor: T) -> Attached
+    where
+        T: Task + 'static,
+    {
+        let model_token = self.token();
+        let token = model_token.child_token();
+        let cancellation = Cancellation::new(token.clone(), None);
+        let actor = Box::new(actor);
+        let name = actor.as_plain().client;
+        log::info!("{} attached", name);
+        self.task_set
+            .get_or_insert_with(Default::default)
+            .spawn(async move {
+                let result = match actor.cancellable_task(cancellation).await {
+                    Err(Cancelled(msg)) if !model_token.is_cancelled() => Err(Retired(msg)),
+                    result => result,
+                };
+                (name, result)
+            });
+        Attached(token)
+    }
     /// Waits for the task of each actor to finish
+    ///
+    /// If an actor fails, the other actors are cancelled and the error of the
+    /// first failure is returned once all the tasks are finished.
+    /// A failure of an actor client is returned as a [ModelFailure] error
+    /// listing the actors that have been disconnected or cancelled after the failure
     pub async fn wait(mut self) -> Result<Model<Completed>> {
         let mut task_set = self.task_set.take().unwrap();
+        let token = self.token();
+        let mut report = Report::default();
+        let mut failure: Option<ModelError> = None;
+        let mut disconnected = Vec::new();
         // for task_handle in task_set.into_iter() {
         while let Some(task_handle) = task_set.join_next().await {
             // task_handle.await?.map_err(|e| Box::new(e))?;
-            match task_handle? {
+            let (name, result) = match task_handle {
+                Ok(output) => output,
+                Err(e) => {
+                    report.push(format!("task {}", e.id()), Outcome::Failed(e.to_string()));
+                    token.cancel();
+                    failure.get_or_insert(e.into());
+                    continue;
+                }
+            };
+            match result {
                 Ok(_) => {
-                    log::debug!(
-                        "{} succesfully completed",
-                        self.name.as_ref().unwrap_or(&String::from("Model"))
-                    );
-                    Ok(())
+                    log::debug!("{} succesfully completed", name);
+                    report.push(name, Outcome::Completed);
                 }
                 Err(FromActor(Disconnected(msg))) => {
                     log::debug!("{} has been disconnected", msg);
-                    Ok(())
+                    disconnected.push(name.clone());
+                    report.push(name, Outcome::Completed);
                 }
                 Err(FromActor(DropRecv { msg, .. })) => {
                     log::debug!("{} has been dropped", msg);
-                    Ok(())
+                    disconnected.push(name.clone());
+                    report.push(name, Outcome::Completed);
                 }
                 Err(FromActor(DropSend { msg, .. })) => {
                     log::debug!("{} has been dropped", msg);
-                    Ok(())
+                    disconnected.push(name.clone());
+                    report.push(name, Outcome::Completed);
+                }
+                Err(Retired(msg)) => {
+                    log::debug!("{} has been retired", msg);
+                    report.push(name, Outcome::Retired);
+                }
+                Err(Cancelled(msg)) => {
+                    log::debug!("{} has been cancelled", msg);
+                    disconnected.push(name.clone());
+                    report.push(name, Outcome::Cancelled);
                 }
-                Err(e) => Err(e),
+                Err(Client(e)) => {
+                    report.push(name, Outcome::Failed(e.to_string()));
+                    token.cancel();
+                    // the client failure is the root cause of the other failures
+                    if !matches!(failure, Some(ModelError::Failure(_))) {
+                        failure = Some(ModelError::Failure(Box::new(ModelFailure::from(*e))));
+                    }
+                }
+                // failure of a client within a system
+                Err(FromModel(ModelError::Failure(e))) => {
+                    report.push(name, Outcome::Failed(e.to_string()));
+                    token.cancel();
+                    if !matches!(failure, Some(ModelError::Failure(_))) {
+                        failure = Some(ModelError::Failure(e));
+                    }
+                }
+                Err(e) => {
+                    report.push(name, Outcome::Failed(e.to_string()));
+                    token.cancel();
+                    failure.get_or_insert(Box::<TaskError>::new(e).into());
+                }
+            }
+        }
+        if let Some(mut e) = failure {
+            if let ModelError::Failure(failure) = &mut e {
+                failure.disconnected.extend(disconnected);
             }
-            .map_err(Box::new)?;
+            self.verbose.then(|| {
+                eprintln!(
+                    "[{}] FAILED:\n{}",
+                    self.name
+                        .as_ref()
+                        .unwrap_or(&String::from("Model"))
+                        .to_uppercase(),
+                    report
+                )
+            });
+            return Err(e);
         }
         let elapsed_time = Instant::now().duration_since(self.start);
         let now: DateTime<Local> = Local::now();
         self.verbose.then(|| {
             eprintln!(
-                "[{}<{}>] COMPLETED in {}",
+                "[{}<{}>] {} in {}",
                 self.name
                     .as_ref()
                     .unwrap_or(&String::from("Model"))
                     .to_uppercase(),
                 now.to_rfc3339_opts(SecondsFormat::Secs, true),
+                if report.is_cancelled() {
+                    "CANCELLED"
+                } else {
+                    "COMPLETED"
+                },
                 humantime::format_duration(elapsed_time)
             )
         });
@@ -59,6 +235,10 @@ impl Model<Running> {
             name: self.name,
             actors: None,
             task_set: None,
+            schedule: None,
+            token: None,
+            report: Some(report),
+            stats: self.stats,
             state: PhantomData,
             start: Instant::now(),
             verbose: self.verbose,
@@ -67,6 +247,17 @@ impl Model<Running> {
     }
 }
 
+impl Model<Completed> {
+    /// Returns the outcomes of the actor tasks
+    pub fn report(&self) -> Option<&Report> {
+        self.report.as_ref()
+    }
+    /// Returns the runtime statistics of the actors if the model has been [profiled](Model::profile)
+    pub fn stats(&self) -> Option<ModelStats> {
+        self.stats.as_deref().map(ModelStats::new)
+    }
+}
+
 pub type ModelCompleted = Pin<
     Box<dyn Future<Output = std::result::Result<Model<Completed>, ModelError>> + Send + 'static>,
 >;
@@ -77,3 +268,183 @@ impl IntoFuture for Model<Running> {
         Box::pin(self.wait())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{model::ModelError, prelude::*};
+    use gmt_dos_clients::signals::Signal;
+    use gmt_dos_clients::{logging::Logging, signals::Signals};
+    use interface::{
+        Async, AsyncRead, AsyncUpdate, AsyncWrite, ClientHooks, Data, Read, TryRead, TryUpdate,
+        TryWrite, Update, UID,
+    };
+    use std::time::Duration;
+
+    #[derive(UID)]
+    enum Sig {}
+    #[derive(UID)]
+    enum Echo {}
+
+    #[derive(Default)]
+    struct Delay {
+        data: Vec<f64>,
+        limit: Option<f64>,
+    }
+    impl AsyncUpdate for Delay {
+        type Error = std::io::Error;
+        async fn update(&mut self) -> std::result::Result<(), Self::Error> {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+            match self.limit {
+                Some(limit) if self.data[0] >= limit => Err(std::io::Error::other("out of range")),
+                _ => Ok(()),
+            }
+        }
+    }
+    impl ClientHooks for Delay {}
+    impl AsyncRead<Sig> for Delay {
+        type Error = std::io::Error;
+        async fn read(&mut self, data: Data<Sig>) -> std::result::Result<(), std::io::Error> {
+            self.data = data.into();
+            Ok(())
+        }
+    }
+    impl AsyncWrite<Echo> for Delay {
+        type Error = std::io::Error;
+        async fn write(&mut self) -> std::result::Result<Option<Data<Echo>>, std::io::Error> {
+            Ok(Some(self.data.clone().into()))
+        }
+    }
+
+    #[derive(Default)]
+    struct Flush(bool);
+    impl Update for Flush {}
+    impl ClientHooks for Flush {
+        fn on_shutdown(&mut self) {
+            self.0 = true;
+        }
+    }
+    impl Read<Sig> for Flush {
+        fn read(&mut self, _: Data<Sig>) {}
+    }
+
+    #[derive(Default)]
+    struct Failing(f64);
+    impl TryUpdate for Failing {
+        type Error = std::io::Error;
+        fn try_update(&mut self) -> std::result::Result<&mut Self, Self::Error> {
+            if self.0 < 3. {
+                Ok(self)
+            } else {
+                Err(std::io::Error::other("out of range"))
+            }
+        }
+    }
+    impl ClientHooks for Failing {}
+    impl TryRead<Sig> for Failing {
+        type Error = std::io::Error;
+        fn try_read(&mut self, data: Data<Sig>) -> std::result::Result<&mut Self, std::io::Error> {
+            self.0 = data[0];
+            Ok(self)
+        }
+    }
+    impl TryWrite<Echo> for Failing {
+        type Error = std::io::Error;
+        fn try_write(&mut self) -> std::result::Result<Option<Data<Echo>>, std::io::Error> {
+            Ok(Some(vec![self.0].into()))
+        }
+    }
+
+    #[tokio::test]
+    async fn run_for() -> anyhow::Result<()> {
+        let mut source: Initiator<_> = Signals::new(1, 100).into();
+        let logging = Logging::<f64>::default().into_arcx();
+        let mut sink = Terminator::<_>::new(logging.clone());
+        source.add_output().build::<Sig>().into_input(&mut sink)?;
+        let model = model!(source, sink).quiet().check()?.run_for(10).await?;
+        assert_eq!(logging.lock().await.len(), 10);
+        let report = model.report().unwrap();
+        assert_eq!(report.completed().count(), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn timeout() -> anyhow::Result<()> {
+        let mut source: Initiator<_> = Signals::new(1, usize::MAX).into();
+        let flush = Flush::default().into_arcx();
+        let mut sink = Terminator::<_>::new(flush.clone());
+        source.add_output().build::<Sig>().into_input(&mut sink)?;
+        let model = model!(source, sink)
+            .quiet()
+            .check()?
+            .run()
+            .timeout(Duration::from_millis(100))
+            .await?;
+        let report = model.report().unwrap();
+        assert!(report.is_cancelled());
+        assert!(flush.lock().await.0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn async_client() -> anyhow::Result<()> {
+        let mut source: Initiator<_> = Signals::new(1, 5)
+            .channels(Signal::Ramp { a: 1., b: 0. })
+            .into();
+        let mut delay: Actor<_> = Async::new(Delay::default()).into();
+        let logging = Logging::<f64>::default().into_arcx();
+        let mut sink = Terminator::<_>::new(logging.clone());
+        source.add_output().build::<Sig>().into_input(&mut delay)?;
+        delay.add_output().build::<Echo>().into_input(&mut sink)?;
+        model!(source, delay, sink).quiet().check()?.run().await?;
+        assert_eq!(*logging.lock().await.as_slice(), [0., 1., 2., 3., 4.]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn client_failure() -> anyhow::Result<()> {
+        let mut source: Initiator<_> = Signals::new(1, 10)
+            .channels(Signal::Ramp { a: 1., b: 0. })
+            .into();
+        let mut failing: Actor<_> = Failing::default().into();
+        let mut sink = Terminator::<_>::new(Logging::<f64>::default().into_arcx());
+        source
+            .add_output()
+            .build::<Sig>()
+            .into_input(&mut failing)?;
+        failing.add_output().build::<Echo>().into_input(&mut sink)?;
+        failing.dump_input::<Sig>()?;
+        let Err(ModelError::Failure(failure)) =
+            model!(source, failing, sink).quiet().check()?.run().await
+        else {
+            panic!("expected a client failure")
+        };
+        assert!(failure.actor.contains("Failing"));
+        assert_eq!(failure.step, 3);
+        assert!(failure.inputs[0].contains("3.0"));
+        assert!(!failure.disconnected.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn async_client_failure() -> anyhow::Result<()> {
+        let mut source: Initiator<_> = Signals::new(1, 10)
+            .channels(Signal::Ramp { a: 1., b: 0. })
+            .into();
+        let mut delay: Actor<_> = Async::new(Delay {
+            limit: Some(3.),
+            ..Default::default()
+        })
+        .into();
+        let mut sink = Terminator::<_>::new(Logging::<f64>::default().into_arcx());
+        source.add_output().build::<Sig>().into_input(&mut delay)?;
+        delay.add_output().build::<Echo>().into_input(&mut sink)?;
+        let Err(ModelError::Failure(failure)) =
+            model!(source, delay, sink).quiet().check()?.run().await
+        else {
+            panic!("expected a client failure")
+        };
+        assert!(failure.actor.contains("Delay"));
+        assert_eq!(failure.step, 3);
+        Ok(())
+    }
+}
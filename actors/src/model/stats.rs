@@ -0,0 +1,184 @@
+use std::{
+    fmt::Display,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// Durations of an actor operation
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Timing {
+    /// Number of times the operation has been performed
+    pub count: usize,
+    /// Total time spent in the operation
+    pub total: Duration,
+    /// Longest time spent in the operation
+    pub max: Duration,
+}
+impl Timing {
+    pub(crate) fn record(&mut self, elapsed: Duration) {
+        self.count += 1;
+        self.total += elapsed;
+        self.max = self.max.max(elapsed);
+    }
+    /// Returns the mean time spent in the operation
+    pub fn mean(&self) -> Duration {
+        if self.count == 0 {
+            Duration::ZERO
+        } else {
+            self.total.div_f64(self.count as f64)
+        }
+    }
+}
+impl Display for Timing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:>10.3?} (mean: {:>10.3?}, max: {:>10.3?})",
+            self.total,
+            self.mean(),
+            self.max
+        )
+    }
+}
+
+/// Occupancy of the channels of an actor output
+#[derive(Debug, Clone, Default)]
+pub struct OutputStats {
+    /// Output name
+    pub name: String,
+    /// Largest number of data waiting in a channel when sending new data
+    pub max_depth: usize,
+    total_depth: usize,
+    count: usize,
+}
+impl OutputStats {
+    pub(crate) fn new(name: String) -> Self {
+        Self {
+            name,
+            ..Default::default()
+        }
+    }
+    pub(crate) fn record(&mut self, depth: usize) {
+        self.count += 1;
+        self.total_depth += depth;
+        self.max_depth = self.max_depth.max(depth);
+    }
+    /// Returns the mean number of data waiting in a channel when sending new data
+    pub fn mean_depth(&self) -> f64 {
+        if self.count == 0 {
+            0.
+        } else {
+            self.total_depth as f64 / self.count as f64
+        }
+    }
+}
+
+/// Runtime statistics of an [Actor](crate::actor::Actor)
+///
+/// The time spent in `collect` is mostly the time waiting for the inputs,
+/// the time spent in `update` includes the time to lock the client and
+/// the time spent in `distribute` is mostly the time waiting for the receivers to be ready
+#[derive(Debug, Clone, Default)]
+pub struct ActorStats {
+    /// Actor name
+    pub name: String,
+    pub(crate) hashes: Vec<u64>,
+    /// Inputs collection timing
+    pub collect: Timing,
+    /// Client update timing
+    pub update: Timing,
+    /// Outputs distribution timing
+    pub distribute: Timing,
+    /// Outputs channels occupancy
+    pub outputs: Vec<OutputStats>,
+}
+impl ActorStats {
+    pub(crate) fn new(name: String, hashes: Vec<u64>, outputs: Vec<OutputStats>) -> Self {
+        Self {
+            name,
+            hashes,
+            outputs,
+            ..Default::default()
+        }
+    }
+    /// Returns the total time spent in the actor loop
+    pub fn total(&self) -> Duration {
+        self.collect.total + self.update.total + self.distribute.total
+    }
+}
+impl Display for ActorStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}:", self.name)?;
+        writeln!(f, " - collect   : {}", self.collect)?;
+        writeln!(f, " - update    : {}", self.update)?;
+        writeln!(f, " - distribute: {}", self.distribute)?;
+        for output in &self.outputs {
+            writeln!(
+                f,
+                " - {}: queue depth (mean: {:.2}, max: {})",
+                output.name,
+                output.mean_depth(),
+                output.max_depth
+            )?;
+        }
+        Ok(())
+    }
+}
+
+pub(crate) type Profiler = Arc<Mutex<ActorStats>>;
+
+/// Runtime statistics of the actors of a [Model](super::Model)
+#[derive(Debug, Clone, Default)]
+pub struct ModelStats(pub(crate) Vec<ActorStats>);
+impl ModelStats {
+    pub(crate) fn new(profilers: &[Profiler]) -> Self {
+        Self(
+            profilers
+                .iter()
+                .map(|profiler| profiler.lock().unwrap().clone())
+                .collect(),
+        )
+    }
+    /// Iterates over the statistics of each actor
+    pub fn iter(&self) -> impl Iterator<Item = &ActorStats> {
+        self.0.iter()
+    }
+    /// Returns the statistics of the actor that spent the most time updating its client
+    pub fn hottest(&self) -> Option<&ActorStats> {
+        self.0.iter().max_by_key(|stats| stats.update.total)
+    }
+}
+impl Display for ModelStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for stats in &self.0 {
+            stats.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{framework::model::FlowChart, prelude::*};
+    use gmt_dos_clients::{logging::Logging, signals::Signals};
+    use interface::UID;
+
+    #[derive(UID)]
+    enum Sig {}
+
+    #[tokio::test]
+    async fn profile() -> anyhow::Result<()> {
+        let mut source: Initiator<_> = Signals::new(1, 10).into();
+        let logging = Logging::<f64>::default().into_arcx();
+        let mut sink = Terminator::<_>::new(logging.clone());
+        source.add_output().build::<Sig>().into_input(&mut sink)?;
+        let model = model!(source, sink).quiet().check()?.profile();
+        let graph = model.graph().unwrap();
+        let stats = model.run().await?.stats().unwrap();
+        assert_eq!(stats.iter().count(), 2);
+        let source = stats.iter().find(|stats| stats.outputs.len() == 1).unwrap();
+        assert_eq!(source.update.count, 11);
+        assert!(graph.annotate(&stats).to_string().contains("reds9"));
+        Ok(())
+    }
+}
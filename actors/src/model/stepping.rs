@@ -0,0 +1,485 @@
+/*!
+# Deterministic model executor
+
+[Model]`<`[Stepping]`>` is an alternative to [Model]`<`[Running]`>` that runs all the actors
+on the current thread, one sample of the simulation sampling frequency at a time.
+
+The actors are scheduled following the topological order of the model graph,
+bootstrapped outputs are ignored when sorting the actors as they break feedback loops.
+At each step, the scheduler sweeps the actors in that order, performing for each actor
+the [Operation]s of its loop (collect, update, distribute) that are due at this step and
+that can proceed without waiting on a channel, and it does so until no more progress can be made.
+
+The actors execution order is always the same and so are the results of the model.
+
+The operations of the actors are driven by a single-threaded [tokio] runtime owned by the model,
+so clients implementing the asynchronous interface are supported.
+As it blocks the current thread, a stepping model must not be used from within an asynchronous context,
+e.g. wrap it into [tokio::task::spawn_blocking] instead.
+
+```
+use gmt_dos_actors::prelude::*;
+use gmt_dos_clients::{logging::Logging, signals::Signals};
+use interface::UID;
+
+#[derive(UID)]
+enum Sig {}
+
+let mut source: Initiator<_> = Signals::new(1, 10).into();
+let logging = Logging::<f64>::default().into_arcx();
+let mut sink = Terminator::<_>::new(logging.clone());
+source.add_output().build::<Sig>().into_input(&mut sink)?;
+
+let mut model = Model::new(vec![Box::new(source), Box::new(sink)])
+    .check()?
+    .stepping()?;
+model.step_n(5)?;
+model.run_until(|step| step == 8)?;
+model.wait()?;
+# Ok::<(), anyhow::Error>(())
+```
+
+[Running]: crate::model::Running
+[Operation]: crate::framework::model::Operation
+*/
+
+use super::{Completed, Model, ModelError, ModelFailure, Ready, Result, Stepping};
+use crate::{
+    actor::PlainActor,
+    framework::model::{Sequence, Task, TaskError},
+    ActorError::{Disconnected, DropRecv, DropSend},
+};
+use std::{collections::HashMap, marker::PhantomData, time::Instant};
+use tokio::runtime::{Builder, Runtime};
+
+/// Actor task and its progression through its [Sequence] of operations
+pub(crate) struct Stepper {
+    pub(super) name: String,
+    pub(super) task: Option<Box<dyn Task>>,
+    sequence: Sequence,
+    pub(super) cursor: usize,
+}
+impl Stepper {
+    fn new(task: Box<dyn Task>) -> Self {
+        Self {
+            name: task.as_plain().client,
+            sequence: task.sequence(),
+            task: Some(task),
+            cursor: 0,
+        }
+    }
+    /// Performs all the operations due at `step` that are ready
+    ///
+    /// Returns `true` if at least one operation has been performed
+    fn advance(&mut self, step: usize, runtime: &Runtime) -> std::result::Result<bool, TaskError> {
+        let mut progress = false;
+        while let Some(task) = self.task.as_mut() {
+            let Some((operation, i)) = self.sequence.get(self.cursor) else {
+                self.task = None;
+                return Ok(true);
+            };
+            if i > step || !task.is_ready(operation) {
+                break;
+            }
+            progress = true;
+            if let Err(e) = task.execute(operation, runtime) {
+                // dropping the task disconnects its inputs and outputs
+                self.task = None;
+                return match e {
+                    TaskError::FromActor(Disconnected(msg)) => {
+                        log::debug!("{} has been disconnected", msg);
+                        Ok(true)
+                    }
+                    TaskError::FromActor(DropRecv { msg, .. } | DropSend { msg, .. }) => {
+                        log::debug!("{} has been dropped", msg);
+                        Ok(true)
+                    }
+                    e => Err(e),
+                };
+            }
+            self.cursor += 1;
+        }
+        Ok(progress)
+    }
+    /// Checks if the next operation is due at `step` but cannot proceed
+    fn is_waiting(&self, step: usize) -> bool {
+        self.task.is_some()
+            && self
+                .sequence
+                .get(self.cursor)
+                .is_some_and(|(_, i)| i <= step)
+    }
+}
+
+/// Actors execution schedule
+pub(crate) struct Schedule {
+    pub(super) steppers: Vec<Stepper>,
+    pub(super) step: usize,
+    runtime: Runtime,
+}
+impl Schedule {
+    /// Sorts the tasks in topological order
+    ///
+    /// Actors that are part of a feedback loop without bootstrapped outputs
+    /// are appended in the order they were given to the model.
+    fn new(tasks: Vec<Box<dyn Task>>) -> Result<Self> {
+        let runtime = Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(ModelError::Runtime)?;
+        let plains: Vec<_> = tasks.iter().map(|task| task.as_plain()).collect();
+        let mut tasks: Vec<_> = tasks.into_iter().map(Some).collect();
+        Ok(Self {
+            steppers: order(&plains)
+                .into_iter()
+                .filter_map(|i| tasks[i].take())
+                .map(Stepper::new)
+                .collect(),
+            step: 0,
+            runtime,
+        })
+    }
+    fn is_completed(&self) -> bool {
+        self.steppers.iter().all(|stepper| stepper.task.is_none())
+    }
+    fn step(&mut self) -> Result<()> {
+        let mut any_progress = false;
+        loop {
+            let mut progress = false;
+            for stepper in self.steppers.iter_mut() {
+                progress |= stepper
+                    .advance(self.step, &self.runtime)
+                    .map_err(|e| match e {
+                        TaskError::Client(failure) => {
+                            ModelError::Failure(Box::new(ModelFailure::from(*failure)))
+                        }
+                        e => Box::new(e).into(),
+                    })?;
+            }
+            if !progress {
+                break;
+            }
+            any_progress = true;
+        }
+        if !any_progress && !self.is_completed() {
+            let active = self
+                .steppers
+                .iter()
+                .filter(|stepper| stepper.task.is_some());
+            if active.clone().all(|stepper| stepper.is_waiting(self.step)) {
+                return Err(ModelError::Stalled(
+                    self.step,
+                    active
+                        .map(|stepper| stepper.name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                ));
+            }
+        }
+        self.step += 1;
+        Ok(())
+    }
+}
+
+/// Sorts the actors in topological order
+///
+/// Actors that are part of a feedback loop without bootstrapped outputs
+/// are appended in the order they were given to the model.
+pub(crate) fn order(plains: &[PlainActor]) -> Vec<usize> {
+    let producers: HashMap<u64, usize> = plains
+        .iter()
+        .enumerate()
+        .flat_map(|(i, actor)| {
+            actor
+                .outputs
+                .iter()
+                .flatten()
+                .filter(|output| !matches!(output, crate::actor::plain::IO::Bootstrap(_)))
+                .map(move |output| (output.hash(), i))
+        })
+        .collect();
+    let upstreams: Vec<Vec<usize>> = plains
+        .iter()
+        .enumerate()
+        .map(|(i, actor)| {
+            let mut upstream: Vec<_> = actor
+                .inputs
+                .iter()
+                .flatten()
+                .filter_map(|input| producers.get(&input.hash()).copied())
+                .filter(|&j| j != i)
+                .collect();
+            upstream.sort_unstable();
+            upstream.dedup();
+            upstream
+        })
+        .collect();
+    let mut order = Vec::with_capacity(plains.len());
+    let mut scheduled = vec![false; plains.len()];
+    while order.len() < plains.len() {
+        match (0..plains.len())
+            .find(|&i| !scheduled[i] && upstreams[i].iter().all(|&j| scheduled[j]))
+        {
+            Some(i) => {
+                scheduled[i] = true;
+                order.push(i);
+            }
+            None => {
+                let remaining: Vec<_> = (0..plains.len()).filter(|&i| !scheduled[i]).collect();
+                log::warn!(
+                    "feedback loop without bootstrapped output between: {}",
+                    remaining
+                        .iter()
+                        .map(|&i| plains[i].client.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+                order.extend(remaining);
+                break;
+            }
+        }
+    }
+    order
+}
+
+impl Model<Ready> {
+    /// Schedules the actors for the deterministic executor
+    ///
+    /// Actors within a [System](crate::system::System) are scheduled individually
+    pub fn stepping(self) -> Result<Model<Stepping>> {
+        let tasks: Vec<_> = self
+            .actors
+            .into_iter()
+            .flatten()
+            .flat_map(|task| task.into_tasks())
+            .collect();
+        Ok(Model::<Stepping> {
+            name: self.name,
+            actors: None,
+            task_set: None,
+            schedule: Some(Schedule::new(tasks)?),
+            token: None,
+            report: None,
+            stats: self.stats,
+            state: PhantomData,
+            start: Instant::now(),
+            verbose: self.verbose,
+            elapsed_time: Default::default(),
+        })
+    }
+}
+
+impl Model<Stepping> {
+    pub(super) fn schedule(&mut self) -> &mut Schedule {
+        self.schedule
+            .as_mut()
+            .expect("the stepping model has no schedule")
+    }
+    /// Returns the number of steps performed so far
+    pub fn step_count(&self) -> usize {
+        self.schedule.as_ref().map_or(0, |schedule| schedule.step)
+    }
+    /// Checks if all the actors have completed their tasks
+    pub fn is_completed(&self) -> bool {
+        self.schedule
+            .as_ref()
+            .is_none_or(|schedule| schedule.is_completed())
+    }
+    /// Advances the model by one sample of the simulation sampling frequency
+    pub fn step(&mut self) -> Result<&mut Self> {
+        self.schedule().step()?;
+        Ok(self)
+    }
+    /// Advances the model by `n` samples or until completion
+    pub fn step_n(&mut self, n: usize) -> Result<&mut Self> {
+        for _ in 0..n {
+            if self.is_completed() {
+                break;
+            }
+            self.step()?;
+        }
+        Ok(self)
+    }
+    /// Advances the model until the predicate on the step count is true or until completion
+    pub fn run_until<F>(&mut self, mut predicate: F) -> Result<&mut Self>
+    where
+        F: FnMut(usize) -> bool,
+    {
+        while !self.is_completed() && !predicate(self.step_count()) {
+            self.step()?;
+        }
+        Ok(self)
+    }
+    /// Advances the model until all the actors have completed their tasks
+    pub fn wait(mut self) -> Result<Model<Completed>> {
+        self.run_until(|_| false)?;
+        let elapsed_time = Instant::now().duration_since(self.start);
+        self.verbose.then(|| {
+            eprintln!(
+                "[{}] COMPLETED {} steps in {}",
+                self.name
+                    .as_ref()
+                    .unwrap_or(&String::from("Model"))
+                    .to_uppercase(),
+                self.step_count(),
+                humantime::format_duration(elapsed_time)
+            )
+        });
+        Ok(Model::<Completed> {
+            name: self.name,
+            actors: None,
+            task_set: None,
+            schedule: None,
+            token: None,
+            report: None,
+            stats: self.stats,
+            state: PhantomData,
+            start: Instant::now(),
+            verbose: self.verbose,
+            elapsed_time: elapsed_time.as_secs_f64(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use gmt_dos_clients::{
+        logging::Logging,
+        sampler::Sampler,
+        signals::{Signal, Signals},
+    };
+    use interface::{AsyncRead, AsyncUpdate, AsyncWrite, ClientHooks, Data, UID};
+    use std::{convert::Infallible, time::Duration};
+
+    #[derive(UID)]
+    enum Ramp {}
+    #[derive(UID)]
+    enum Decimated {}
+
+    #[derive(Default)]
+    struct Delay(Vec<f64>);
+    impl AsyncUpdate for Delay {
+        type Error = Infallible;
+        async fn update(&mut self) -> std::result::Result<(), Infallible> {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+            Ok(())
+        }
+    }
+    impl ClientHooks for Delay {}
+    impl AsyncRead<Ramp> for Delay {
+        type Error = Infallible;
+        async fn read(&mut self, data: Data<Ramp>) -> std::result::Result<(), Infallible> {
+            self.0 = data.into();
+            Ok(())
+        }
+    }
+    impl AsyncWrite<Decimated> for Delay {
+        type Error = Infallible;
+        async fn write(&mut self) -> std::result::Result<Option<Data<Decimated>>, Infallible> {
+            Ok(Some(self.0.clone().into()))
+        }
+    }
+
+    fn build() -> anyhow::Result<(
+        Model<model::Ready>,
+        std::sync::Arc<tokio::sync::Mutex<Logging<f64>>>,
+    )> {
+        let mut source: Initiator<_> = Signals::new(1, 20)
+            .channels(Signal::Ramp { a: 1., b: 0. })
+            .into();
+        let mut sampler: Actor<_, 1, 5> = Sampler::<Vec<f64>, Ramp, Decimated>::default().into();
+        let logging = Logging::<f64>::default().into_arcx();
+        let mut sink = Terminator::<_, 5>::new(logging.clone());
+        source
+            .add_output()
+            .build::<Ramp>()
+            .into_input(&mut sampler)?;
+        sampler
+            .add_output()
+            .build::<Decimated>()
+            .into_input(&mut sink)?;
+        let model = Model::new(vec![Box::new(sink), Box::new(sampler), Box::new(source)])
+            .quiet()
+            .check()?;
+        Ok((model, logging))
+    }
+
+    #[test]
+    fn stepping() -> anyhow::Result<()> {
+        let (model, logging) = build()?;
+        let mut model = model.stepping()?;
+        model.step_n(6)?;
+        assert_eq!(model.step_count(), 6);
+        assert_eq!(*logging.blocking_lock().as_slice(), [0., 5.]);
+        model.run_until(|step| step == 11)?;
+        assert_eq!(*logging.blocking_lock().as_slice(), [0., 5., 10.]);
+        model.wait()?;
+        assert_eq!(*logging.blocking_lock().as_slice(), [0., 5., 10., 15.]);
+        Ok(())
+    }
+
+    #[test]
+    fn async_client() -> anyhow::Result<()> {
+        let mut source: Initiator<_> = Signals::new(1, 5)
+            .channels(Signal::Ramp { a: 1., b: 0. })
+            .into();
+        let mut delay: Actor<_> = interface::Async::new(Delay::default()).into();
+        let logging = Logging::<f64>::default().into_arcx();
+        let mut sink = Terminator::<_>::new(logging.clone());
+        source.add_output().build::<Ramp>().into_input(&mut delay)?;
+        delay
+            .add_output()
+            .build::<Decimated>()
+            .into_input(&mut sink)?;
+        model!(source, delay, sink)
+            .quiet()
+            .check()?
+            .stepping()?
+            .wait()?;
+        assert_eq!(*logging.blocking_lock().as_slice(), [0., 1., 2., 3., 4.]);
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn stepping_vs_running() -> anyhow::Result<()> {
+        let (model, logging) = build()?;
+        model.run().await?;
+        let running = logging.lock().await.as_slice().to_vec();
+        let (model, logging) = build()?;
+        tokio::task::spawn_blocking(move || model.stepping()?.wait()).await??;
+        let stepping = logging.lock().await.as_slice().to_vec();
+        assert_eq!(running, stepping);
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn fractional_rates() -> anyhow::Result<()> {
+        let build = || -> anyhow::Result<_> {
+            let mut source: Initiator<_, 2> = Signals::new(1, 20)
+                .channels(Signal::Ramp { a: 1., b: 0. })
+                .into();
+            let mut sampler: Actor<_, 2, 5> =
+                Sampler::<Vec<f64>, Ramp, Decimated>::default().into();
+            let logging = Logging::<f64>::default().into_arcx();
+            let mut sink = Terminator::<_, 5>::new(logging.clone());
+            source
+                .add_output()
+                .build::<Ramp>()
+                .into_input(&mut sampler)?;
+            sampler
+                .add_output()
+                .build::<Decimated>()
+                .into_input(&mut sink)?;
+            Ok((model!(source, sampler, sink).quiet().check()?, logging))
+        };
+        let (model, logging) = build()?;
+        model.run().await?;
+        let running = logging.lock().await.as_slice().to_vec();
+        assert_eq!(running, [0., 2., 5., 7., 10., 12., 15., 17.]);
+        let (model, logging) = build()?;
+        tokio::task::spawn_blocking(move || model.stepping()?.wait()).await??;
+        assert_eq!(*logging.lock().await.as_slice(), running);
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
 use crate::framework::model::Task;
 
-use super::{Actors, Model, ModelError, Ready, Result, Unknown};
+use super::{Actors, Diagnostic, Model, ModelError, Provenance, Ready, Result, Unknown};
 use std::{marker::PhantomData, time::Instant};
 
 impl Default for Model<Unknown> {
@@ -9,6 +9,10 @@ impl Default for Model<Unknown> {
             name: Default::default(),
             actors: Default::default(),
             task_set: Default::default(),
+            schedule: Default::default(),
+            token: Default::default(),
+            report: Default::default(),
+            stats: Default::default(),
             state: Default::default(),
             start: Instant::now(),
             verbose: true,
@@ -33,6 +37,10 @@ impl Model<Unknown> {
             name: None,
             actors: Some(actors),
             task_set: None,
+            schedule: None,
+            token: None,
+            report: None,
+            stats: None,
             state: PhantomData,
             start: Instant::now(),
             verbose: true,
@@ -51,12 +59,26 @@ impl Model<Unknown> {
         self.verbose = false;
         self
     }
-    pub fn verbose(mut self,verbose: bool) -> Self {
+    pub fn verbose(mut self, verbose: bool) -> Self {
         self.verbose = verbose;
         self
     }
     /// Validates actors inputs and outputs
+    ///
+    /// The check fails if the [diagnostics](Model::diagnostics) of the model
+    /// have any issue with [Severity::Error](super::Severity::Error).
+    /// The clients are given the rate of the inputs of their actor,
+    /// see [set_inputs_rate](interface::ClientHooks::set_inputs_rate),
+    /// and the default [provenance](super::provenance) of the model
     pub fn check(self) -> Result<Model<Ready>> {
+        self.validate(true)
+    }
+    /// Validates actors inputs and outputs, the [diagnostics](Model::diagnostics) issues,
+    /// except rate mismatches, being logged as warnings
+    pub fn lenient_check(self) -> Result<Model<Ready>> {
+        self.validate(false)
+    }
+    fn validate(self, strict: bool) -> Result<Model<Ready>> {
         let (n_inputs, n_outputs) = self.n_io();
         let name = self.name.clone().unwrap_or_default();
         assert_eq!(
@@ -83,24 +105,63 @@ impl Model<Unknown> {
                 "{} I/O hashes difference: expected 0, found {}, did you forget to add some actors to the model?",
                 self.name.unwrap_or_default(),
                 hashes_diff);
-                Ok(Model::<Ready> {
+                let diagnostics = self.diagnostics();
+                if let Some(Diagnostic::RateMismatch {
+                    output,
+                    output_rate,
+                    input,
+                    input_rate,
+                    ..
+                }) = diagnostics
+                    .iter()
+                    .find(|diagnostic| matches!(diagnostic, Diagnostic::RateMismatch { .. }))
+                {
+                    return Err(ModelError::RateMismatch(
+                        output.clone(),
+                        *output_rate,
+                        input.clone(),
+                        *input_rate,
+                    ));
+                }
+                if strict && diagnostics.has_errors() {
+                    return Err(ModelError::Diagnostics(diagnostics));
+                }
+                diagnostics
+                    .iter()
+                    .for_each(|diagnostic| log::warn!("{diagnostic}"));
+                actors.iter().for_each(|actor| actor.set_inputs_rate());
+                let model = Model::<Ready> {
                     name: self.name,
                     actors: self.actors,
                     task_set: None,
+                    schedule: None,
+                    token: None,
+                    report: None,
+                    stats: None,
                     state: PhantomData,
                     start: Instant::now(),
                     verbose: self.verbose,
                     elapsed_time: Default::default(),
-                })
+                };
+                Provenance::default().inject(&model);
+                Ok(model)
             }
             None => Err(ModelError::NoActors),
         }
     }
     pub fn skip_check(self) -> Model<Ready> {
+        self.actors
+            .iter()
+            .flatten()
+            .for_each(|actor| actor.set_inputs_rate());
         Model::<Ready> {
             name: self.name,
             actors: self.actors,
             task_set: None,
+            schedule: None,
+            token: None,
+            report: None,
+            stats: None,
             state: PhantomData,
             start: Instant::now(),
             verbose: self.verbose,
@@ -1,9 +1,10 @@
 use crate::{
     actor::{Actor, PlainActor},
     framework::{
-        model::{Check, CheckError, Task, TaskError},
+        model::{Cancellation, Check, CheckError, Task, TaskError},
         network::{ActorOutput, AddActorInput},
     },
+    model::stats::Profiler,
     prelude::{AddActorOutput, GetName, Model, Unknown},
     ActorError,
 };
@@ -39,15 +40,89 @@ where
         Ok(())
     }
 
+    async fn cancellable_task(
+        self: Box<Self>,
+        cancellation: Cancellation,
+    ) -> std::result::Result<(), TaskError> {
+        let name = self.name();
+        let verbose = self.verbose;
+        // a failure of a client within the system is returned as a `ModelError::Failure`,
+        // unwrapped by the model the system belongs to
+        Model::<Unknown>::from_iter(Box::new(self.sys))
+            .name(name)
+            .verbose(verbose)
+            .skip_check()
+            .spawn(cancellation.child())
+            .await?;
+        if cancellation.token().is_cancelled() {
+            Err(TaskError::Cancelled(name.to_string()))
+        } else {
+            Ok(())
+        }
+    }
+
     fn as_plain(&self) -> PlainActor {
         self.plain()
     }
+
+    fn as_plains(&self) -> Vec<PlainActor> {
+        self.into_iter()
+            .filter_map(|check| check.as_task().map(|task| task.as_plains()))
+            .flatten()
+            .collect()
+    }
+
+    fn into_tasks(self: Box<Self>) -> Vec<Box<dyn Task>> {
+        Box::new(self.sys)
+            .into_iter()
+            .flat_map(|task| task.into_tasks())
+            .collect()
+    }
+
+    fn profile(&self) -> Vec<Profiler> {
+        self.into_iter()
+            .filter_map(|check| check.as_task().map(|task| task.profile()))
+            .flatten()
+            .collect()
+    }
+
+    fn provenance(&self) -> Vec<(String, String)> {
+        self.into_iter()
+            .filter_map(|check| {
+                check.as_task().map(|task| {
+                    let name = check._as_plain().client;
+                    task.provenance()
+                        .into_iter()
+                        .map(move |(key, value)| (format!("{name}/{key}"), value))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .flatten()
+            .collect()
+    }
+
+    fn set_provenance(&self, provenance: &[(String, String)]) {
+        self.into_iter().for_each(|check| {
+            if let Some(task) = check.as_task() {
+                task.set_provenance(provenance);
+            }
+        });
+    }
+
+    fn set_inputs_rate(&self) {
+        self.into_iter().for_each(|check| {
+            if let Some(task) = check.as_task() {
+                task.set_inputs_rate();
+            }
+        });
+    }
 }
 
 impl<T> Check for Sys<T>
 where
     T: System,
     for<'a> &'a T: IntoIterator<Item = Box<&'a dyn Check>>,
+    Box<T>: IntoIterator<Item = Box<dyn Task>>,
 {
     fn check_inputs(&self) -> std::result::Result<(), CheckError> {
         self.into_iter()
@@ -93,6 +168,10 @@ where
     fn is_system(&self) -> bool {
         true
     }
+
+    fn as_task(&self) -> Option<&dyn Task> {
+        Some(self)
+    }
 }
 
 impl<T: System> GetName for T {
@@ -1,5 +1,5 @@
 use gmt_dos_actors::prelude::*;
-use interface::{Data, Read, Update, Write, UID};
+use interface::{ClientHooks, Data, Read, Update, Write, UID};
 use nanorand::{Rng, WyRand};
 
 // ANCHOR: client
@@ -20,6 +20,7 @@ enum Out {}
 // ANCHOR_END: client_out
 // ANCHOR: client_io_update
 impl Update for Client {}
+impl ClientHooks for Client {}
 // ANCHOR_END: client_io_update
 // ANCHOR: client_io_read
 impl Read<In> for Client {
@@ -49,6 +50,7 @@ impl RandGen {
     }
 }
 impl Update for RandGen {}
+impl ClientHooks for RandGen {}
 impl Write<In> for RandGen {
     fn write(&mut self) -> Option<Data<In>> {
         self.data.pop().map(|val| Data::new(val))
@@ -61,6 +63,7 @@ struct DataLogger {
     data: Vec<f32>,
 }
 impl Update for DataLogger {}
+impl ClientHooks for DataLogger {}
 impl Read<Out> for DataLogger {
     fn read(&mut self, data: Data<Out>) {
         self.data.push(*data);
@@ -5,7 +5,7 @@ use gmt_dos_clients::{
     sampler::Sampler,
     signals::{Signal, Signals},
 };
-use interface::{Data, Read, Update, Write, UID};
+use interface::{ClientHooks, Data, Read, Update, Write, UID};
 use std::collections::HashMap;
 
 // ANCHOR: io
@@ -45,6 +45,7 @@ impl Update for SignedDiff {
             .for_each(|(d, delta)| *delta = -d * delta.signum());
     }
 }
+impl ClientHooks for SignedDiff {}
 impl Read<A> for SignedDiff {
     fn read(&mut self, data: Data<A>) {
         self.right = data.clone();
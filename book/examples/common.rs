@@ -1,4 +1,4 @@
-use interface::{Data, Read, Update, Write, UID};
+use interface::{ClientHooks, Data, Read, Update, Write, UID};
 
 #[derive(UID)]
 pub enum U {}
@@ -22,6 +22,7 @@ impl Default for Sum {
     }
 }
 impl Update for Sum {}
+impl ClientHooks for Sum {}
 impl Read<U> for Sum {
     fn read(&mut self, data: Data<U>) {
         self.left = data.clone();
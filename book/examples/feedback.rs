@@ -4,7 +4,7 @@ use gmt_dos_clients::{
     logging::Logging,
     signals::{Signal, Signals},
 };
-use interface::{Data, Read, Update, Write, UID};
+use interface::{ClientHooks, Data, Read, Update, Write, UID};
 
 // ANCHOR: io
 #[derive(UID)]
@@ -29,6 +29,7 @@ impl Default for Sum {
     }
 }
 impl Update for Sum {}
+impl ClientHooks for Sum {}
 impl Read<U> for Sum {
     fn read(&mut self, data: Data<U>) {
         self.left = data.clone();
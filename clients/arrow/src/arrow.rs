@@ -1,4 +1,4 @@
-use std::{collections::HashMap, fmt::Display, mem::size_of};
+use std::{collections::HashMap, fmt::Display, mem::size_of, sync::Mutex};
 
 use apache_arrow::{
     array::BufferBuilder,
@@ -6,6 +6,7 @@ use apache_arrow::{
     record_batch::RecordBatch,
 };
 use interface::{Entry, UniqueIdentifier, print_info};
+use parquet::basic::Compression;
 
 use crate::{
     ArrowBuffer, BufferDataType, BufferObject, DropOption, FileFormat, LogData, MAX_CAPACITY_BYTE,
@@ -13,25 +14,37 @@ use crate::{
 
 mod arrow;
 mod builder;
+pub(crate) mod stream;
 // mod get;
 pub(crate) mod iter;
+pub(crate) use arrow::parquet_path;
 pub use builder::ArrowBuilder;
+use stream::StreamWriter;
 
 /// Apache [Arrow](https://docs.rs/arrow) client
 pub struct Arrow {
     n_step: usize,
     capacities: Vec<usize>,
     buffers: Vec<(Box<dyn BufferObject>, DataType)>,
-    metadata: Option<HashMap<String, String>>,
+    pub(crate) metadata: Option<HashMap<String, String>>,
     pub(crate) step: usize,
     pub(crate) n_entry: usize,
     record: Option<RecordBatch>,
     batch: Option<Vec<RecordBatch>>,
-    drop_option: DropOption,
+    pub(crate) drop_option: DropOption,
     pub(crate) decimation: usize,
     pub(crate) count: usize,
     file_format: FileFormat,
     pub(crate) batch_size: Option<usize>,
+    pub(crate) streaming: bool,
+    sync: bool,
+    n_record: usize,
+    compression: Option<Compression>,
+    column_compression: Vec<(String, Compression)>,
+    writer: Option<Mutex<StreamWriter>>,
+    sampling_frequency: Option<f64>,
+    pub(crate) inputs_rate: usize,
+    n_row: usize,
 }
 impl Default for Arrow {
     fn default() -> Self {
@@ -49,6 +62,15 @@ impl Default for Arrow {
             count: 0,
             file_format: Default::default(),
             batch_size: None,
+            streaming: false,
+            sync: false,
+            n_record: 0,
+            compression: None,
+            column_compression: Vec::new(),
+            writer: None,
+            sampling_frequency: None,
+            inputs_rate: 1,
+            n_row: 0,
         }
     }
 }
@@ -82,7 +104,10 @@ where
     U: 'static + Send + Sync + UniqueIdentifier<DataType = Vec<T>>,
 {
     fn entry(&mut self, size: usize) {
-        let mut capacity = size * (1 + self.n_step / self.decimation);
+        let mut capacity = match self.batch_size {
+            Some(batch_size) if self.streaming => size * batch_size,
+            _ => size * (1 + self.n_step / self.decimation),
+        };
         //log::info!("Buffer capacity: {}", capacity);
         if capacity * size_of::<T>() > MAX_CAPACITY_BYTE {
             capacity = MAX_CAPACITY_BYTE / size_of::<T>();
@@ -162,6 +187,11 @@ impl Drop for Arrow {
                     .cloned()
                     .unwrap_or_else(|| "data".to_string());
                 match self.file_format {
+                    FileFormat::Parquet if self.streaming => {
+                        if let Err(e) = self.close_stream(file_name) {
+                            print_info("Arrow error", Some(&e));
+                        }
+                    }
                     FileFormat::Parquet => {
                         if let Err(e) = self.to_parquet(file_name) {
                             print_info("Arrow error", Some(&e));
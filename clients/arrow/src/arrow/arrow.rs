@@ -1,8 +1,8 @@
 use std::{
     env,
     fs::{DirBuilder, File},
-    path::Path,
-    sync::Arc,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
 };
 
 use apache_arrow::{
@@ -13,11 +13,12 @@ use apache_arrow::{
 };
 use interface::print_info;
 use parquet::{
-    arrow::{ArrowWriter, arrow_reader::ParquetRecordBatchReaderBuilder},
+    arrow::{ArrowSchemaConverter, ArrowWriter, arrow_reader::ParquetRecordBatchReaderBuilder},
     file::properties::WriterProperties,
 };
 
-use crate::{Arrow, ArrowError, DropOption, FileFormat, Result};
+use super::StreamWriter;
+use crate::{Arrow, ArrowError, DropOption, FileFormat, Result, time::time_columns};
 
 impl Arrow {
     /// Writes the record to file
@@ -29,6 +30,11 @@ impl Arrow {
                     .cloned()
                     .unwrap_or_else(|| "data".to_string());
                 match self.file_format {
+                    FileFormat::Parquet if self.streaming => {
+                        if let Err(e) = self.stream(file_name) {
+                            print_info("Arrow error", Some(&e));
+                        }
+                    }
                     FileFormat::Parquet => {
                         if let Err(e) = self.to_parquet(file_name) {
                             print_info("Arrow error", Some(&e));
@@ -67,36 +73,107 @@ impl Arrow {
     /// Returns the data record
     pub fn record(&mut self) -> Result<&RecordBatch> {
         if self.record.is_none() {
-            let mut lists: Vec<Arc<dyn Array>> = vec![];
-            for ((buffer, buffer_data_type), n) in self.buffers.iter_mut().zip(&self.capacities) {
-                let list = buffer.into_list(
-                    self.batch_size.unwrap_or(self.count / self.n_entry),
-                    *n,
-                    buffer_data_type.clone(),
-                )?;
-                lists.push(Arc::new(list));
-            }
+            let n_step = self
+                .batch_size
+                .unwrap_or_else(|| self.count.checked_div(self.n_entry).unwrap_or_default());
+            self.record = Some(self.new_record(n_step)?);
+        }
+        self.record.as_ref().ok_or(ArrowError::NoRecord)
+    }
+    /// Moves the content of the buffers into a new record of `n_step` rows
+    fn new_record(&mut self, n_step: usize) -> Result<RecordBatch> {
+        let mut lists: Vec<Arc<dyn Array>> = vec![];
+        for ((buffer, buffer_data_type), n) in self.buffers.iter_mut().zip(&self.capacities) {
+            let list = buffer.into_list(n_step, *n, buffer_data_type.clone())?;
+            lists.push(Arc::new(list));
+        }
 
-            let fields: Vec<_> = self
-                .buffers
-                .iter()
-                .map(|(buffer, data_type)| {
-                    Field::new(
-                        &buffer.who(),
-                        DataType::List(Arc::new(Field::new("values", data_type.clone(), false))),
-                        false,
-                    )
-                })
-                .collect();
-            let schema = Arc::new(if let Some(metadata) = self.metadata.as_ref() {
-                Schema::new_with_metadata(fields, metadata.clone())
-            } else {
-                Schema::new(fields)
-            });
+        let mut fields: Vec<_> = self
+            .buffers
+            .iter()
+            .map(|(buffer, data_type)| {
+                Field::new(
+                    &buffer.who(),
+                    DataType::List(Arc::new(Field::new("values", data_type.clone(), false))),
+                    false,
+                )
+            })
+            .collect();
+        if let Some(sampling_frequency) = self.sampling_frequency {
+            let rows = self.n_row..self.n_row + n_step;
+            let (time_fields, time_lists): (Vec<_>, Vec<_>) =
+                time_columns(sampling_frequency, self.inputs_rate, self.decimation, rows)
+                    .into_iter()
+                    .unzip();
+            fields.splice(0..0, time_fields);
+            lists.splice(0..0, time_lists);
+        }
+        self.n_row += n_step;
+        let schema = Arc::new(if let Some(metadata) = self.metadata.as_ref() {
+            Schema::new_with_metadata(fields, metadata.clone())
+        } else {
+            Schema::new(fields)
+        });
 
-            self.record = Some(RecordBatch::try_new(Arc::clone(&schema), lists)?);
+        Ok(RecordBatch::try_new(Arc::clone(&schema), lists)?)
+    }
+    /// Returns the Parquet writer properties with the compression of each column
+    fn writer_properties(&self, schema: &Schema) -> Result<WriterProperties> {
+        let mut builder = WriterProperties::builder();
+        if let Some(compression) = self.compression {
+            builder = builder.set_compression(compression);
         }
-        self.record.as_ref().ok_or(ArrowError::NoRecord)
+        if !self.column_compression.is_empty() {
+            let descriptor = ArrowSchemaConverter::new().convert(schema)?;
+            for (name, compression) in &self.column_compression {
+                for column in descriptor
+                    .columns()
+                    .iter()
+                    .filter(|column| column.path().parts()[0] == *name)
+                {
+                    builder = builder.set_column_compression(column.path().clone(), *compression);
+                }
+            }
+        }
+        Ok(builder.build())
+    }
+    /// Appends the data logged since the last row group to the [Parquet](https://docs.rs/parquet) data file
+    ///
+    /// The file is created at the first call and the buffers are emptied after each row group
+    pub(crate) fn stream<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        let n_step = self.count.checked_div(self.n_entry).unwrap_or_default() - self.n_record;
+        if n_step == 0 {
+            return Ok(());
+        }
+        let record = self.new_record(n_step)?;
+        if self.writer.is_none() {
+            let root = parquet_path(path)?;
+            let props = self.writer_properties(&record.schema())?;
+            self.writer = Some(Mutex::new(
+                StreamWriter::try_new(root, record.schema(), props)?.sync(self.sync),
+            ));
+        }
+        if let Some(writer) = self.writer.as_mut() {
+            let writer = writer.get_mut().unwrap();
+            writer.write(&record)?;
+            log::debug!("{n_step} steps appended to {:?}", writer.path());
+        }
+        self.n_record += n_step;
+        Ok(())
+    }
+    /// Appends the remaining data to the [Parquet](https://docs.rs/parquet) data file and closes it
+    pub(crate) fn close_stream<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        self.stream(path)?;
+        self.finish_stream()?;
+        self.drop_option = DropOption::NoSave;
+        Ok(())
+    }
+    /// Closes the streamed [Parquet](https://docs.rs/parquet) data file, if any
+    pub(crate) fn finish_stream(&mut self) -> Result<()> {
+        if let Some(writer) = self.writer.take() {
+            writer.into_inner().unwrap().close()?;
+        }
+        Ok(())
     }
     /// Saves the data to a [Parquet](https://docs.rs/parquet) data file
     ///
@@ -106,15 +183,9 @@ impl Arrow {
     pub fn to_parquet<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
         // let batch = self.record()?;
         let batch = self.concat_batches()?;
-        let root_env = env::var("DATA_REPO").unwrap_or_else(|_| ".".to_string());
-        let root = Path::new(&root_env).join(&path).with_extension("parquet");
-        if let Some(path) = root.parent() {
-            if !path.is_dir() {
-                DirBuilder::new().recursive(true).create(&path)?;
-            }
-        };
+        let root = parquet_path(path)?;
         let file = File::create(&root)?;
-        let props = WriterProperties::builder().build();
+        let props = self.writer_properties(&batch.schema())?;
         let mut writer = ArrowWriter::try_new(file, Arc::clone(&batch.schema()), Some(props))?;
         writer.write(&batch)?;
         writer.close()?;
@@ -162,6 +233,15 @@ impl Arrow {
             count: 0,
             file_format: FileFormat::Parquet,
             batch_size: None,
+            streaming: false,
+            sync: false,
+            n_record: 0,
+            compression: None,
+            column_compression: Vec::new(),
+            writer: None,
+            sampling_frequency: None,
+            inputs_rate: 1,
+            n_row: 0,
         })
     }
     #[cfg(feature = "matio-rs")]
@@ -175,7 +255,13 @@ impl Arrow {
         let root = Path::new(&root_env).join(&path).with_extension("mat");
         let mat_file = MatFile::save(&root)?;
         let mut n_sample = 0;
-        for field in batch.schema().fields() {
+        // the `Step` and `Time` columns are not lists
+        for field in batch
+            .schema()
+            .fields()
+            .iter()
+            .filter(|field| matches!(field.data_type(), DataType::List(_)))
+        {
             let name = field.name();
             let data: Vec<Vec<f64>> = self.iter(name)?.collect();
             n_sample = data.len();
@@ -201,3 +287,16 @@ impl Arrow {
         Ok(())
     }
 }
+
+/// Returns the path to the [Parquet](https://docs.rs/parquet) data file in `DATA_REPO`,
+/// creating the directory if it does not exist
+pub(crate) fn parquet_path<P: AsRef<Path>>(path: P) -> Result<PathBuf> {
+    let root_env = env::var("DATA_REPO").unwrap_or_else(|_| ".".to_string());
+    let root = Path::new(&root_env).join(&path).with_extension("parquet");
+    if let Some(path) = root.parent()
+        && !path.is_dir()
+    {
+        DirBuilder::new().recursive(true).create(path)?;
+    }
+    Ok(root)
+}
@@ -5,10 +5,11 @@ use apache_arrow::{
     datatypes::{ArrowNativeType, DataType},
 };
 use interface::UniqueIdentifier;
+use parquet::basic::Compression;
 
 use crate::{
     Arrow, ArrowBuffer, BufferDataType, BufferObject, DropOption, FileFormat, LogData,
-    MAX_CAPACITY_BYTE,
+    MAX_CAPACITY_BYTE, who,
 };
 
 /// Arrow format logger builder
@@ -22,6 +23,11 @@ pub struct ArrowBuilder {
     decimation: usize,
     file_format: FileFormat,
     batch_size: Option<usize>,
+    streaming: bool,
+    sync: bool,
+    compression: Option<Compression>,
+    column_compression: Vec<(String, Compression)>,
+    sampling_frequency: Option<f64>,
 }
 
 impl ArrowBuilder {
@@ -37,6 +43,11 @@ impl ArrowBuilder {
             decimation: 1,
             file_format: Default::default(),
             batch_size: None,
+            streaming: false,
+            sync: false,
+            compression: None,
+            column_compression: Vec::new(),
+            sampling_frequency: None,
         }
     }
     /// Adds an entry to the logger
@@ -71,6 +82,38 @@ impl ArrowBuilder {
         self.batch_size = Some(batch_size);
         self
     }
+    /// Streams the data to the Parquet file, one row group every `row_group_size` time steps
+    ///
+    /// The buffers are emptied after each row group, so the memory used by the logger
+    /// does not depend on the number of time steps.
+    /// If the process is killed, the Parquet file truncated after its last footer remains readable.
+    pub fn streaming(mut self, row_group_size: usize) -> Self {
+        self.batch_size = Some(row_group_size);
+        self.streaming = true;
+        self
+    }
+    /// Flushes the streamed Parquet file to the disk every time its footer is written
+    pub fn sync(mut self) -> Self {
+        self.sync = true;
+        self
+    }
+    /// Sets the Parquet compression of all the columns (default: uncompressed)
+    ///
+    /// ```
+    /// use gmt_dos_clients_arrow::{Arrow, Compression, ZstdLevel};
+    /// let logging = Arrow::builder(1000)
+    ///     .compression(Compression::ZSTD(ZstdLevel::default()))
+    ///     .build();
+    /// ```
+    pub fn compression(mut self, compression: Compression) -> Self {
+        self.compression = Some(compression);
+        self
+    }
+    /// Sets the Parquet compression of the column of the data `U`
+    pub fn column_compression<U: UniqueIdentifier>(mut self, compression: Compression) -> Self {
+        self.column_compression.push((who::<U>(), compression));
+        self
+    }
     /// Sets the name of the file to save the data to (default: "data.parquet")
     pub fn filename<S: Into<String>>(self, filename: S) -> Self {
         Self {
@@ -100,6 +143,14 @@ impl ArrowBuilder {
     pub fn decimation(self, decimation: usize) -> Self {
         Self { decimation, ..self }
     }
+    /// Adds the `Step` and `Time` columns with the simulation step index and time of each record
+    ///
+    /// The step index and the time are derived from the simulation `sampling_frequency` in Hz,
+    /// the rate of the inputs of the logger actor and the decimation factor (see [time](crate::time))
+    pub fn time(mut self, sampling_frequency: f64) -> Self {
+        self.sampling_frequency = Some(sampling_frequency);
+        self
+    }
     /// Builds the Arrow logger
     pub fn build(self) -> Arrow {
         /*if self.n_entry == 0 {
@@ -119,6 +170,15 @@ impl ArrowBuilder {
             count: 0,
             file_format: self.file_format,
             batch_size: self.batch_size,
+            streaming: self.streaming,
+            sync: self.sync,
+            n_record: 0,
+            compression: self.compression,
+            column_compression: self.column_compression,
+            writer: None,
+            sampling_frequency: self.sampling_frequency,
+            inputs_rate: 1,
+            n_row: 0,
         }
     }
 }
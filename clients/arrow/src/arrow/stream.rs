@@ -0,0 +1,207 @@
+/*!
+Streaming Parquet writer
+
+Each record is encoded in memory into Parquet row groups that are appended to the file.
+The footer with the metadata of all the row groups written so far is appended after the row groups,
+once the row groups written since the last footer are larger than the footer itself,
+so the time spent writing footers grows linearly with the size of the file.
+A footer is never overwritten: the next row groups are written after it,
+so if the process is killed, the file truncated after its last footer
+is a valid Parquet file with all the data that has been written before that footer.
+The file is closed with a footer describing all the row groups.
+
+The data is flushed to the disk after each footer only if it is requested with [StreamWriter::sync].
+*/
+
+use std::{
+    fs::File,
+    io::{Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use apache_arrow::{datatypes::SchemaRef, record_batch::RecordBatch};
+use parquet::{
+    arrow::{ArrowSchemaConverter, ArrowWriter, add_encoded_arrow_schema_to_metadata},
+    file::{
+        metadata::{
+            ColumnChunkMetaData, FileMetaData, KeyValue, ParquetMetaData, ParquetMetaDataWriter,
+            RowGroupMetaData,
+        },
+        properties::WriterProperties,
+    },
+    schema::types::SchemaDescPtr,
+};
+
+use crate::Result;
+
+const PARQUET_MAGIC: &[u8; 4] = b"PAR1";
+
+/// Parquet writer appending the row groups of each record to a file
+pub(crate) struct StreamWriter {
+    file: File,
+    // end of the file
+    position: u64,
+    // size of the last footer
+    footer: u64,
+    // size of the row groups written after the last footer
+    unfooted: u64,
+    sync: bool,
+    schema: SchemaRef,
+    descriptor: SchemaDescPtr,
+    props: WriterProperties,
+    key_value: Option<Vec<KeyValue>>,
+    row_groups: Vec<RowGroupMetaData>,
+    path: PathBuf,
+}
+impl StreamWriter {
+    /// Creates the Parquet file
+    pub fn try_new<P: AsRef<Path>>(
+        path: P,
+        schema: SchemaRef,
+        props: WriterProperties,
+    ) -> Result<Self> {
+        let mut footer_props = props.clone();
+        add_encoded_arrow_schema_to_metadata(&schema, &mut footer_props);
+        let mut file = File::create(&path)?;
+        file.write_all(PARQUET_MAGIC)?;
+        let mut this = Self {
+            file,
+            position: PARQUET_MAGIC.len() as u64,
+            footer: 0,
+            unfooted: 0,
+            sync: false,
+            descriptor: Arc::new(ArrowSchemaConverter::new().convert(&schema)?),
+            schema,
+            props,
+            key_value: footer_props.key_value_metadata().cloned(),
+            row_groups: Vec::new(),
+            path: path.as_ref().to_path_buf(),
+        };
+        this.footer()?;
+        Ok(this)
+    }
+    /// Flushes the file to the disk after each footer if `sync` is true (default: false)
+    pub fn sync(mut self, sync: bool) -> Self {
+        self.sync = sync;
+        self
+    }
+    /// Returns the path to the Parquet file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+    /// Appends the record to the file
+    pub fn write(&mut self, record: &RecordBatch) -> Result<()> {
+        let mut writer = ArrowWriter::try_new(
+            Vec::new(),
+            Arc::clone(&self.schema),
+            Some(self.props.clone()),
+        )?;
+        writer.write(record)?;
+        writer.flush()?;
+        let row_groups = writer.flushed_row_groups().to_vec();
+        let bytes = writer.into_inner()?;
+        // the row groups are followed by the bloom filters and the page indices
+        let end = row_groups
+            .iter()
+            .flat_map(|row_group| row_group.columns())
+            .map(|column| {
+                let (start, length) = column.byte_range();
+                start + length
+            })
+            .max()
+            .unwrap_or(PARQUET_MAGIC.len() as u64) as usize;
+        let offset = self.position as i64 - PARQUET_MAGIC.len() as i64;
+        self.file.seek(SeekFrom::Start(self.position))?;
+        self.file.write_all(&bytes[PARQUET_MAGIC.len()..end])?;
+        self.position += (end - PARQUET_MAGIC.len()) as u64;
+        self.unfooted += (end - PARQUET_MAGIC.len()) as u64;
+        for row_group in row_groups {
+            let ordinal = self.row_groups.len() as i16;
+            self.row_groups.push(shift(row_group, offset, ordinal)?);
+        }
+        if self.unfooted >= self.footer {
+            self.footer()?;
+        }
+        Ok(())
+    }
+    /// Appends the footer after the last row group
+    fn footer(&mut self) -> Result<()> {
+        let num_rows = self
+            .row_groups
+            .iter()
+            .map(|row_group| row_group.num_rows())
+            .sum();
+        let metadata = ParquetMetaData::new(
+            FileMetaData::new(
+                1,
+                num_rows,
+                Some(self.props.created_by().to_string()),
+                self.key_value.clone(),
+                Arc::clone(&self.descriptor),
+                None,
+            ),
+            self.row_groups.clone(),
+        );
+        let mut footer = vec![];
+        ParquetMetaDataWriter::new(&mut footer, &metadata).finish()?;
+        self.file.seek(SeekFrom::Start(self.position))?;
+        self.file.write_all(&footer)?;
+        self.position += footer.len() as u64;
+        self.footer = footer.len() as u64;
+        self.unfooted = 0;
+        if self.sync {
+            self.file.sync_data()?;
+        }
+        Ok(())
+    }
+    /// Writes the footer of all the row groups and closes the file
+    pub fn close(mut self) -> Result<()> {
+        if self.unfooted > 0 {
+            self.footer()?;
+        }
+        self.file.sync_all()?;
+        log::info!("Arrow data saved to {:?}", self.path);
+        Ok(())
+    }
+}
+impl Drop for StreamWriter {
+    fn drop(&mut self) {
+        if self.unfooted > 0
+            && let Err(e) = self.footer()
+        {
+            log::warn!("failed to write the footer of {:?}: {e}", self.path);
+        }
+    }
+}
+
+/// Moves the row group by `offset` bytes in the file
+fn shift(row_group: RowGroupMetaData, offset: i64, ordinal: i16) -> Result<RowGroupMetaData> {
+    let shift = |value: Option<i64>| value.map(|value| value + offset);
+    let columns = row_group
+        .columns()
+        .iter()
+        .map(|column| {
+            ColumnChunkMetaData::into_builder(column.clone())
+                .set_data_page_offset(column.data_page_offset() + offset)
+                .set_dictionary_page_offset(shift(column.dictionary_page_offset()))
+                .set_index_page_offset(shift(column.index_page_offset()))
+                .set_bloom_filter_offset(None)
+                .set_bloom_filter_length(None)
+                .set_column_index_offset(None)
+                .set_column_index_length(None)
+                .set_offset_index_offset(None)
+                .set_offset_index_length(None)
+                .build()
+        })
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    let file_offset = row_group.file_offset().map(|value| value + offset);
+    let mut builder = row_group
+        .into_builder()
+        .set_column_metadata(columns)
+        .set_ordinal(ordinal);
+    if let Some(file_offset) = file_offset {
+        builder = builder.set_file_offset(file_offset);
+    }
+    Ok(builder.build()?)
+}
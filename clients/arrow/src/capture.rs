@@ -0,0 +1,592 @@
+/*!
+# Triggered capture logger
+
+An oscilloscope-style logger that keeps a ring buffer of the last `n_pre` samples of every
+input and saves them, together with the next `n_post` samples, each time a trigger fires.
+
+The trigger is a predicate on the data of an input: a threshold crossing on a channel
+of a logged input ([threshold]), a [Pulse](https://docs.rs/gmt_dos-clients/latest/gmt_dos_clients/pulse/struct.Pulse.html)
+event or any external trigger input ([pulse]) or any user-supplied closure.
+The input the trigger is watching doesn't need to be logged.
+
+Each event is written into a separate row group of the [Parquet](https://docs.rs/parquet) file
+with the columns:
+ - `Event`: the event index,
+ - `Trigger`: the trigger timestamp in seconds,
+ - `Step` and `Time`: the simulation sample index and timestamp in seconds (see [time](crate::time)),
+ - and one column per logged input.
+
+The first `n_pre` rows of an event are the samples preceding the trigger and the last `n_post` rows
+are the samples starting at the trigger.
+All the inputs must be sampled at the same rate.
+Triggers that fire while an event is being captured are ignored.
+
+# Example
+
+```
+use gmt_dos_clients_arrow::capture::{self, Capture, Edge};
+use interface::UID;
+
+#[derive(UID)]
+enum Signal {}
+
+let capture = Capture::builder(100, 400)
+    .sampling_frequency(8e3)
+    .filename("transients")
+    .trigger::<f64, Signal, _>(capture::threshold(0, 1e-3, Edge::Rising))
+    .build();
+```
+*/
+
+use std::{
+    any::{Any, type_name},
+    collections::{HashMap, VecDeque},
+    env,
+    fs::{DirBuilder, File},
+    marker::PhantomData,
+    mem::take,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use apache_arrow::{
+    array::{ArrayData, ArrayRef, Float64Array, ListArray, UInt64Array},
+    buffer::Buffer,
+    datatypes::{ArrowNativeType, DataType, Field, Schema, ToByteSlice},
+    record_batch::RecordBatch,
+};
+use interface::{ClientHooks, Data, Entry, Read, UniqueIdentifier, Update, print_info};
+use parquet::{arrow::ArrowWriter, file::properties::WriterProperties};
+
+use crate::{BufferDataType, Result, time::time_columns};
+
+type Predicate<T> = Box<dyn FnMut(&[T]) -> bool + Send + Sync>;
+
+/// Threshold crossing direction
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Edge {
+    #[default]
+    Rising,
+    Falling,
+    Either,
+}
+
+/// Threshold crossing trigger
+///
+/// Fires when the value of the given `channel` crosses `level` in the direction set by `edge`
+pub fn threshold<T>(channel: usize, level: T, edge: Edge) -> impl FnMut(&[T]) -> bool + Send + Sync
+where
+    T: Copy + PartialOrd + Send + Sync,
+{
+    let mut previous: Option<T> = None;
+    move |data: &[T]| {
+        let Some(&value) = data.get(channel) else {
+            return false;
+        };
+        let fire = previous.is_some_and(|previous| {
+            let rising = previous < level && value >= level;
+            let falling = previous > level && value <= level;
+            match edge {
+                Edge::Rising => rising,
+                Edge::Falling => falling,
+                Edge::Either => rising || falling,
+            }
+        });
+        previous = Some(value);
+        fire
+    }
+}
+
+/// Pulse trigger
+///
+/// Fires when any value of the input departs from the default value
+/// (i.e. on the leading edge of a pulse)
+pub fn pulse<T>() -> impl FnMut(&[T]) -> bool + Send + Sync
+where
+    T: Default + PartialEq + Send + Sync,
+{
+    let mut active = false;
+    move |data: &[T]| {
+        let was_active = active;
+        active = data.iter().any(|x| *x != T::default());
+        active && !was_active
+    }
+}
+
+/// Captured input generic interface
+trait CaptureObject: Send + Sync {
+    fn who(&self) -> String;
+    fn as_mut_any(&mut self) -> &mut dyn Any;
+    fn data_type(&self) -> DataType;
+    /// Moves the pre-trigger ring buffer into the event buffer
+    fn arm(&mut self);
+    /// Records the latest sample either into the ring buffer or into the event buffer
+    fn record(&mut self, capturing: bool);
+    /// Drains the event buffer into a list array
+    fn drain_list(&mut self, n_row: usize) -> Result<ListArray>;
+}
+
+struct Channel<T, U> {
+    size: usize,
+    n_pre: usize,
+    latest: Option<Arc<Vec<T>>>,
+    ring: VecDeque<Arc<Vec<T>>>,
+    event: Vec<T>,
+    uid: PhantomData<U>,
+}
+impl<T, U> CaptureObject for Channel<T, U>
+where
+    T: 'static + BufferDataType + ArrowNativeType + Send + Sync,
+    U: 'static + Send + Sync + UniqueIdentifier<DataType = Vec<T>>,
+{
+    fn who(&self) -> String {
+        type_name::<U>()
+            .split("<")
+            .map(|x| x.split("::").last().unwrap().to_string())
+            .collect::<Vec<_>>()
+            .join("<")
+    }
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+    fn data_type(&self) -> DataType {
+        T::buffer_data_type()
+    }
+    fn arm(&mut self) {
+        for data in self.ring.drain(..) {
+            self.event.extend_from_slice(&data);
+        }
+    }
+    fn record(&mut self, capturing: bool) {
+        let Some(data) = self.latest.take() else {
+            return;
+        };
+        if capturing {
+            self.event.extend_from_slice(&data);
+        } else if self.n_pre > 0 {
+            if self.ring.len() == self.n_pre {
+                self.ring.pop_front();
+            }
+            self.ring.push_back(data);
+        }
+    }
+    fn drain_list(&mut self, n_row: usize) -> Result<ListArray> {
+        let event = take(&mut self.event);
+        let data = ArrayData::builder(self.data_type())
+            .len(event.len())
+            .add_buffer(Buffer::from_vec(event))
+            .build()?;
+        let offsets = (0..)
+            .step_by(self.size)
+            .take(n_row + 1)
+            .collect::<Vec<i32>>();
+        let list = ArrayData::builder(DataType::List(Arc::new(Field::new(
+            "values",
+            self.data_type(),
+            false,
+        ))))
+        .len(n_row)
+        .add_buffer(Buffer::from(offsets.to_byte_slice()))
+        .add_child_data(data)
+        .build()?;
+        Ok(ListArray::from(list))
+    }
+}
+
+/// Trigger generic interface
+trait TriggerObject: Send + Sync {
+    fn as_mut_any(&mut self) -> &mut dyn Any;
+}
+struct Trigger<T, U> {
+    predicate: Predicate<T>,
+    uid: PhantomData<U>,
+}
+impl<T, U> TriggerObject for Trigger<T, U>
+where
+    T: 'static,
+    U: 'static + Send + Sync,
+{
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Current event
+struct Event {
+    trigger_step: usize,
+    n_pre: usize,
+    n_post: usize,
+}
+
+/// [Capture] logger builder
+pub struct CaptureBuilder {
+    n_pre: usize,
+    n_post: usize,
+    sampling_frequency: f64,
+    filename: String,
+    triggers: Vec<Box<dyn TriggerObject>>,
+}
+impl CaptureBuilder {
+    /// Sets the simulation sampling frequency in Hz (default: 1Hz)
+    pub fn sampling_frequency(mut self, sampling_frequency: f64) -> Self {
+        self.sampling_frequency = sampling_frequency;
+        self
+    }
+    /// Sets the name of the file to save the events to (default: "capture.parquet")
+    pub fn filename<S: Into<String>>(mut self, filename: S) -> Self {
+        self.filename = filename.into();
+        self
+    }
+    /// Adds a trigger on input `U`
+    ///
+    /// The `predicate` is called with the data of `U` every time `U` is read
+    /// and the trigger fires if it returns `true`
+    pub fn trigger<T, U, F>(mut self, predicate: F) -> Self
+    where
+        T: 'static,
+        U: 'static + Send + Sync + UniqueIdentifier<DataType = Vec<T>>,
+        F: FnMut(&[T]) -> bool + Send + Sync + 'static,
+    {
+        self.triggers.push(Box::new(Trigger::<T, U> {
+            predicate: Box::new(predicate),
+            uid: PhantomData,
+        }));
+        self
+    }
+    /// Builds the [Capture] logger
+    pub fn build(self) -> Capture {
+        let root_env = env::var("DATA_REPO").unwrap_or_else(|_| ".".to_string());
+        Capture {
+            n_pre: self.n_pre,
+            n_post: self.n_post,
+            sampling_frequency: self.sampling_frequency,
+            path: Path::new(&root_env)
+                .join(self.filename)
+                .with_extension("parquet"),
+            triggers: self.triggers,
+            channels: Vec::new(),
+            fired: false,
+            step: 0,
+            inputs_rate: 1,
+            n_ring: 0,
+            event: None,
+            n_event: 0,
+            metadata: HashMap::new(),
+            writer: None,
+        }
+    }
+}
+
+/// Triggered ring buffer capture logger
+pub struct Capture {
+    n_pre: usize,
+    n_post: usize,
+    sampling_frequency: f64,
+    path: PathBuf,
+    triggers: Vec<Box<dyn TriggerObject>>,
+    channels: Vec<Box<dyn CaptureObject>>,
+    fired: bool,
+    step: usize,
+    inputs_rate: usize,
+    n_ring: usize,
+    event: Option<Event>,
+    n_event: usize,
+    metadata: HashMap<String, String>,
+    // the writer is not `Sync`
+    writer: Option<Mutex<ArrowWriter<File>>>,
+}
+impl Capture {
+    /// Creates a new capture logger builder
+    ///
+    /// - `n_pre`: the number of samples saved before the trigger
+    /// - `n_post`: the number of samples saved from the trigger onward
+    pub fn builder(n_pre: usize, n_post: usize) -> CaptureBuilder {
+        CaptureBuilder {
+            n_pre,
+            n_post,
+            sampling_frequency: 1f64,
+            filename: "capture".to_string(),
+            triggers: Vec::new(),
+        }
+    }
+    /// Returns the number of events saved to file
+    pub fn n_event(&self) -> usize {
+        self.n_event
+    }
+    /// Returns the path to the Parquet file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+    /// Writes the current event into a new row group of the Parquet file
+    fn save_event(&mut self) -> Result<()> {
+        let Some(event) = self.event.take() else {
+            return Ok(());
+        };
+        let n_row = event.n_pre + event.n_post;
+        let trigger_time = self.trigger_time(&event);
+        let first_step = event.trigger_step - event.n_pre;
+        let mut fields = vec![
+            Field::new("Event", DataType::UInt64, false),
+            Field::new("Trigger", DataType::Float64, false),
+        ];
+        let mut columns: Vec<ArrayRef> = vec![
+            Arc::new(UInt64Array::from(vec![self.n_event as u64; n_row])),
+            Arc::new(Float64Array::from(vec![trigger_time; n_row])),
+        ];
+        for (field, column) in time_columns(
+            self.sampling_frequency,
+            self.inputs_rate,
+            1,
+            first_step..first_step + n_row,
+        ) {
+            fields.push(field);
+            columns.push(column);
+        }
+        for channel in self.channels.iter_mut() {
+            fields.push(Field::new(
+                channel.who(),
+                DataType::List(Arc::new(Field::new("values", channel.data_type(), false))),
+                false,
+            ));
+            columns.push(Arc::new(channel.drain_list(n_row)?));
+        }
+        let schema = Arc::new(Schema::new_with_metadata(fields, self.metadata.clone()));
+        let record = RecordBatch::try_new(Arc::clone(&schema), columns)?;
+        let writer = match self.writer.as_mut() {
+            Some(writer) => writer.get_mut().unwrap(),
+            None => {
+                if let Some(path) = self.path.parent()
+                    && !path.is_dir()
+                {
+                    DirBuilder::new().recursive(true).create(path)?;
+                }
+                let file = File::create(&self.path)?;
+                let props = WriterProperties::builder().build();
+                self.writer
+                    .insert(Mutex::new(ArrowWriter::try_new(file, schema, Some(props))?))
+                    .get_mut()
+                    .unwrap()
+            }
+        };
+        writer.write(&record)?;
+        // one row group per event
+        writer.flush()?;
+        log::info!(
+            "Capture event #{} at {:.6}s saved to {:?}",
+            self.n_event,
+            trigger_time,
+            self.path
+        );
+        self.n_event += 1;
+        Ok(())
+    }
+    /// Returns the trigger timestamp in seconds
+    fn trigger_time(&self, event: &Event) -> f64 {
+        (event.trigger_step * self.inputs_rate) as f64 / self.sampling_frequency
+    }
+    /// Saves any pending event and closes the Parquet file
+    pub fn close(&mut self) -> Result<()> {
+        self.save_event()?;
+        if let Some(writer) = self.writer.take() {
+            writer.into_inner().unwrap().close()?;
+        }
+        Ok(())
+    }
+}
+
+impl Update for Capture {
+    fn update(&mut self) {
+        if self.event.is_none() && take(&mut self.fired) {
+            self.channels.iter_mut().for_each(|channel| channel.arm());
+            self.event = Some(Event {
+                trigger_step: self.step,
+                n_pre: take(&mut self.n_ring),
+                n_post: 0,
+            });
+        }
+        self.fired = false;
+        let capturing = self.event.is_some();
+        self.channels
+            .iter_mut()
+            .for_each(|channel| channel.record(capturing));
+        self.step += 1;
+        match self.event.as_mut() {
+            Some(event) => {
+                event.n_post += 1;
+                if event.n_post == self.n_post
+                    && let Err(e) = self.save_event()
+                {
+                    print_info("Capture error", Some(&e));
+                }
+            }
+            None => self.n_ring = self.n_pre.min(self.n_ring + 1),
+        }
+    }
+}
+impl ClientHooks for Capture {
+    /// Saves the pending event if the model is interrupted
+    fn on_shutdown(&mut self) {
+        if let Err(e) = self.close() {
+            print_info("Capture error", Some(&e));
+        }
+    }
+    /// Adds the model provenance to the metadata of the Parquet file
+    fn set_provenance(&mut self, provenance: &[(String, String)]) {
+        self.metadata.extend(provenance.iter().cloned());
+    }
+    fn set_inputs_rate(&mut self, rate: usize) {
+        self.inputs_rate = rate;
+    }
+}
+
+impl<T, U> Entry<U> for Capture
+where
+    T: 'static + BufferDataType + ArrowNativeType + Send + Sync,
+    U: 'static + Send + Sync + UniqueIdentifier<DataType = Vec<T>>,
+{
+    fn entry(&mut self, size: usize) {
+        let channel = Channel::<T, U> {
+            size,
+            n_pre: self.n_pre,
+            latest: None,
+            ring: VecDeque::with_capacity(self.n_pre),
+            event: Vec::new(),
+            uid: PhantomData,
+        };
+        let name = channel.who();
+        if self.channels.iter().any(|channel| channel.who() == name) {
+            log::info!(
+                r#"found existing entry with same name in Capture channels, skipping "{name}""#
+            );
+            return;
+        }
+        self.channels.push(Box::new(channel));
+    }
+}
+
+impl<T, U> Read<U> for Capture
+where
+    T: 'static + Send + Sync,
+    U: 'static + Send + Sync + UniqueIdentifier<DataType = Vec<T>>,
+{
+    fn read(&mut self, data: Data<U>) {
+        for trigger in self.triggers.iter_mut() {
+            if let Some(trigger) = trigger.as_mut_any().downcast_mut::<Trigger<T, U>>() {
+                self.fired |= (trigger.predicate)(&data);
+            }
+        }
+        if let Some(channel) = self
+            .channels
+            .iter_mut()
+            .find_map(|channel| channel.as_mut_any().downcast_mut::<Channel<T, U>>())
+        {
+            channel.latest = Some(data.into_arc());
+        }
+    }
+}
+
+impl Drop for Capture {
+    fn drop(&mut self) {
+        if let Err(e) = self.close() {
+            print_info("Capture error", Some(&e));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use apache_arrow::array::AsArray;
+    use interface::UID;
+    use parquet::{
+        arrow::arrow_reader::ParquetRecordBatchReaderBuilder, file::reader::FileReader,
+        file::serialized_reader::SerializedFileReader,
+    };
+
+    #[derive(UID)]
+    enum Signal {}
+    #[derive(UID)]
+    #[uid(data = Vec<u8>)]
+    enum Pulse {}
+
+    #[test]
+    fn capture() {
+        let path = env::temp_dir().join("gmt_dos-clients_arrow_capture");
+        let mut capture = Capture::builder(5, 10)
+            .sampling_frequency(100.)
+            .filename(path.to_str().unwrap())
+            .trigger::<f64, Signal, _>(threshold(1, 0.5, Edge::Rising))
+            .trigger::<u8, Pulse, _>(pulse())
+            .build();
+        <Capture as Entry<Signal>>::entry(&mut capture, 2);
+        for i in 0..100 {
+            let x = if (30..35).contains(&i) { 1. } else { 0. };
+            <Capture as Read<Signal>>::read(&mut capture, Data::new(vec![i as f64, x]));
+            let p = if (2..4).contains(&i) || i == 70 { 1 } else { 0 };
+            <Capture as Read<Pulse>>::read(&mut capture, Data::new(vec![p]));
+            capture.update();
+        }
+        capture.close().unwrap();
+        assert_eq!(capture.n_event(), 3);
+
+        let file = File::open(capture.path()).unwrap();
+        let reader = SerializedFileReader::new(file.try_clone().unwrap()).unwrap();
+        let row_groups: Vec<_> = reader
+            .metadata()
+            .row_groups()
+            .iter()
+            .map(|row_group| row_group.num_rows())
+            .collect();
+        assert_eq!(row_groups, vec![12, 15, 15]);
+
+        let record = ParquetRecordBatchReaderBuilder::try_new(file)
+            .unwrap()
+            .build()
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+        let trigger = record["Trigger"].as_primitive::<apache_arrow::datatypes::Float64Type>();
+        assert_eq!(trigger.value(0), 0.02);
+        assert_eq!(trigger.value(12), 0.3);
+        assert_eq!(trigger.value(27), 0.7);
+        let signal = record["Signal"].as_list::<i32>();
+        let first = signal.value(12);
+        let first = first.as_primitive::<apache_arrow::datatypes::Float64Type>();
+        assert_eq!(first.values().as_ref(), &[25., 0.]);
+    }
+
+    #[test]
+    fn inputs_rate() {
+        let path = env::temp_dir().join("gmt_dos-clients_arrow_capture_rate");
+        let mut capture = Capture::builder(1, 2)
+            .sampling_frequency(100.)
+            .filename(path.to_str().unwrap())
+            .trigger::<f64, Signal, _>(threshold(0, 0.5, Edge::Rising))
+            .build();
+        capture.set_inputs_rate(10);
+        <Capture as Entry<Signal>>::entry(&mut capture, 1);
+        for i in 0..5 {
+            let x = if i >= 2 { 1. } else { 0. };
+            <Capture as Read<Signal>>::read(&mut capture, Data::new(vec![x]));
+            capture.update();
+        }
+        capture.close().unwrap();
+
+        let file = File::open(capture.path()).unwrap();
+        let record = ParquetRecordBatchReaderBuilder::try_new(file)
+            .unwrap()
+            .build()
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+        let trigger = record["Trigger"].as_primitive::<apache_arrow::datatypes::Float64Type>();
+        assert_eq!(trigger.value(0), 0.2);
+        let step = record["Step"].as_primitive::<apache_arrow::datatypes::UInt64Type>();
+        assert_eq!(step.values().as_ref(), &[10, 20, 30]);
+        let time = record["Time"].as_primitive::<apache_arrow::datatypes::Float64Type>();
+        assert_eq!(time.values().as_ref(), &[0.1, 0.2, 0.3]);
+        std::fs::remove_file(capture.path()).unwrap();
+    }
+}
@@ -68,6 +68,30 @@ impl Size<Source> for Signals {
 source.add_output().build::<Source>().log(&mut sink);
 # Ok::<(), gmt_dos_actors::model::ModelError>(())
 ```
+
+For long simulations, the data can be streamed to the Parquet file with a constant memory footprint
+```
+use gmt_dos_clients_arrow::{Arrow, Compression};
+
+let logging = Arrow::builder(1_000_000)
+                       .streaming(1000)
+                       .compression(Compression::SNAPPY)
+                       .build();
+```
+
+For long simulations where only transients are of interest,
+the [Capture] logger saves a window of samples around trigger events (see [capture]).
+
+Data types that are not vectors of primitive types, like structures or scalars, can be logged with the
+[SerdeArrow](serde_log::SerdeArrow) logger as long as they implement `serde::Serialize` (requires the `serde` feature, see [serde_log]).
+
+The data recorded in a Parquet file can be fed back into a model with the [Replay] client (see [replay]).
+
+The simulation step index and time can be logged with the data and the data logged at different rates
+can be aligned on a common time base with [Align] (see [time]).
+
+The provenance of a model, gathered with [Model::provenance](https://docs.rs/gmt_dos-actors/latest/gmt_dos_actors/model/struct.Model.html#method.provenance),
+is added to the metadata of the Parquet files of all the loggers.
 */
 
 use apache_arrow::datatypes::ArrowNativeType;
@@ -76,7 +100,7 @@ use apache_arrow::{
     buffer::Buffer,
     datatypes::{DataType, Field, ToByteSlice},
 };
-use interface::{Data, Read, UniqueIdentifier, Update};
+use interface::{ClientHooks, Data, Read, UniqueIdentifier, Update};
 use std::sync::Arc;
 use std::{
     any::{Any, type_name},
@@ -98,6 +122,8 @@ pub enum ArrowError {
     FieldNotFound(String),
     #[error("Parsing field {0} failed")]
     ParseField(String),
+    #[error("data of column {0} do not match the column schema")]
+    Schema(String, #[source] apache_arrow::error::ArrowError),
     #[cfg(feature = "matio-rs")]
     #[error("failed to save data to mat file")]
     MatFile(#[from] matio_rs::MatioError),
@@ -145,6 +171,15 @@ trait BufferObject: Send + Sync {
     fn into_list(&mut self, n_step: usize, n: usize, data_type: DataType) -> Result<ListArray>;
 }
 
+/// Returns the name of the column of the data `U`
+fn who<U>() -> String {
+    type_name::<U>()
+        .split("<")
+        .map(|x| format!("{}", x.split("::").last().unwrap()))
+        .collect::<Vec<_>>()
+        .join("<")
+}
+
 /// Arrow buffer type match to a dos-actors Data type
 struct ArrowBuffer<U: UniqueIdentifier>(PhantomData<U>);
 impl<T: ArrowNativeType, U: UniqueIdentifier<DataType = Vec<T>>> UniqueIdentifier
@@ -176,11 +211,7 @@ where
     U: 'static + Send + Sync + UniqueIdentifier<DataType = Vec<T>>,
 {
     fn who(&self) -> String {
-        type_name::<U>()
-            .split("<")
-            .map(|x| format!("{}", x.split("::").last().unwrap()))
-            .collect::<Vec<_>>()
-            .join("<")
+        who::<U>()
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -247,8 +278,39 @@ enum DropOption {
 
 mod arrow;
 pub use arrow::{Arrow, ArrowBuilder, iter::ArrowIter};
+pub use parquet::basic::{Compression, ZstdLevel};
+pub mod capture;
+pub use capture::{Capture, CaptureBuilder};
+pub mod replay;
+pub use replay::{Replay, ReplayBuilder};
+pub mod time;
+pub use time::{Align, Alignment};
+#[cfg(feature = "serde")]
+pub mod serde_log;
+#[cfg(feature = "serde")]
+pub use serde_log::{SerdeArrow, SerdeArrowBuilder};
 
 impl Update for Arrow {}
+impl ClientHooks for Arrow {
+    /// Writes the record to file if the model is interrupted
+    fn on_shutdown(&mut self) {
+        self.save();
+        if let Err(e) = self.finish_stream() {
+            interface::print_info("Arrow error", Some(&e));
+        }
+        // the record has been saved, no need to save it again on drop
+        self.drop_option = DropOption::NoSave;
+    }
+    /// Adds the model provenance to the metadata of the Parquet file
+    fn set_provenance(&mut self, provenance: &[(String, String)]) {
+        self.metadata
+            .get_or_insert_default()
+            .extend(provenance.iter().cloned());
+    }
+    fn set_inputs_rate(&mut self, rate: usize) {
+        self.inputs_rate = rate;
+    }
+}
 impl<T, U> Read<U> for Arrow
 where
     T: ArrowNativeType,
@@ -309,6 +371,17 @@ mod tests {
         assert_eq!(arw.record().unwrap().schema(), schema);
     }
 
+    #[test]
+    fn provenance() {
+        let mut arw = Arrow::builder(10).no_save().build();
+        #[derive(UID)]
+        pub enum Data {}
+        <Arrow as Entry<Data>>::entry(&mut arw, 1);
+        arw.set_provenance(&[("provenance".into(), "{}".into())]);
+        let schema = arw.record().unwrap().schema();
+        assert_eq!(schema.metadata()["provenance"], "{}");
+    }
+
     #[test]
     fn batch() {
         //env_logger::init();
@@ -338,4 +411,93 @@ mod tests {
             arw.read(Data::<V>::new(vec![(10 * i) as f64; 3]));
         }
     }
+
+    #[test]
+    fn streaming() {
+        use parquet::{arrow::arrow_reader::ParquetRecordBatchReaderBuilder, basic::Compression};
+        use std::fs::File;
+
+        let n_step = 26;
+        let path = std::env::temp_dir().join("gmt_dos-clients_arrow-streaming");
+        let mut arw = Arrow::builder(n_step)
+            .streaming(4)
+            .column_compression::<V>(Compression::ZSTD(Default::default()))
+            .filename(path.to_str().unwrap())
+            .build();
+        #[derive(UID)]
+        pub enum U {}
+        <Arrow as Entry<U>>::entry(&mut arw, 1);
+        #[derive(UID)]
+        pub enum V {}
+        <Arrow as Entry<V>>::entry(&mut arw, 1024);
+        let file = path.with_extension("parquet");
+        for i in 0..n_step {
+            arw.read(Data::<U>::new(vec![i as f64]));
+            arw.read(Data::<V>::new(
+                (0..1024).map(|j| ((i * 1024 + j) as f64).sin()).collect(),
+            ));
+            if i == 3 || i == 9 {
+                // the file is readable while the logger is still running
+                let reader =
+                    ParquetRecordBatchReaderBuilder::try_new(File::open(&file).unwrap()).unwrap();
+                assert_eq!(
+                    reader.metadata().file_metadata().num_rows(),
+                    4 * ((i + 1) / 4) as i64
+                );
+            }
+        }
+        drop(arw);
+
+        let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(&file).unwrap()).unwrap();
+        let metadata = reader.metadata().clone();
+        assert_eq!(metadata.num_row_groups(), 7);
+        assert_eq!(metadata.file_metadata().num_rows(), n_step as i64);
+        let row_group = metadata.row_group(0);
+        assert_eq!(row_group.column(0).compression(), Compression::UNCOMPRESSED);
+        assert!(matches!(
+            row_group.column(1).compression(),
+            Compression::ZSTD(_)
+        ));
+        let mut arw = Arrow::from_parquet(&path).unwrap();
+        let u: Vec<Vec<f64>> = arw.iter("U").unwrap().collect();
+        assert_eq!(
+            u.concat(),
+            (0..n_step).map(|i| i as f64).collect::<Vec<_>>()
+        );
+        std::fs::remove_file(file).unwrap();
+    }
+
+    #[test]
+    fn streaming_small_row_groups() {
+        use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+        use std::fs::File;
+
+        let n_step = 200;
+        let path = std::env::temp_dir().join("gmt_dos-clients_arrow-streaming-small");
+        let mut arw = Arrow::builder(n_step)
+            .streaming(1)
+            .sync()
+            .filename(path.to_str().unwrap())
+            .build();
+        #[derive(UID)]
+        pub enum U {}
+        <Arrow as Entry<U>>::entry(&mut arw, 1);
+        let file = path.with_extension("parquet");
+        for i in 0..n_step {
+            arw.read(Data::<U>::new(vec![i as f64]));
+        }
+        // the footer is not written after each of the small row groups,
+        // the footer of all the row groups is written when the logger is dropped
+        drop(arw);
+
+        let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(&file).unwrap()).unwrap();
+        assert_eq!(reader.metadata().num_row_groups(), n_step);
+        let mut arw = Arrow::from_parquet(&path).unwrap();
+        let u: Vec<Vec<f64>> = arw.iter("U").unwrap().collect();
+        assert_eq!(
+            u.concat(),
+            (0..n_step).map(|i| i as f64).collect::<Vec<_>>()
+        );
+        std::fs::remove_file(file).unwrap();
+    }
 }
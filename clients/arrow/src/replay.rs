@@ -0,0 +1,279 @@
+/*!
+# Parquet replay
+
+An initiator client that plays back the data recorded by the [Arrow](crate::Arrow) logger.
+
+The [Replay] client loads a [Parquet](https://docs.rs/parquet) file and writes, at each step,
+one row of the column with the same name as the output UID (the name given to the column by the
+[Arrow](crate::Arrow) logger).
+The replay can be restricted to a window of the recorded samples, looped over
+and resampled to a different sampling rate with a zero-order hold.
+
+The client stops the model once all the samples have been replayed, unless looping is set.
+A missing column or a column of the wrong data type is reported as an [ArrowError]
+by the actor the client belongs to.
+
+# Example
+
+```no_run
+use gmt_dos_actors::prelude::*;
+use gmt_dos_clients_arrow::Replay;
+use interface::UID;
+
+#[derive(UID)]
+enum WindLoads {}
+
+let replay = Replay::builder("wind-loads.parquet")
+    .skip(1000)
+    .take(8000)
+    .resample(1e3, 8e3)
+    .build()?;
+let mut source: Initiator<_> = replay.into();
+# Ok::<(), gmt_dos_clients_arrow::ArrowError>(())
+```
+*/
+
+use std::{
+    env,
+    fs::File,
+    path::{Path, PathBuf},
+};
+
+use apache_arrow::{
+    array::{Array, ListArray, PrimitiveArray},
+    compute::concat_batches,
+    datatypes::{ArrowPrimitiveType, SchemaRef},
+    record_batch::{RecordBatch, RecordBatchReader},
+};
+use interface::{ClientHooks, Data, Size, TryWrite, UniqueIdentifier, Update};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+use crate::{ArrowError, BufferDataType, Result, who};
+
+/// [Replay] builder
+#[derive(Debug, Clone)]
+pub struct ReplayBuilder {
+    path: PathBuf,
+    skip: usize,
+    take: Option<usize>,
+    looping: bool,
+    ratio: f64,
+}
+impl ReplayBuilder {
+    /// Skips the first `n` recorded samples
+    pub fn skip(mut self, n: usize) -> Self {
+        self.skip = n;
+        self
+    }
+    /// Replays at most `n` recorded samples
+    pub fn take(mut self, n: usize) -> Self {
+        self.take = Some(n);
+        self
+    }
+    /// Restarts from the first sample once the last one has been replayed
+    pub fn looping(mut self) -> Self {
+        self.looping = true;
+        self
+    }
+    /// Resamples the data recorded at the `recorded` sampling frequency to the `replay` sampling frequency
+    ///
+    /// Each replayed sample is the last recorded sample at the time of the replay
+    pub fn resample(mut self, recorded: f64, replay: f64) -> Self {
+        self.ratio = recorded / replay;
+        self
+    }
+    /// Loads the data and builds the [Replay] client
+    ///
+    /// The [Parquet](https://docs.rs/parquet) data file is loaded from the current
+    /// directory unless the environment variable `DATA_REPO` is set to another directory.
+    pub fn build(self) -> Result<Replay> {
+        let root_env = env::var("DATA_REPO").unwrap_or_else(|_| ".".to_string());
+        let filename = Path::new(&root_env)
+            .join(&self.path)
+            .with_extension("parquet");
+        let file = File::open(&filename)?;
+        log::info!("Replaying {:?}", filename);
+        let mut builder = ParquetRecordBatchReaderBuilder::try_new(file)?.with_offset(self.skip);
+        if let Some(take) = self.take {
+            builder = builder.with_limit(take);
+        }
+        let reader = builder.build()?;
+        let schema = reader.schema();
+        let records = reader.collect::<std::result::Result<Vec<_>, _>>()?;
+        let record = concat_batches(&schema, records.as_slice())?;
+        Ok(Replay {
+            record,
+            looping: self.looping,
+            ratio: self.ratio,
+            step: 0,
+            row: None,
+        })
+    }
+}
+
+/// Parquet data replay
+#[derive(Debug)]
+pub struct Replay {
+    record: RecordBatch,
+    looping: bool,
+    ratio: f64,
+    step: usize,
+    row: Option<usize>,
+}
+impl Replay {
+    /// Creates a [Replay] builder from a [Parquet](https://docs.rs/parquet) file
+    pub fn builder<P: AsRef<Path>>(path: P) -> ReplayBuilder {
+        ReplayBuilder {
+            path: path.as_ref().to_path_buf(),
+            skip: 0,
+            take: None,
+            looping: false,
+            ratio: 1.,
+        }
+    }
+    /// Returns the schema of the replayed data
+    pub fn schema(&self) -> SchemaRef {
+        self.record.schema()
+    }
+    /// Returns the number of recorded samples
+    pub fn len(&self) -> usize {
+        self.record.num_rows()
+    }
+    /// Checks if there are recorded samples
+    pub fn is_empty(&self) -> bool {
+        self.record.num_rows() == 0
+    }
+    /// Returns the column with the name of the UID `U`
+    fn column<U: UniqueIdentifier>(&self) -> Result<&ListArray> {
+        let name = who::<U>();
+        self.record
+            .column_by_name(&name)
+            .ok_or_else(|| ArrowError::FieldNotFound(name.clone()))?
+            .as_any()
+            .downcast_ref::<ListArray>()
+            .ok_or(ArrowError::ParseField(name))
+    }
+}
+
+impl Update for Replay {
+    fn update(&mut self) {
+        let n = self.len();
+        let row = (self.step as f64 * self.ratio).floor() as usize;
+        self.row = match row {
+            row if row < n => Some(row),
+            row if self.looping && n > 0 => Some(row % n),
+            _ => None,
+        };
+        self.step += 1;
+    }
+}
+impl ClientHooks for Replay {}
+
+impl<T, U> TryWrite<U> for Replay
+where
+    T: BufferDataType,
+    <T as BufferDataType>::ArrayType: ArrowPrimitiveType,
+    Vec<T>: FromIterator<<<T as BufferDataType>::ArrayType as ArrowPrimitiveType>::Native>,
+    U: UniqueIdentifier<DataType = Vec<T>>,
+{
+    type Error = ArrowError;
+
+    fn try_write(&mut self) -> Result<Option<Data<U>>> {
+        let Some(row) = self.row else {
+            return Ok(None);
+        };
+        let values = self.column::<U>()?.value(row);
+        let data = values
+            .as_any()
+            .downcast_ref::<PrimitiveArray<<T as BufferDataType>::ArrayType>>()
+            .map(|data| data.values().iter().copied().collect::<Vec<T>>())
+            .ok_or_else(|| ArrowError::ParseField(who::<U>()))?;
+        Ok(Some(Data::new(data)))
+    }
+}
+
+impl<T, U> Size<U> for Replay
+where
+    T: BufferDataType,
+    <T as BufferDataType>::ArrayType: ArrowPrimitiveType,
+    Vec<T>: FromIterator<<<T as BufferDataType>::ArrayType as ArrowPrimitiveType>::Native>,
+    U: UniqueIdentifier<DataType = Vec<T>>,
+{
+    fn len(&self) -> usize {
+        self.column::<U>()
+            .ok()
+            .filter(|column| !column.is_empty())
+            .map_or(0, |column| column.value_length(0) as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use interface::{Entry, Read, UID};
+
+    use super::*;
+    use crate::Arrow;
+
+    #[derive(UID)]
+    pub enum U {}
+    #[derive(UID)]
+    pub enum V {}
+    mod single {
+        use interface::UID;
+        // same column name as `U` with a different data type
+        #[derive(UID)]
+        #[uid(data = Vec<f32>)]
+        pub enum U {}
+    }
+
+    #[test]
+    fn replay() {
+        let path = env::temp_dir().join("gmt_dos-clients_arrow-replay");
+        let mut arw = Arrow::builder(10).filename(path.to_str().unwrap()).build();
+        <Arrow as Entry<U>>::entry(&mut arw, 2);
+        for i in 0..10 {
+            arw.read(Data::<U>::new(vec![i as f64, -(i as f64)]));
+        }
+        drop(arw);
+
+        let mut replay = Replay::builder(&path)
+            .skip(2)
+            .take(3)
+            .looping()
+            .resample(1., 2.)
+            .build()
+            .unwrap();
+        assert_eq!(<Replay as Size<U>>::len(&replay), 2);
+        let data: Vec<f64> = (0..8)
+            .map(|_| {
+                replay.update();
+                <Replay as TryWrite<U>>::try_write(&mut replay)
+                    .unwrap()
+                    .unwrap()[0]
+            })
+            .collect();
+        assert_eq!(data, vec![2., 2., 3., 3., 4., 4., 2., 2.]);
+
+        let mut replay = Replay::builder(&path).skip(8).build().unwrap();
+        let data: Vec<_> = std::iter::from_fn(|| {
+            replay.update();
+            <Replay as TryWrite<U>>::try_write(&mut replay)
+                .unwrap()
+                .map(|data| data[1])
+        })
+        .collect();
+        assert_eq!(data, vec![-8., -9.]);
+
+        let mut replay = Replay::builder(&path).build().unwrap();
+        replay.update();
+        assert!(matches!(
+            <Replay as TryWrite<V>>::try_write(&mut replay),
+            Err(ArrowError::FieldNotFound(_))
+        ));
+        assert!(matches!(
+            <Replay as TryWrite<single::U>>::try_write(&mut replay),
+            Err(ArrowError::ParseField(_))
+        ));
+        std::fs::remove_file(path.with_extension("parquet")).unwrap();
+    }
+}
@@ -0,0 +1,541 @@
+/*!
+# Serde data logger
+
+A logger for any data type that implements [Serialize], not only vectors of primitive types.
+
+Each input is recorded into a column named after the input UID (the same naming as the [Arrow](crate::Arrow) logger).
+The Arrow data type of a column follows the serde data model:
+ - scalars (numbers, booleans and strings) are mapped to primitive columns,
+ - sequences and tuples (with all the elements of the same type) are mapped to `List` columns,
+ - structs and maps are mapped to `Struct` columns,
+ - unit enum variants are mapped to strings and the other variants to a `Struct` with a single field named after the variant,
+ - `Option`s are mapped to null values.
+
+The data is serialized directly into the Arrow arrays, so floats are recorded as they are, including NaN and infinities.
+
+The schema of each column is inferred from the data of the first row group,
+so optional fields that are always `None` in the first row group are given a `Null` type.
+If later data do not match the schema of a column, [TryRead::try_read] returns an [ArrowError::Schema]
+error and the rows that could not be written are kept in the logger.
+The Arrow data type of such a column can be set beforehand with [SerdeArrowBuilder::field].
+
+The data is streamed to a [Parquet](https://docs.rs/parquet) file, one row group every
+`row_group_size` time steps, or saved in a single row group when the logger is dropped.
+
+# Example
+
+```
+use gmt_dos_clients_arrow::SerdeArrow;
+use interface::{Data, TryRead, UID};
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct State {
+    pub tip_tilt: (f64, f64),
+    pub modes: Vec<f64>,
+}
+
+#[derive(UID)]
+#[uid(data = State)]
+pub enum MountState {}
+#[derive(UID)]
+#[uid(data = f64)]
+pub enum Wfe {}
+
+let mut logging = SerdeArrow::builder()
+    .row_group_size(1000)
+    .no_save()
+    .build();
+<SerdeArrow as TryRead<MountState>>::try_read(&mut logging, Data::new(State { tip_tilt: (0., 0.), modes: vec![0.; 3] }))?;
+<SerdeArrow as TryRead<Wfe>>::try_read(&mut logging, Data::new(1e-9))?;
+# Ok::<(), gmt_dos_clients_arrow::ArrowError>(())
+```
+*/
+
+use std::{
+    any::Any,
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+};
+
+use apache_arrow::{
+    array::ArrayRef,
+    datatypes::{DataType, Field, FieldRef, Schema},
+    record_batch::RecordBatch,
+};
+use interface::{ClientHooks, Data, Entry, TryRead, UniqueIdentifier, Update, print_info};
+use parquet::{basic::Compression, file::properties::WriterProperties};
+use serde::Serialize;
+
+use crate::{
+    ArrowError, Result,
+    arrow::{parquet_path, stream::StreamWriter},
+    time::time_columns,
+    who,
+};
+
+mod builder;
+mod schema;
+use builder::Builder;
+use schema::Shape;
+
+/// Columns generic interface
+trait SerdeColumn: Send + Sync {
+    fn len(&self) -> usize;
+    fn as_mut_any(&mut self) -> &mut dyn Any;
+    /// Converts the first `n` rows to an Arrow array, keeping the rows
+    fn encode(&mut self, n: usize) -> Result<(FieldRef, ArrayRef)>;
+    /// Removes the first `n` rows
+    fn remove(&mut self, n: usize);
+}
+
+/// Column of serializable data
+struct Column<U: UniqueIdentifier> {
+    name: String,
+    rows: VecDeque<Arc<U::DataType>>,
+    field: Option<FieldRef>,
+    count: usize,
+}
+impl<U: UniqueIdentifier> Column<U> {
+    fn new(field: Option<FieldRef>) -> Self {
+        Self {
+            name: who::<U>(),
+            rows: VecDeque::new(),
+            field,
+            count: 0,
+        }
+    }
+}
+impl<U> SerdeColumn for Column<U>
+where
+    U: UniqueIdentifier + 'static,
+    U::DataType: Serialize,
+{
+    fn len(&self) -> usize {
+        self.rows.len()
+    }
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+    fn encode(&mut self, n: usize) -> Result<(FieldRef, ArrayRef)> {
+        let rows = self.rows.iter().take(n);
+        let field = match &self.field {
+            Some(field) => field.clone(),
+            None => {
+                let mut shape = Shape::default();
+                rows.clone()
+                    .try_for_each(|data| data.as_ref().serialize(&mut shape))
+                    .map_err(|e| ArrowError::Schema(self.name.clone(), e.into()))?;
+                Arc::new(Field::new(&self.name, shape.data_type(), true))
+            }
+        };
+        let array = Builder::new(field.data_type())
+            .and_then(|mut builder| {
+                for data in rows {
+                    data.as_ref().serialize(&mut builder)?;
+                }
+                Ok(builder)
+            })
+            .map_err(apache_arrow::error::ArrowError::from)
+            .and_then(|mut builder| builder.finish())
+            .map_err(|e| ArrowError::Schema(self.name.clone(), e))?;
+        self.field = Some(field.clone());
+        Ok((field, array))
+    }
+    fn remove(&mut self, n: usize) {
+        self.rows.drain(..n);
+    }
+}
+
+/// [SerdeArrow] builder
+pub struct SerdeArrowBuilder {
+    filename: Option<String>,
+    row_group_size: Option<usize>,
+    sync: bool,
+    decimation: usize,
+    compression: Option<Compression>,
+    metadata: Option<HashMap<String, String>>,
+    sampling_frequency: Option<f64>,
+    fields: HashMap<String, FieldRef>,
+}
+impl SerdeArrowBuilder {
+    /// Sets the name of the file to save the data to (default: "data.parquet")
+    pub fn filename<S: Into<String>>(mut self, filename: S) -> Self {
+        self.filename = Some(filename.into());
+        self
+    }
+    /// No saving to parquet file
+    pub fn no_save(mut self) -> Self {
+        self.filename = None;
+        self
+    }
+    /// Writes a row group to the Parquet file every `row_group_size` time steps
+    pub fn row_group_size(mut self, row_group_size: usize) -> Self {
+        self.row_group_size = Some(row_group_size.max(1));
+        self
+    }
+    /// Flushes the Parquet file to the disk every time its footer is written
+    pub fn sync(mut self) -> Self {
+        self.sync = true;
+        self
+    }
+    /// Decimate the data by the given factor
+    pub fn decimation(mut self, decimation: usize) -> Self {
+        self.decimation = decimation.max(1);
+        self
+    }
+    /// Sets the Parquet compression of all the columns (default: uncompressed)
+    pub fn compression(mut self, compression: Compression) -> Self {
+        self.compression = Some(compression);
+        self
+    }
+    /// Sets the Parquet file metadata
+    pub fn metadata(mut self, metadata: HashMap<String, String>) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+    /// Adds the `Step` and `Time` columns with the simulation step index and time of each row
+    ///
+    /// The step index and the time are derived from the simulation `sampling_frequency` in Hz,
+    /// the rate of the inputs of the logger actor and the decimation factor (see [time](crate::time))
+    pub fn time(mut self, sampling_frequency: f64) -> Self {
+        self.sampling_frequency = Some(sampling_frequency);
+        self
+    }
+    /// Sets the Arrow data type of the column of the input `U`
+    ///
+    /// The data type is inferred from the data of the first row group otherwise
+    pub fn field<U: UniqueIdentifier>(mut self, data_type: DataType) -> Self {
+        let name = who::<U>();
+        self.fields
+            .insert(name.clone(), Arc::new(Field::new(name, data_type, true)));
+        self
+    }
+    /// Builds the [SerdeArrow] logger
+    pub fn build(self) -> SerdeArrow {
+        SerdeArrow {
+            columns: Vec::new(),
+            filename: self.filename,
+            row_group_size: self.row_group_size,
+            sync: self.sync,
+            decimation: self.decimation,
+            compression: self.compression,
+            metadata: self.metadata,
+            writer: None,
+            sampling_frequency: self.sampling_frequency,
+            inputs_rate: 1,
+            n_row: 0,
+            fields: self.fields,
+        }
+    }
+}
+
+/// Serde data logger
+pub struct SerdeArrow {
+    columns: Vec<Box<dyn SerdeColumn>>,
+    filename: Option<String>,
+    row_group_size: Option<usize>,
+    sync: bool,
+    decimation: usize,
+    compression: Option<Compression>,
+    metadata: Option<HashMap<String, String>>,
+    writer: Option<Mutex<StreamWriter>>,
+    sampling_frequency: Option<f64>,
+    inputs_rate: usize,
+    n_row: usize,
+    fields: HashMap<String, FieldRef>,
+}
+impl SerdeArrow {
+    /// Creates a [SerdeArrow] logger builder
+    pub fn builder() -> SerdeArrowBuilder {
+        SerdeArrowBuilder {
+            filename: Some("data".to_string()),
+            row_group_size: None,
+            sync: false,
+            decimation: 1,
+            compression: None,
+            metadata: None,
+            sampling_frequency: None,
+            fields: HashMap::new(),
+        }
+    }
+    /// Returns the column of the input `U`, creating it if needed
+    fn column<U>(&mut self) -> &mut Column<U>
+    where
+        U: UniqueIdentifier + 'static,
+        U::DataType: Serialize + Send + Sync,
+    {
+        let idx = match self
+            .columns
+            .iter_mut()
+            .position(|column| column.as_mut_any().is::<Column<U>>())
+        {
+            Some(idx) => idx,
+            None => {
+                let field = self.fields.get(&who::<U>()).cloned();
+                self.columns.push(Box::new(Column::<U>::new(field)));
+                self.columns.len() - 1
+            }
+        };
+        self.columns[idx]
+            .as_mut_any()
+            .downcast_mut::<Column<U>>()
+            .unwrap()
+    }
+    /// Writes the first `n` rows of all the columns to the Parquet file
+    ///
+    /// The rows are removed from the columns only if they have been written
+    fn write(&mut self, n: usize) -> Result<()> {
+        let (mut fields, mut columns): (Vec<_>, Vec<_>) = self
+            .columns
+            .iter_mut()
+            .map(|column| column.encode(n))
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .unzip();
+        if let Some(sampling_frequency) = self.sampling_frequency {
+            let rows = self.n_row..self.n_row + n;
+            let (time_fields, time_columns): (Vec<_>, Vec<_>) =
+                time_columns(sampling_frequency, self.inputs_rate, self.decimation, rows)
+                    .into_iter()
+                    .map(|(field, column)| (Arc::new(field), column))
+                    .unzip();
+            fields.splice(0..0, time_fields);
+            columns.splice(0..0, time_columns);
+        }
+        if let Some(filename) = self.filename.as_ref() {
+            let schema = Arc::new(Schema::new_with_metadata(
+                fields,
+                self.metadata.clone().unwrap_or_default(),
+            ));
+            let record = RecordBatch::try_new(schema.clone(), columns)?;
+            if self.writer.is_none() {
+                let mut props = WriterProperties::builder();
+                if let Some(compression) = self.compression {
+                    props = props.set_compression(compression);
+                }
+                self.writer = Some(Mutex::new(
+                    StreamWriter::try_new(parquet_path(filename)?, schema, props.build())?
+                        .sync(self.sync),
+                ));
+            }
+            if let Some(writer) = self.writer.as_mut() {
+                writer.get_mut().unwrap().write(&record)?;
+            }
+        }
+        self.columns.iter_mut().for_each(|column| column.remove(n));
+        self.n_row += n;
+        Ok(())
+    }
+    /// Writes the remaining data to the Parquet file and closes it
+    pub fn close(&mut self) -> Result<()> {
+        let n = self
+            .columns
+            .iter()
+            .map(|column| column.len())
+            .min()
+            .unwrap_or_default();
+        if n > 0 {
+            self.write(n)?;
+        }
+        if let Some(writer) = self.writer.take() {
+            writer.into_inner().unwrap().close()?;
+        }
+        self.filename = None;
+        Ok(())
+    }
+}
+
+impl Update for SerdeArrow {}
+impl ClientHooks for SerdeArrow {
+    fn on_shutdown(&mut self) {
+        if let Err(e) = self.close() {
+            print_info("Arrow error", Some(&e));
+        }
+    }
+    /// Adds the model provenance to the metadata of the Parquet file
+    fn set_provenance(&mut self, provenance: &[(String, String)]) {
+        self.metadata
+            .get_or_insert_default()
+            .extend(provenance.iter().cloned());
+    }
+    fn set_inputs_rate(&mut self, rate: usize) {
+        self.inputs_rate = rate;
+    }
+}
+
+impl<U> Entry<U> for SerdeArrow
+where
+    U: UniqueIdentifier + 'static,
+    U::DataType: Serialize + Send + Sync,
+{
+    fn entry(&mut self, _size: usize) {
+        self.column::<U>();
+    }
+}
+
+impl<U> TryRead<U> for SerdeArrow
+where
+    U: UniqueIdentifier + 'static,
+    U::DataType: Serialize + Send + Sync,
+{
+    type Error = ArrowError;
+    fn try_read(&mut self, data: Data<U>) -> Result<&mut Self> {
+        let decimation = self.decimation;
+        let column = self.column::<U>();
+        column.count += 1;
+        if !column.count.is_multiple_of(decimation) {
+            return Ok(self);
+        }
+        column.rows.push_back(data.as_arc());
+        if let Some(n) = self.row_group_size
+            && self.columns.iter().all(|column| column.len() >= n)
+        {
+            self.write(n)?;
+        }
+        Ok(self)
+    }
+}
+
+impl Drop for SerdeArrow {
+    fn drop(&mut self) {
+        if let Err(e) = self.close() {
+            print_info("Arrow error", Some(&e));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use apache_arrow::{
+        array::{Array, AsArray},
+        datatypes::Float64Type,
+    };
+    use interface::UID;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    use super::*;
+
+    #[derive(Serialize)]
+    pub struct Segment {
+        rbms: Vec<f64>,
+        modes: Option<Vec<f64>>,
+    }
+    #[derive(Serialize)]
+    pub struct Mirror {
+        sid: u8,
+        segment: Segment,
+        doublet: (Vec<f64>, Vec<f64>),
+    }
+
+    #[derive(UID)]
+    #[uid(data = Mirror)]
+    pub enum M1 {}
+    #[derive(UID)]
+    #[uid(data = f64)]
+    pub enum Wfe {}
+
+    #[test]
+    fn serde_log() {
+        let path = std::env::temp_dir().join("gmt_dos-clients_arrow-serde");
+        let mut logging = SerdeArrow::builder()
+            .filename(path.to_str().unwrap())
+            .row_group_size(4)
+            .build();
+        for i in 0..10 {
+            let x = i as f64;
+            <SerdeArrow as TryRead<M1>>::try_read(
+                &mut logging,
+                Data::new(Mirror {
+                    sid: 1 + i as u8 % 7,
+                    segment: Segment {
+                        rbms: vec![x; 6],
+                        modes: Some(vec![-x; 3]),
+                    },
+                    doublet: (vec![x], vec![x, x]),
+                }),
+            )
+            .unwrap();
+            <SerdeArrow as TryRead<Wfe>>::try_read(&mut logging, Data::new(x * 1e-9)).unwrap();
+        }
+        drop(logging);
+
+        let file = path.with_extension("parquet");
+        let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(&file).unwrap()).unwrap();
+        assert_eq!(reader.metadata().num_row_groups(), 3);
+        let schema = reader.schema().clone();
+        assert!(matches!(
+            schema.field_with_name("M1").unwrap().data_type(),
+            DataType::Struct(fields) if fields.len() == 3
+        ));
+        assert_eq!(
+            schema.field_with_name("Wfe").unwrap().data_type(),
+            &DataType::Float64
+        );
+        let records: Vec<_> = reader
+            .build()
+            .unwrap()
+            .map(|record| record.unwrap())
+            .collect();
+        assert_eq!(records.iter().map(|r| r.num_rows()).sum::<usize>(), 10);
+        std::fs::remove_file(file).unwrap();
+    }
+
+    #[derive(UID)]
+    #[uid(data = Option<Vec<f64>>)]
+    pub enum Modes {}
+
+    #[test]
+    fn schema_mismatch() {
+        let mut logging = SerdeArrow::builder().row_group_size(2).no_save().build();
+        for _ in 0..2 {
+            <SerdeArrow as TryRead<Modes>>::try_read(&mut logging, Data::new(None)).unwrap();
+        }
+        <SerdeArrow as TryRead<Modes>>::try_read(&mut logging, Data::new(Some(vec![1.; 3])))
+            .unwrap();
+        let result =
+            <SerdeArrow as TryRead<Modes>>::try_read(&mut logging, Data::new(Some(vec![2.; 3])));
+        assert!(matches!(result, Err(ArrowError::Schema(..))));
+        assert_eq!(logging.columns[0].len(), 2);
+
+        let mut logging = SerdeArrow::builder()
+            .row_group_size(2)
+            .field::<Modes>(DataType::new_list(DataType::Float64, true))
+            .no_save()
+            .build();
+        for modes in [None, None, Some(vec![1.; 3]), Some(vec![2.; 3])] {
+            <SerdeArrow as TryRead<Modes>>::try_read(&mut logging, Data::new(modes)).unwrap();
+        }
+        assert_eq!(logging.columns[0].len(), 0);
+    }
+
+    #[derive(UID)]
+    #[uid(data = Vec<f64>)]
+    pub enum Residuals {}
+
+    #[test]
+    fn non_finite_floats() {
+        let mut column = Column::<Residuals>::new(None);
+        column.rows.push_back(Arc::new(vec![
+            f64::NAN,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            1.,
+        ]));
+        let (field, array) = column.encode(1).unwrap();
+        assert_eq!(
+            field.data_type(),
+            &DataType::new_list(DataType::Float64, true)
+        );
+        let values = array.as_list::<i32>().value(0);
+        let values = values.as_primitive::<Float64Type>();
+        assert_eq!(values.null_count(), 0);
+        assert!(values.value(0).is_nan());
+        assert_eq!(
+            &values.values()[1..],
+            &[f64::INFINITY, f64::NEG_INFINITY, 1.]
+        );
+    }
+}
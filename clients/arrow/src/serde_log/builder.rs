@@ -0,0 +1,576 @@
+/*!
+Arrow array builder
+
+The rows of a column are appended to the Arrow array builders of the column data type by the [Builder] serializer,
+without any intermediate representation of the rows.
+*/
+
+use std::sync::Arc;
+
+use apache_arrow::{
+    array::{
+        ArrayBuilder, ArrayRef, BinaryBuilder, BooleanBuilder, Float32Builder, Float64Builder,
+        Int8Builder, Int16Builder, Int32Builder, Int64Builder, ListArray, NullArray, StringBuilder,
+        StructArray, UInt8Builder, UInt16Builder, UInt32Builder, UInt64Builder,
+    },
+    buffer::{NullBuffer, OffsetBuffer},
+    datatypes::{DataType, FieldRef, Fields},
+};
+use serde::{
+    Serialize, Serializer,
+    ser::{
+        SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant, SerializeTuple,
+        SerializeTupleStruct, SerializeTupleVariant,
+    },
+};
+
+use super::schema::{Error, Key};
+
+macro_rules! leaf {
+    ($($variant:ident($builder:ty)),*) => {
+        /// Builder of a primitive, string or binary array
+        pub(super) enum Leaf {
+            $($variant($builder)),*
+        }
+        impl Leaf {
+            fn new(data_type: &DataType) -> Option<Self> {
+                match data_type {
+                    $(DataType::$variant => Some(Leaf::$variant(<$builder>::new())),)*
+                    _ => None,
+                }
+            }
+            fn data_type(&self) -> DataType {
+                match self {
+                    $(Leaf::$variant(_) => DataType::$variant,)*
+                }
+            }
+            fn len(&self) -> usize {
+                match self {
+                    $(Leaf::$variant(builder) => builder.len(),)*
+                }
+            }
+            fn append_null(&mut self) {
+                match self {
+                    $(Leaf::$variant(builder) => builder.append_null(),)*
+                }
+            }
+            fn finish(&mut self) -> ArrayRef {
+                match self {
+                    $(Leaf::$variant(builder) => Arc::new(builder.finish()),)*
+                }
+            }
+        }
+    };
+}
+leaf!(
+    Boolean(BooleanBuilder),
+    Int8(Int8Builder),
+    Int16(Int16Builder),
+    Int32(Int32Builder),
+    Int64(Int64Builder),
+    UInt8(UInt8Builder),
+    UInt16(UInt16Builder),
+    UInt32(UInt32Builder),
+    UInt64(UInt64Builder),
+    Float32(Float32Builder),
+    Float64(Float64Builder),
+    Utf8(StringBuilder),
+    Binary(BinaryBuilder)
+);
+
+/// Builder of the array of a column
+///
+/// The rows are appended to the builder by serializing them into it.
+/// Floats are appended as they are, including NaN and infinities.
+pub(super) enum Builder {
+    Null(usize),
+    Leaf(Leaf),
+    List {
+        field: FieldRef,
+        values: Box<Builder>,
+        offsets: Vec<i32>,
+        validity: Vec<bool>,
+    },
+    Struct {
+        fields: Fields,
+        children: Vec<Builder>,
+        validity: Vec<bool>,
+    },
+}
+impl Builder {
+    /// Creates a builder of an array of the given data type
+    pub fn new(data_type: &DataType) -> Result<Self, Error> {
+        match data_type {
+            DataType::Null => Ok(Builder::Null(0)),
+            DataType::List(field) => Ok(Builder::List {
+                field: field.clone(),
+                values: Box::new(Builder::new(field.data_type())?),
+                offsets: vec![0],
+                validity: Vec::new(),
+            }),
+            DataType::Struct(fields) => Ok(Builder::Struct {
+                fields: fields.clone(),
+                children: fields
+                    .iter()
+                    .map(|field| Builder::new(field.data_type()))
+                    .collect::<Result<_, _>>()?,
+                validity: Vec::new(),
+            }),
+            data_type => Leaf::new(data_type)
+                .map(Builder::Leaf)
+                .ok_or_else(|| Error::new(format!("{data_type} arrays are not supported"))),
+        }
+    }
+    fn data_type(&self) -> DataType {
+        match self {
+            Builder::Null(_) => DataType::Null,
+            Builder::Leaf(leaf) => leaf.data_type(),
+            Builder::List { field, .. } => DataType::List(field.clone()),
+            Builder::Struct { fields, .. } => DataType::Struct(fields.clone()),
+        }
+    }
+    fn len(&self) -> usize {
+        match self {
+            Builder::Null(len) => *len,
+            Builder::Leaf(leaf) => leaf.len(),
+            Builder::List { validity, .. } | Builder::Struct { validity, .. } => validity.len(),
+        }
+    }
+    /// Returns the array of the rows appended so far and resets the builder
+    pub fn finish(&mut self) -> Result<ArrayRef, apache_arrow::error::ArrowError> {
+        Ok(match self {
+            Builder::Null(len) => Arc::new(NullArray::new(std::mem::take(len))),
+            Builder::Leaf(leaf) => leaf.finish(),
+            Builder::List {
+                field,
+                values,
+                offsets,
+                validity,
+            } => Arc::new(ListArray::try_new(
+                field.clone(),
+                OffsetBuffer::new(std::mem::replace(offsets, vec![0]).into()),
+                values.finish()?,
+                nulls(validity),
+            )?),
+            Builder::Struct {
+                fields,
+                children,
+                validity,
+            } => Arc::new(StructArray::try_new(
+                fields.clone(),
+                children
+                    .iter_mut()
+                    .map(|child| child.finish())
+                    .collect::<Result<_, _>>()?,
+                nulls(validity),
+            )?),
+        })
+    }
+    fn append_null(&mut self) {
+        match self {
+            Builder::Null(len) => *len += 1,
+            Builder::Leaf(leaf) => leaf.append_null(),
+            Builder::List {
+                offsets, validity, ..
+            } => {
+                offsets.push(offsets.last().copied().unwrap_or_default());
+                validity.push(false);
+            }
+            Builder::Struct {
+                children, validity, ..
+            } => {
+                children.iter_mut().for_each(|child| child.append_null());
+                validity.push(false);
+            }
+        }
+    }
+    fn append_integer(&mut self, v: i128) -> Result<(), Error> {
+        fn cast<T: TryFrom<i128>>(v: i128) -> Result<T, Error> {
+            T::try_from(v).map_err(|_| Error::new(format!("{v} is out of the range of the array")))
+        }
+        match self {
+            Builder::Leaf(Leaf::Int8(builder)) => builder.append_value(cast(v)?),
+            Builder::Leaf(Leaf::Int16(builder)) => builder.append_value(cast(v)?),
+            Builder::Leaf(Leaf::Int32(builder)) => builder.append_value(cast(v)?),
+            Builder::Leaf(Leaf::Int64(builder)) => builder.append_value(cast(v)?),
+            Builder::Leaf(Leaf::UInt8(builder)) => builder.append_value(cast(v)?),
+            Builder::Leaf(Leaf::UInt16(builder)) => builder.append_value(cast(v)?),
+            Builder::Leaf(Leaf::UInt32(builder)) => builder.append_value(cast(v)?),
+            Builder::Leaf(Leaf::UInt64(builder)) => builder.append_value(cast(v)?),
+            Builder::Leaf(Leaf::Float32(builder)) => builder.append_value(v as f32),
+            Builder::Leaf(Leaf::Float64(builder)) => builder.append_value(v as f64),
+            _ => return Err(self.mismatch("an integer")),
+        }
+        Ok(())
+    }
+    fn append_float(&mut self, v: f64) -> Result<(), Error> {
+        match self {
+            Builder::Leaf(Leaf::Float32(builder)) => builder.append_value(v as f32),
+            Builder::Leaf(Leaf::Float64(builder)) => builder.append_value(v),
+            _ => return Err(self.mismatch("a float")),
+        }
+        Ok(())
+    }
+    fn append_bool(&mut self, v: bool) -> Result<(), Error> {
+        match self {
+            Builder::Leaf(Leaf::Boolean(builder)) => builder.append_value(v),
+            _ => return Err(self.mismatch("a boolean")),
+        }
+        Ok(())
+    }
+    fn append_str(&mut self, v: &str) -> Result<(), Error> {
+        match self {
+            Builder::Leaf(Leaf::Utf8(builder)) => builder.append_value(v),
+            _ => return Err(self.mismatch("a string")),
+        }
+        Ok(())
+    }
+    fn append_bytes(&mut self, v: &[u8]) -> Result<(), Error> {
+        match self {
+            Builder::Leaf(Leaf::Binary(builder)) => builder.append_value(v),
+            _ => return Err(self.mismatch("bytes")),
+        }
+        Ok(())
+    }
+    /// Starts a list
+    fn seq(&mut self) -> Result<Seq<'_>, Error> {
+        match self {
+            Builder::List {
+                values,
+                offsets,
+                validity,
+                ..
+            } => Ok(Seq {
+                values,
+                offsets,
+                validity,
+            }),
+            _ => Err(self.mismatch("a sequence")),
+        }
+    }
+    /// Starts a struct
+    fn record(&mut self) -> Result<Record<'_>, Error> {
+        match self {
+            Builder::Struct {
+                fields,
+                children,
+                validity,
+            } => Ok(Record {
+                fields,
+                children,
+                validity,
+                key: None,
+            }),
+            _ => Err(self.mismatch("a struct")),
+        }
+    }
+    /// Appends a struct with only the field `variant` set and returns the builder of that field
+    fn variant(&mut self, variant: &str) -> Result<&mut Builder, Error> {
+        match self {
+            Builder::Struct {
+                fields,
+                children,
+                validity,
+            } => {
+                let idx = position(fields, variant)?;
+                children
+                    .iter_mut()
+                    .enumerate()
+                    .filter(|(i, _)| *i != idx)
+                    .for_each(|(_, child)| child.append_null());
+                validity.push(true);
+                Ok(&mut children[idx])
+            }
+            _ => Err(self.mismatch("an enum variant")),
+        }
+    }
+    fn mismatch(&self, value: &str) -> Error {
+        Error::new(format!(
+            "cannot append {value} to a {} array",
+            self.data_type()
+        ))
+    }
+}
+
+fn nulls(validity: &mut Vec<bool>) -> Option<NullBuffer> {
+    let validity = std::mem::take(validity);
+    validity.contains(&false).then(|| validity.into())
+}
+
+fn position(fields: &Fields, name: &str) -> Result<usize, Error> {
+    fields
+        .find(name)
+        .map(|(idx, _)| idx)
+        .ok_or_else(|| Error::new(format!("the field {name} is not in the schema")))
+}
+
+impl<'a> Serializer for &'a mut Builder {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Seq<'a>;
+    type SerializeTuple = Seq<'a>;
+    type SerializeTupleStruct = Seq<'a>;
+    type SerializeTupleVariant = Seq<'a>;
+    type SerializeMap = Record<'a>;
+    type SerializeStruct = Record<'a>;
+    type SerializeStructVariant = Record<'a>;
+
+    fn serialize_bool(self, v: bool) -> Result<(), Error> {
+        self.append_bool(v)
+    }
+    fn serialize_i8(self, v: i8) -> Result<(), Error> {
+        self.append_integer(v.into())
+    }
+    fn serialize_i16(self, v: i16) -> Result<(), Error> {
+        self.append_integer(v.into())
+    }
+    fn serialize_i32(self, v: i32) -> Result<(), Error> {
+        self.append_integer(v.into())
+    }
+    fn serialize_i64(self, v: i64) -> Result<(), Error> {
+        self.append_integer(v.into())
+    }
+    fn serialize_u8(self, v: u8) -> Result<(), Error> {
+        self.append_integer(v.into())
+    }
+    fn serialize_u16(self, v: u16) -> Result<(), Error> {
+        self.append_integer(v.into())
+    }
+    fn serialize_u32(self, v: u32) -> Result<(), Error> {
+        self.append_integer(v.into())
+    }
+    fn serialize_u64(self, v: u64) -> Result<(), Error> {
+        self.append_integer(v.into())
+    }
+    fn serialize_f32(self, v: f32) -> Result<(), Error> {
+        self.append_float(v.into())
+    }
+    fn serialize_f64(self, v: f64) -> Result<(), Error> {
+        self.append_float(v)
+    }
+    fn serialize_char(self, v: char) -> Result<(), Error> {
+        self.append_str(v.encode_utf8(&mut [0; 4]))
+    }
+    fn serialize_str(self, v: &str) -> Result<(), Error> {
+        self.append_str(v)
+    }
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), Error> {
+        self.append_bytes(v)
+    }
+    fn serialize_none(self) -> Result<(), Error> {
+        self.append_null();
+        Ok(())
+    }
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<(), Error> {
+        value.serialize(self)
+    }
+    fn serialize_unit(self) -> Result<(), Error> {
+        self.append_null();
+        Ok(())
+    }
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Error> {
+        self.append_null();
+        Ok(())
+    }
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), Error> {
+        self.append_str(variant)
+    }
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(self.variant(variant)?)
+    }
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        self.seq()
+    }
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Error> {
+        self.seq()
+    }
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        self.seq()
+    }
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        self.variant(variant)?.seq()
+    }
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        self.record()
+    }
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Error> {
+        self.record()
+    }
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        self.variant(variant)?.record()
+    }
+}
+
+/// Builder of a list
+pub(super) struct Seq<'a> {
+    values: &'a mut Builder,
+    offsets: &'a mut Vec<i32>,
+    validity: &'a mut Vec<bool>,
+}
+impl Seq<'_> {
+    fn element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        value.serialize(&mut *self.values)
+    }
+    fn close(self) -> Result<(), Error> {
+        let offset = i32::try_from(self.values.len())
+            .map_err(|_| Error::new("the list values overflow the array offsets"))?;
+        self.offsets.push(offset);
+        self.validity.push(true);
+        Ok(())
+    }
+}
+impl SerializeSeq for Seq<'_> {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+    fn end(self) -> Result<(), Error> {
+        self.close()
+    }
+}
+impl SerializeTuple for Seq<'_> {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+    fn end(self) -> Result<(), Error> {
+        self.close()
+    }
+}
+impl SerializeTupleStruct for Seq<'_> {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+    fn end(self) -> Result<(), Error> {
+        self.close()
+    }
+}
+impl SerializeTupleVariant for Seq<'_> {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+    fn end(self) -> Result<(), Error> {
+        self.close()
+    }
+}
+
+/// Builder of a struct
+///
+/// The fields that are not serialized are set to null
+pub(super) struct Record<'a> {
+    fields: &'a Fields,
+    children: &'a mut Vec<Builder>,
+    validity: &'a mut Vec<bool>,
+    key: Option<usize>,
+}
+impl Record<'_> {
+    fn field<T: ?Sized + Serialize>(&mut self, idx: usize, value: &T) -> Result<(), Error> {
+        let child = &mut self.children[idx];
+        if child.len() > self.validity.len() {
+            return Err(Error::new(format!(
+                "the field {} is set twice",
+                self.fields[idx].name()
+            )));
+        }
+        value.serialize(child)
+    }
+    fn close(self) -> Result<(), Error> {
+        let len = self.validity.len();
+        self.children
+            .iter_mut()
+            .filter(|child| child.len() == len)
+            .for_each(|child| child.append_null());
+        self.validity.push(true);
+        Ok(())
+    }
+}
+impl SerializeMap for Record<'_> {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), Error> {
+        self.key = Some(position(self.fields, &key.serialize(Key)?)?);
+        Ok(())
+    }
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        let idx = self
+            .key
+            .take()
+            .ok_or_else(|| Error::new("map value without a key"))?;
+        self.field(idx, value)
+    }
+    fn end(self) -> Result<(), Error> {
+        self.close()
+    }
+}
+impl SerializeStruct for Record<'_> {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.field(position(self.fields, key)?, value)
+    }
+    fn end(self) -> Result<(), Error> {
+        self.close()
+    }
+}
+impl SerializeStructVariant for Record<'_> {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.field(position(self.fields, key)?, value)
+    }
+    fn end(self) -> Result<(), Error> {
+        self.close()
+    }
+}
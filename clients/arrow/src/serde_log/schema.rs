@@ -0,0 +1,498 @@
+/*!
+Arrow data type inference
+
+The Arrow data type of a column is derived from the serde data model of its rows with the [Shape] serializer,
+without any intermediate representation of the rows.
+*/
+
+use std::fmt::Display;
+
+use apache_arrow::datatypes::{DataType, Field};
+use serde::{
+    Serialize, Serializer,
+    ser::{
+        Impossible, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant,
+        SerializeTuple, SerializeTupleStruct, SerializeTupleVariant,
+    },
+};
+
+/// Serialization error
+#[derive(Debug)]
+pub(super) struct Error(String);
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+impl std::error::Error for Error {}
+impl serde::ser::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+impl From<Error> for apache_arrow::error::ArrowError {
+    fn from(e: Error) -> Self {
+        apache_arrow::error::ArrowError::SchemaError(e.0)
+    }
+}
+impl Error {
+    pub(super) fn new<T: Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+/// Shape of the rows of a column
+///
+/// The shape of each row is merged into the shape of the column:
+/// `None` and units leave the shape unchanged,
+/// integers of different types are merged into `Int64` and integers with floats into `Float64`,
+/// the items of a sequence are merged together
+/// and the fields of structs and maps are merged by name.
+#[derive(Debug, Default)]
+pub(super) enum Shape {
+    #[default]
+    Null,
+    Scalar(DataType),
+    List(Box<Shape>),
+    Struct(Vec<(String, Shape)>),
+}
+impl Shape {
+    /// Returns the Arrow data type of the shape
+    pub fn data_type(&self) -> DataType {
+        match self {
+            Shape::Null => DataType::Null,
+            Shape::Scalar(data_type) => data_type.clone(),
+            Shape::List(item) => DataType::new_list(item.data_type(), true),
+            Shape::Struct(fields) => DataType::Struct(
+                fields
+                    .iter()
+                    .map(|(name, shape)| Field::new(name, shape.data_type(), true))
+                    .collect(),
+            ),
+        }
+    }
+    fn scalar(&mut self, data_type: DataType) -> Result<(), Error> {
+        match self {
+            Shape::Null => *self = Shape::Scalar(data_type),
+            Shape::Scalar(current) if *current == data_type => (),
+            Shape::Scalar(current) if current.is_numeric() && data_type.is_numeric() => {
+                *current = if current.is_integer() && data_type.is_integer() {
+                    DataType::Int64
+                } else {
+                    DataType::Float64
+                }
+            }
+            _ => return Err(self.mismatch(&data_type)),
+        }
+        Ok(())
+    }
+    /// Returns the shape of the items of a sequence
+    fn item(&mut self) -> Result<&mut Shape, Error> {
+        if let Shape::Null = self {
+            *self = Shape::List(Box::default());
+        }
+        match self {
+            Shape::List(item) => Ok(item),
+            _ => Err(self.mismatch(&DataType::new_list(DataType::Null, true))),
+        }
+    }
+    /// Returns the shape of the field `name` of a struct
+    fn field(&mut self, name: &str) -> Result<&mut Shape, Error> {
+        let fields = self.record()?;
+        let idx = match fields.iter().position(|(field, _)| field == name) {
+            Some(idx) => idx,
+            None => {
+                fields.push((name.to_string(), Shape::Null));
+                fields.len() - 1
+            }
+        };
+        Ok(&mut fields[idx].1)
+    }
+    /// Returns the fields of a struct
+    fn record(&mut self) -> Result<&mut Vec<(String, Shape)>, Error> {
+        if let Shape::Null = self {
+            *self = Shape::Struct(Vec::new());
+        }
+        match self {
+            Shape::Struct(fields) => Ok(fields),
+            _ => Err(self.mismatch(&DataType::Struct(Default::default()))),
+        }
+    }
+    fn mismatch(&self, data_type: &DataType) -> Error {
+        Error(format!(
+            "cannot merge {data_type} into {}",
+            self.data_type()
+        ))
+    }
+}
+
+impl<'a> Serializer for &'a mut Shape {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = &'a mut Shape;
+    type SerializeTuple = &'a mut Shape;
+    type SerializeTupleStruct = &'a mut Shape;
+    type SerializeTupleVariant = &'a mut Shape;
+    type SerializeMap = MapShape<'a>;
+    type SerializeStruct = &'a mut Shape;
+    type SerializeStructVariant = &'a mut Shape;
+
+    fn serialize_bool(self, _v: bool) -> Result<(), Error> {
+        self.scalar(DataType::Boolean)
+    }
+    fn serialize_i8(self, _v: i8) -> Result<(), Error> {
+        self.scalar(DataType::Int8)
+    }
+    fn serialize_i16(self, _v: i16) -> Result<(), Error> {
+        self.scalar(DataType::Int16)
+    }
+    fn serialize_i32(self, _v: i32) -> Result<(), Error> {
+        self.scalar(DataType::Int32)
+    }
+    fn serialize_i64(self, _v: i64) -> Result<(), Error> {
+        self.scalar(DataType::Int64)
+    }
+    fn serialize_u8(self, _v: u8) -> Result<(), Error> {
+        self.scalar(DataType::UInt8)
+    }
+    fn serialize_u16(self, _v: u16) -> Result<(), Error> {
+        self.scalar(DataType::UInt16)
+    }
+    fn serialize_u32(self, _v: u32) -> Result<(), Error> {
+        self.scalar(DataType::UInt32)
+    }
+    fn serialize_u64(self, _v: u64) -> Result<(), Error> {
+        self.scalar(DataType::UInt64)
+    }
+    fn serialize_f32(self, _v: f32) -> Result<(), Error> {
+        self.scalar(DataType::Float32)
+    }
+    fn serialize_f64(self, _v: f64) -> Result<(), Error> {
+        self.scalar(DataType::Float64)
+    }
+    fn serialize_char(self, _v: char) -> Result<(), Error> {
+        self.scalar(DataType::Utf8)
+    }
+    fn serialize_str(self, _v: &str) -> Result<(), Error> {
+        self.scalar(DataType::Utf8)
+    }
+    fn serialize_bytes(self, _v: &[u8]) -> Result<(), Error> {
+        self.scalar(DataType::Binary)
+    }
+    fn serialize_none(self) -> Result<(), Error> {
+        Ok(())
+    }
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<(), Error> {
+        value.serialize(self)
+    }
+    fn serialize_unit(self) -> Result<(), Error> {
+        Ok(())
+    }
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Error> {
+        Ok(())
+    }
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<(), Error> {
+        self.scalar(DataType::Utf8)
+    }
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(self.field(variant)?)
+    }
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        self.item()
+    }
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Error> {
+        self.item()
+    }
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        self.item()
+    }
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        self.field(variant)?.item()
+    }
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        self.record()?;
+        Ok(MapShape {
+            shape: self,
+            key: None,
+        })
+    }
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Error> {
+        self.record()?;
+        Ok(self)
+    }
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        self.field(variant)?.serialize_struct(variant, len)
+    }
+}
+
+impl SerializeSeq for &mut Shape {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        value.serialize(&mut **self)
+    }
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+impl SerializeTuple for &mut Shape {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        value.serialize(&mut **self)
+    }
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+impl SerializeTupleStruct for &mut Shape {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        value.serialize(&mut **self)
+    }
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+impl SerializeTupleVariant for &mut Shape {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        value.serialize(&mut **self)
+    }
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+impl SerializeStruct for &mut Shape {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(self.field(key)?)
+    }
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+impl SerializeStructVariant for &mut Shape {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(self.field(key)?)
+    }
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// Shape of a map
+pub(super) struct MapShape<'a> {
+    shape: &'a mut Shape,
+    key: Option<String>,
+}
+impl SerializeMap for MapShape<'_> {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), Error> {
+        self.key = Some(key.serialize(Key)?);
+        Ok(())
+    }
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| Error::new("map value without a key"))?;
+        value.serialize(self.shape.field(&key)?)
+    }
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// Serializer of map keys into struct field names
+///
+/// Only strings, characters, integers and unit variants are valid keys
+pub(super) struct Key;
+impl Key {
+    fn invalid() -> Error {
+        Error::new("map keys must be strings or integers")
+    }
+}
+impl Serializer for Key {
+    type Ok = String;
+    type Error = Error;
+    type SerializeSeq = Impossible<String, Error>;
+    type SerializeTuple = Impossible<String, Error>;
+    type SerializeTupleStruct = Impossible<String, Error>;
+    type SerializeTupleVariant = Impossible<String, Error>;
+    type SerializeMap = Impossible<String, Error>;
+    type SerializeStruct = Impossible<String, Error>;
+    type SerializeStructVariant = Impossible<String, Error>;
+
+    fn serialize_bool(self, _v: bool) -> Result<String, Error> {
+        Err(Self::invalid())
+    }
+    fn serialize_i8(self, v: i8) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+    fn serialize_i16(self, v: i16) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+    fn serialize_i32(self, v: i32) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+    fn serialize_i64(self, v: i64) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+    fn serialize_u8(self, v: u8) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+    fn serialize_u16(self, v: u16) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+    fn serialize_u32(self, v: u32) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+    fn serialize_u64(self, v: u64) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+    fn serialize_f32(self, _v: f32) -> Result<String, Error> {
+        Err(Self::invalid())
+    }
+    fn serialize_f64(self, _v: f64) -> Result<String, Error> {
+        Err(Self::invalid())
+    }
+    fn serialize_char(self, v: char) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+    fn serialize_str(self, v: &str) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+    fn serialize_bytes(self, _v: &[u8]) -> Result<String, Error> {
+        Err(Self::invalid())
+    }
+    fn serialize_none(self) -> Result<String, Error> {
+        Err(Self::invalid())
+    }
+    fn serialize_some<T: ?Sized + Serialize>(self, _value: &T) -> Result<String, Error> {
+        Err(Self::invalid())
+    }
+    fn serialize_unit(self) -> Result<String, Error> {
+        Err(Self::invalid())
+    }
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<String, Error> {
+        Err(Self::invalid())
+    }
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<String, Error> {
+        Ok(variant.to_string())
+    }
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<String, Error> {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<String, Error> {
+        Err(Self::invalid())
+    }
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        Err(Self::invalid())
+    }
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Error> {
+        Err(Self::invalid())
+    }
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        Err(Self::invalid())
+    }
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        Err(Self::invalid())
+    }
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        Err(Self::invalid())
+    }
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Error> {
+        Err(Self::invalid())
+    }
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        Err(Self::invalid())
+    }
+}
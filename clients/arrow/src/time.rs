@@ -0,0 +1,340 @@
+/*!
+# Time base
+
+The [Arrow](crate::Arrow) and [SerdeArrow](crate::SerdeArrow) loggers optionally record,
+with each row of data, the columns:
+ - `Step`: the index of the simulation sample,
+ - `Time`: the simulation time in seconds.
+
+The `k`th read of the inputs of a logger actor with the inputs rate `NI` (`Terminator<_, NI>`)
+happens at the simulation sample `k NI`, and with a decimation factor `d`,
+the `i`th row of data is the `(i+1)d - 1`th read.
+The simulation time is the sample index divided by the simulation sampling frequency.
+
+The data logged at different rates into different files are loaded and aligned on a common time base with [Align].
+
+# Example
+
+```no_run
+use gmt_dos_clients_arrow::{Align, Alignment, Arrow};
+
+let logging_1 = Arrow::builder(8000).time(8e3).filename("model-data_1").build();
+let logging_10 = Arrow::builder(800).time(8e3).filename("model-data_10").build();
+
+// ... run the model ...
+
+let record = Align::new(["model-data_1", "model-data_10"])
+    .alignment(Alignment::Linear)
+    .load()?;
+# Ok::<(), gmt_dos_clients_arrow::ArrowError>(())
+```
+*/
+
+use std::{
+    env,
+    fs::File,
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use apache_arrow::{
+    array::{Array, ArrayRef, AsArray, Float64Array, Float64Builder, ListBuilder, UInt64Array},
+    compute::{can_cast_types, cast, concat_batches, take},
+    datatypes::{DataType, Field, Float64Type, Schema},
+    record_batch::{RecordBatch, RecordBatchReader},
+};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+use crate::{ArrowError, Result};
+
+/// Name of the simulation sample index column
+pub const STEP: &str = "Step";
+/// Name of the simulation time column
+pub const TIME: &str = "Time";
+
+/// Returns the `Step` and `Time` columns of the given `rows`
+pub(crate) fn time_columns(
+    sampling_frequency: f64,
+    rate: usize,
+    decimation: usize,
+    rows: Range<usize>,
+) -> [(Field, ArrayRef); 2] {
+    let steps: Vec<_> = rows
+        .map(|i| (((i + 1) * decimation - 1) * rate) as u64)
+        .collect();
+    let time =
+        Float64Array::from_iter_values(steps.iter().map(|&step| step as f64 / sampling_frequency));
+    [
+        (
+            Field::new(STEP, DataType::UInt64, false),
+            Arc::new(UInt64Array::from(steps)),
+        ),
+        (Field::new(TIME, DataType::Float64, false), Arc::new(time)),
+    ]
+}
+
+/// Data alignment method
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Alignment {
+    /// The last sample at or before the time (zero-order hold)
+    #[default]
+    Hold,
+    /// The linear interpolation of the samples before and after the time
+    ///
+    /// Only the lists of numbers are interpolated, the other columns are held
+    Linear,
+}
+
+/// Loader of data logged at different rates
+///
+/// The data files must have been saved with the `Time` column.
+/// The data of all the files are aligned on the time base of the first file
+/// or on a user defined time base, and are merged into a single record
+/// with the `Time` column of the time base.
+/// The data before the first sample of a file or, with [Alignment::Linear], after the last one are null.
+/// If the same column is found in several files, the name of the file is prepended
+/// to the column name: `<file>/<column>`.
+#[derive(Debug, Clone)]
+pub struct Align {
+    paths: Vec<PathBuf>,
+    time: Option<Vec<f64>>,
+    alignment: Alignment,
+}
+impl Align {
+    /// Creates a new loader for the [Parquet](https://docs.rs/parquet) files
+    pub fn new<P: AsRef<Path>>(paths: impl IntoIterator<Item = P>) -> Self {
+        Self {
+            paths: paths
+                .into_iter()
+                .map(|path| path.as_ref().to_path_buf())
+                .collect(),
+            time: None,
+            alignment: Default::default(),
+        }
+    }
+    /// Sets the time base in seconds (default: the time of the first file)
+    pub fn time_base(mut self, time: Vec<f64>) -> Self {
+        self.time = Some(time);
+        self
+    }
+    /// Sets the [Alignment] method (default: [Alignment::Hold])
+    pub fn alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+    /// Loads and aligns the data
+    ///
+    /// The [Parquet](https://docs.rs/parquet) data files are loaded from the current
+    /// directory unless the environment variable `DATA_REPO` is set to another directory.
+    pub fn load(self) -> Result<RecordBatch> {
+        let records = self
+            .paths
+            .iter()
+            .map(|path| {
+                let record = read(path)?;
+                let time = record
+                    .column_by_name(TIME)
+                    .and_then(|time| time.as_primitive_opt::<Float64Type>())
+                    .ok_or_else(|| ArrowError::FieldNotFound(TIME.to_string()))?
+                    .values()
+                    .to_vec();
+                Ok((path, record, time))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let time = match self.time {
+            Some(time) => time,
+            None => records
+                .first()
+                .map(|(_, _, time)| time.clone())
+                .ok_or(ArrowError::NoRecord)?,
+        };
+
+        let mut fields = vec![Field::new(TIME, DataType::Float64, false)];
+        let mut columns: Vec<ArrayRef> = vec![Arc::new(Float64Array::from(time.clone()))];
+        for (path, record, record_time) in &records {
+            let schema = record.schema();
+            for (field, column) in schema.fields().iter().zip(record.columns()) {
+                if field.name() == STEP || field.name() == TIME {
+                    continue;
+                }
+                let column = match self.alignment {
+                    Alignment::Linear if can_interpolate(column.data_type()) => {
+                        interpolate(column, record_time, &time)?
+                    }
+                    _ => hold(column, record_time, &time)?,
+                };
+                let name = if fields.iter().any(|f| f.name() == field.name()) {
+                    format!(
+                        "{}/{}",
+                        path.file_stem().unwrap_or_default().to_string_lossy(),
+                        field.name()
+                    )
+                } else {
+                    field.name().clone()
+                };
+                fields.push(Field::new(name, column.data_type().clone(), true));
+                columns.push(column);
+            }
+        }
+        Ok(RecordBatch::try_new(
+            Arc::new(Schema::new(fields)),
+            columns,
+        )?)
+    }
+}
+
+/// Loads a [Parquet](https://docs.rs/parquet) data file
+fn read(path: &Path) -> Result<RecordBatch> {
+    let root_env = env::var("DATA_REPO").unwrap_or_else(|_| ".".to_string());
+    let filename = Path::new(&root_env).join(path).with_extension("parquet");
+    let file = File::open(&filename)?;
+    log::info!("Loading {:?}", filename);
+    let reader = ParquetRecordBatchReaderBuilder::try_new(file)?.build()?;
+    let schema = reader.schema();
+    let records = reader.collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(concat_batches(&schema, records.as_slice())?)
+}
+
+/// Returns the index of the last sample at or before the time `t`
+fn last_before(time: &[f64], t: f64) -> Option<usize> {
+    time.partition_point(|&ti| ti <= t).checked_sub(1)
+}
+
+/// Selects the last sample at or before each time of the time base
+fn hold(column: &ArrayRef, time: &[f64], time_base: &[f64]) -> Result<ArrayRef> {
+    let indices: UInt64Array = time_base
+        .iter()
+        .map(|&t| last_before(time, t).map(|i| i as u64))
+        .collect();
+    Ok(take(column, &indices, None)?)
+}
+
+fn float_list() -> DataType {
+    DataType::List(Arc::new(Field::new("values", DataType::Float64, false)))
+}
+
+fn can_interpolate(data_type: &DataType) -> bool {
+    matches!(data_type, DataType::List(field) if field.data_type().is_numeric())
+        && can_cast_types(data_type, &float_list())
+}
+
+/// Interpolates linearly the samples at each time of the time base
+fn interpolate(column: &ArrayRef, time: &[f64], time_base: &[f64]) -> Result<ArrayRef> {
+    let column = cast(column, &float_list())?;
+    let lists = column.as_list::<i32>();
+    let values = |i: usize| lists.value(i).as_primitive::<Float64Type>().clone();
+    let mut builder = ListBuilder::new(Float64Builder::new()).with_field(Arc::new(Field::new(
+        "values",
+        DataType::Float64,
+        false,
+    )));
+    for &t in time_base {
+        match last_before(time, t) {
+            Some(i) if time[i] == t => {
+                builder.values().append_slice(values(i).values());
+                builder.append(true);
+            }
+            Some(i) if i + 1 < time.len() => {
+                let w = (t - time[i]) / (time[i + 1] - time[i]);
+                let (v0, v1) = (values(i), values(i + 1));
+                builder.values().extend(
+                    v0.values()
+                        .iter()
+                        .zip(v1.values().iter())
+                        .map(|(v0, v1)| Some(v0 + w * (v1 - v0))),
+                );
+                builder.append(true);
+            }
+            _ => builder.append_null(),
+        }
+    }
+    Ok(Arc::new(builder.finish()))
+}
+
+#[cfg(test)]
+mod tests {
+    use interface::{ClientHooks, Data, Entry, Read, UID, Update};
+
+    use super::*;
+    use crate::Arrow;
+
+    #[derive(UID)]
+    pub enum Fast {}
+    #[derive(UID)]
+    pub enum Slow {}
+
+    #[test]
+    fn align() {
+        let dir = env::temp_dir();
+        let fast = dir.join("gmt_dos-clients_arrow-align_1");
+        let slow = dir.join("gmt_dos-clients_arrow-align_4");
+
+        let mut arw = Arrow::builder(8)
+            .time(100.)
+            .filename(fast.to_str().unwrap())
+            .build();
+        <Arrow as Entry<Fast>>::entry(&mut arw, 1);
+        for i in 0..8 {
+            arw.read(Data::<Fast>::new(vec![i as f64]));
+        }
+        drop(arw);
+
+        let mut arw = Arrow::builder(8)
+            .time(100.)
+            .decimation(2)
+            .filename(slow.to_str().unwrap())
+            .build();
+        arw.set_inputs_rate(2);
+        <Arrow as Entry<Slow>>::entry(&mut arw, 1);
+        for i in 0..4 {
+            arw.read(Data::<Slow>::new(vec![10. * i as f64]));
+        }
+        drop(arw);
+
+        let slow_record = read(&slow).unwrap();
+        let steps = slow_record
+            .column_by_name(STEP)
+            .unwrap()
+            .as_primitive::<apache_arrow::datatypes::UInt64Type>();
+        assert_eq!(steps.values().to_vec(), vec![2, 6]);
+
+        let data = |record: &RecordBatch, name: &str| -> Vec<Option<f64>> {
+            let lists = record.column_by_name(name).unwrap().as_list::<i32>();
+            (0..lists.len())
+                .map(|i| {
+                    lists
+                        .is_valid(i)
+                        .then(|| lists.value(i).as_primitive::<Float64Type>().value(0))
+                })
+                .collect()
+        };
+
+        let record = Align::new([&fast, &slow]).load().unwrap();
+        assert_eq!(record.num_rows(), 8);
+        assert_eq!(
+            data(&record, "Slow"),
+            vec![
+                None,
+                None,
+                Some(10.),
+                Some(10.),
+                Some(10.),
+                Some(10.),
+                Some(30.),
+                Some(30.)
+            ]
+        );
+
+        let record = Align::new([&slow, &fast])
+            .alignment(Alignment::Linear)
+            .time_base(vec![0.03, 0.04, 0.07])
+            .load()
+            .unwrap();
+        assert_eq!(data(&record, "Slow"), vec![Some(15.), Some(20.), None]);
+        assert_eq!(data(&record, "Fast"), vec![Some(3.), Some(4.), Some(7.)]);
+
+        std::fs::remove_file(fast.with_extension("parquet")).unwrap();
+        std::fs::remove_file(slow.with_extension("parquet")).unwrap();
+    }
+}
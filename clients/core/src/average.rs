@@ -1,4 +1,4 @@
-use super::{Data, Read, UniqueIdentifier, Update, Write};
+use super::{ClientHooks, Data, Read, UniqueIdentifier, Update, Write};
 use std::{
     fmt::Debug,
     marker::PhantomData,
@@ -42,6 +42,12 @@ where
     T: Send + Sync,
 {
 }
+impl<T, U: UniqueIdentifier<DataType = Vec<T>>, V: UniqueIdentifier<DataType = Vec<T>>> ClientHooks
+    for Average<T, U, V>
+where
+    T: Send + Sync,
+{
+}
 impl<U, T, V> Read<U> for Average<T, U, V>
 where
     U: UniqueIdentifier<DataType = Vec<T>>,
@@ -0,0 +1,508 @@
+//! # Frequency response estimation
+//!
+//! [Bode] estimates online the frequency response of a MIMO system from the excitation `u`,
+//! received with the [Excitation] input, and the response `y` of the system.
+//!
+//! The excitation and the response are split into blocks and the Fourier transforms `U(f)` and `Y(f)`
+//! of each block update the averaged spectral densities `Suu=<U U*>`, `Syu=<Y U*>` and `Syy=<|Y|²>`.
+//! When the model completes, the frequency response `H=Syu Suu⁻¹` and the multiple coherence
+//! `γ²=H Syu*/Syy` of each output are derived from the spectral densities (see [Bode::frequency_response]).
+//!
+//! The Fourier transforms are computed with either ([BodeMethod]):
+//!  * Welch method: overlapping Hann-windowed blocks Fourier transformed with a FFT,
+//!    suited for broadband excitations like [Signal::Chirp](crate::signals::Signal::Chirp),
+//!    [Signal::Prbs](crate::signals::Signal::Prbs) or white noise,
+//!  * sine correlation: the blocks are correlated with sinusoids at the given frequencies,
+//!    suited for a [Signal::Multisine](crate::signals::Signal::Multisine) excitation
+//!    (the blocks must be an integer number of periods of all the frequencies).
+//!
+//! For a system with several inputs, the excitations of the inputs must be uncorrelated
+//! and the spectral densities must be averaged over at least as many blocks as the number of inputs.
+//!
+//! The response UID `Y` is a type parameter of [Bode] and the excitation UID is [`Excitation<Y>`](Excitation),
+//! an excitation source like [Signals](crate::signals::Signals) can write both.
+//!
+//! ```
+//! use gmt_dos_clients::{
+//!     bode::{Bode, BodeMethod, Excitation},
+//!     signals::Signal,
+//! };
+//! use interface::{Data, Read, UID, Update};
+//!
+//! #[derive(UID)]
+//! enum Y {}
+//!
+//! let fs = 1000.;
+//! let frequencies_hz = vec![10., 50., 100.];
+//! let excitation = Signal::multisine(1., fs, frequencies_hz.clone());
+//! // 5 averages of 100ms blocks
+//! let mut bode = Bode::<Y>::new(fs, BodeMethod::sine_correlation(frequencies_hz, 100));
+//! for i in 0..500 {
+//!     let u = excitation.get(i);
+//!     <_ as Read<Excitation<Y>>>::read(&mut bode, Data::new(vec![u]));
+//!     <_ as Read<Y>>::read(&mut bode, Data::new(vec![2. * u]));
+//!     bode.update();
+//! }
+//! let frf = bode.frequency_response()?;
+//! assert!(frf.magnitude().iter().all(|m| (m[0] - 2.).abs() < 1e-9));
+//! assert!(frf.phase().iter().all(|p| p[0].abs() < 1e-9));
+//! # Ok::<(), gmt_dos_clients::bode::BodeError>(())
+//! ```
+
+use super::{
+    ClientHooks, Data, Read, UniqueIdentifier, Update,
+    fft::{fft, hann},
+};
+use num_complex::Complex;
+use std::{f64::consts::PI, marker::PhantomData, sync::Arc};
+
+#[derive(Debug, thiserror::Error)]
+pub enum BodeError {
+    #[error("no spectral density has been averaged yet")]
+    NoData,
+    #[error("the excitation spectral density is singular at {0}Hz")]
+    Singular(f64),
+    #[cfg(feature = "arrow")]
+    #[error("failed to export the frequency response to Parquet")]
+    Arrow(#[from] gmt_dos_clients_arrow::ArrowError),
+}
+
+type Result<T> = std::result::Result<T, BodeError>;
+
+/// Fourier transform methods of [Bode]
+#[derive(Debug, Clone)]
+pub enum BodeMethod {
+    /// Welch method with blocks of `n_fft` samples (a power of 2)
+    /// overlapping by the fraction `overlap` of the block size
+    Welch { n_fft: usize, overlap: f64 },
+    /// Correlation of blocks of `n_sample` samples with sinusoids at the given frequencies
+    SineCorrelation {
+        frequencies_hz: Vec<f64>,
+        n_sample: usize,
+    },
+}
+impl BodeMethod {
+    /// Welch method with blocks of `n_fft` samples overlapping by 50%
+    pub fn welch(n_fft: usize) -> Self {
+        Self::Welch {
+            n_fft,
+            overlap: 0.5,
+        }
+    }
+    /// Sine correlation method with blocks of `n_sample` samples
+    pub fn sine_correlation(frequencies_hz: Vec<f64>, n_sample: usize) -> Self {
+        Self::SineCorrelation {
+            frequencies_hz,
+            n_sample,
+        }
+    }
+}
+
+/// Online frequency response estimator of the response `Y`
+#[derive(Debug, Clone)]
+pub struct Bode<Y> {
+    method: BodeMethod,
+    frequencies_hz: Vec<f64>,
+    sampling_frequency_hz: f64,
+    window: Vec<f64>,
+    n_sample: usize,
+    hop: usize,
+    u: Arc<Vec<f64>>,
+    y: Arc<Vec<f64>>,
+    u_block: Vec<Arc<Vec<f64>>>,
+    y_block: Vec<Arc<Vec<f64>>>,
+    suu: Vec<Vec<Complex<f64>>>,
+    syu: Vec<Vec<Complex<f64>>>,
+    syy: Vec<Vec<f64>>,
+    n_average: usize,
+    uid: PhantomData<Y>,
+}
+impl<Y> Bode<Y> {
+    /// Creates a new frequency response estimator for data sampled at the given frequency in Hz
+    pub fn new(sampling_frequency_hz: f64, method: BodeMethod) -> Self {
+        let (frequencies_hz, window, n_sample, hop) = match &method {
+            BodeMethod::Welch { n_fft, overlap } => {
+                let n = *n_fft;
+                assert!(
+                    n.is_power_of_two() && n > 1,
+                    "gmt_dos-clients::Bode: the FFT size must be a power of 2, found {n}"
+                );
+                let window = hann(n);
+                let frequencies_hz = (1..=n / 2)
+                    .map(|k| k as f64 * sampling_frequency_hz / n as f64)
+                    .collect();
+                let hop = ((1. - overlap.clamp(0., 0.95)) * n as f64).round() as usize;
+                (frequencies_hz, window, n, hop.max(1))
+            }
+            BodeMethod::SineCorrelation {
+                frequencies_hz,
+                n_sample,
+            } => (frequencies_hz.clone(), vec![], *n_sample, *n_sample),
+        };
+        Self {
+            method,
+            frequencies_hz,
+            sampling_frequency_hz,
+            window,
+            n_sample,
+            hop,
+            u: Default::default(),
+            y: Default::default(),
+            u_block: Vec::with_capacity(n_sample),
+            y_block: Vec::with_capacity(n_sample),
+            suu: vec![],
+            syu: vec![],
+            syy: vec![],
+            n_average: 0,
+            uid: PhantomData,
+        }
+    }
+    /// Returns the number of blocks the spectral densities have been averaged over
+    pub fn n_average(&self) -> usize {
+        self.n_average
+    }
+    /// Returns the frequencies in Hz of the frequency response
+    pub fn frequencies(&self) -> &[f64] {
+        &self.frequencies_hz
+    }
+    /// Resets the spectral densities
+    pub fn reset(&mut self) {
+        self.u_block.clear();
+        self.y_block.clear();
+        self.suu.clear();
+        self.syu.clear();
+        self.syy.clear();
+        self.n_average = 0;
+    }
+    /// Fourier transforms of each channel of a block
+    fn transform(&self, block: &[Arc<Vec<f64>>]) -> Vec<Vec<Complex<f64>>> {
+        let n_channel = block[0].len();
+        (0..n_channel)
+            .map(|c| match &self.method {
+                BodeMethod::Welch { .. } => {
+                    let mut x: Vec<_> = block
+                        .iter()
+                        .zip(&self.window)
+                        .map(|(s, w)| Complex::new(s[c] * w, 0.))
+                        .collect();
+                    fft(&mut x);
+                    x[1..=self.frequencies_hz.len()].to_vec()
+                }
+                BodeMethod::SineCorrelation { .. } => self
+                    .frequencies_hz
+                    .iter()
+                    .map(|f| {
+                        let w = -2. * PI * f / self.sampling_frequency_hz;
+                        block
+                            .iter()
+                            .enumerate()
+                            .map(|(i, s)| Complex::from_polar(s[c], w * i as f64))
+                            .sum()
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
+    /// Updates the spectral densities with the current block
+    fn average(&mut self) {
+        let u = self.transform(&self.u_block);
+        let y = self.transform(&self.y_block);
+        let (n_u, n_y, n_f) = (u.len(), y.len(), self.frequencies_hz.len());
+        if self.suu.is_empty() {
+            self.suu = vec![vec![Complex::default(); n_u * n_u]; n_f];
+            self.syu = vec![vec![Complex::default(); n_y * n_u]; n_f];
+            self.syy = vec![vec![0.; n_y]; n_f];
+        }
+        for k in 0..n_f {
+            for (i, ui) in u.iter().enumerate() {
+                for (l, ul) in u.iter().enumerate() {
+                    self.suu[k][i * n_u + l] += ui[k] * ul[k].conj();
+                }
+                for (j, yj) in y.iter().enumerate() {
+                    self.syu[k][j * n_u + i] += yj[k] * ui[k].conj();
+                }
+            }
+            for (syy, yj) in self.syy[k].iter_mut().zip(&y) {
+                *syy += yj[k].norm_sqr();
+            }
+        }
+        self.n_average += 1;
+    }
+    /// Computes the frequency response and the coherence from the spectral densities
+    pub fn frequency_response(&self) -> Result<FrequencyResponse> {
+        if self.n_average == 0 {
+            return Err(BodeError::NoData);
+        }
+        let n_u = (self.suu[0].len() as f64).sqrt() as usize;
+        let n_y = self.syy[0].len();
+        let mut response = Vec::with_capacity(self.frequencies_hz.len());
+        let mut coherence = Vec::with_capacity(self.frequencies_hz.len());
+        for (k, &f) in self.frequencies_hz.iter().enumerate() {
+            let inv = inverse(&self.suu[k], n_u).ok_or(BodeError::Singular(f))?;
+            let syu = &self.syu[k];
+            let h: Vec<_> = (0..n_y)
+                .flat_map(|j| {
+                    let inv = &inv;
+                    (0..n_u).map(move |i| {
+                        (0..n_u)
+                            .map(|l| syu[j * n_u + l] * inv[l * n_u + i])
+                            .sum::<Complex<f64>>()
+                    })
+                })
+                .collect();
+            let c = (0..n_y)
+                .map(|j| {
+                    (0..n_u)
+                        .map(|i| (h[j * n_u + i] * syu[j * n_u + i].conj()).re)
+                        .sum::<f64>()
+                        / self.syy[k][j]
+                })
+                .collect();
+            response.push(h);
+            coherence.push(c);
+        }
+        Ok(FrequencyResponse {
+            frequencies_hz: self.frequencies_hz.clone(),
+            n_input: n_u,
+            n_output: n_y,
+            response,
+            coherence,
+        })
+    }
+}
+
+/// Gauss-Jordan inverse of the `n`x`n` row-major matrix `a`, returns `None` if `a` is singular
+fn inverse(a: &[Complex<f64>], n: usize) -> Option<Vec<Complex<f64>>> {
+    let mut a = a.to_vec();
+    let mut inv: Vec<_> = (0..n * n)
+        .map(|i| Complex::new(if i % (n + 1) == 0 { 1. } else { 0. }, 0.))
+        .collect();
+    let scale = a.iter().map(|x| x.norm()).fold(0., f64::max);
+    for c in 0..n {
+        let p = (c..n).max_by(|&i, &j| a[i * n + c].norm().total_cmp(&a[j * n + c].norm()))?;
+        if a[p * n + c].norm() <= 1e-12 * scale {
+            return None;
+        }
+        for k in 0..n {
+            a.swap(c * n + k, p * n + k);
+            inv.swap(c * n + k, p * n + k);
+        }
+        let d = a[c * n + c].inv();
+        for k in 0..n {
+            a[c * n + k] *= d;
+            inv[c * n + k] *= d;
+        }
+        for r in (0..n).filter(|&r| r != c) {
+            let m = a[r * n + c];
+            for k in 0..n {
+                let (ack, ick) = (a[c * n + k], inv[c * n + k]);
+                a[r * n + k] -= m * ack;
+                inv[r * n + k] -= m * ick;
+            }
+        }
+    }
+    Some(inv)
+}
+
+impl<Y> Update for Bode<Y>
+where
+    Y: UniqueIdentifier<DataType = Vec<f64>>,
+{
+    fn update(&mut self) {
+        if self.u.is_empty() || self.y.is_empty() {
+            return;
+        }
+        self.u_block.push(self.u.clone());
+        self.y_block.push(self.y.clone());
+        if self.u_block.len() == self.n_sample {
+            self.average();
+            self.u_block.drain(..self.hop);
+            self.y_block.drain(..self.hop);
+        }
+    }
+}
+impl<Y> ClientHooks for Bode<Y> where Y: UniqueIdentifier<DataType = Vec<f64>> {}
+
+/// Excitation `u` of the response `Y`
+pub struct Excitation<Y>(PhantomData<Y>);
+impl<Y: UniqueIdentifier> UniqueIdentifier for Excitation<Y> {
+    type DataType = Vec<f64>;
+}
+impl<Y> Read<Excitation<Y>> for Bode<Y>
+where
+    Y: UniqueIdentifier<DataType = Vec<f64>>,
+{
+    fn read(&mut self, data: Data<Excitation<Y>>) {
+        self.u = data.into_arc();
+    }
+}
+impl<Y> Read<Y> for Bode<Y>
+where
+    Y: UniqueIdentifier<DataType = Vec<f64>>,
+{
+    fn read(&mut self, data: Data<Y>) {
+        self.y = data.into_arc();
+    }
+}
+
+/// Frequency response estimated by [Bode]
+///
+/// The frequency response at each frequency is a `n_output`x`n_input` row-major matrix
+#[derive(Debug, Clone)]
+pub struct FrequencyResponse {
+    pub frequencies_hz: Vec<f64>,
+    pub n_input: usize,
+    pub n_output: usize,
+    /// Complex frequency response
+    pub response: Vec<Vec<Complex<f64>>>,
+    /// Multiple coherence of each output
+    pub coherence: Vec<Vec<f64>>,
+}
+impl FrequencyResponse {
+    /// Returns the magnitude of the frequency response
+    pub fn magnitude(&self) -> Vec<Vec<f64>> {
+        self.response
+            .iter()
+            .map(|h| h.iter().map(|h| h.norm()).collect())
+            .collect()
+    }
+    /// Returns the phase in radians of the frequency response, unwrapped along the frequencies
+    pub fn phase(&self) -> Vec<Vec<f64>> {
+        let mut phase: Vec<Vec<f64>> = self
+            .response
+            .iter()
+            .map(|h| h.iter().map(|h| h.arg()).collect())
+            .collect();
+        for k in 1..phase.len() {
+            for i in 0..phase[k].len() {
+                let d = phase[k][i] - phase[k - 1][i];
+                phase[k][i] -= 2. * PI * (d / (2. * PI)).round();
+            }
+        }
+        phase
+    }
+}
+
+#[cfg(feature = "arrow")]
+mod parquet {
+    use super::{FrequencyResponse, Result};
+    use gmt_dos_clients_arrow::Arrow;
+    use interface::{Data, Entry, Read, UniqueIdentifier};
+    use std::path::Path;
+
+    /// Frequency in Hz
+    pub enum Frequency {}
+    /// Magnitude of the frequency response
+    pub enum Magnitude {}
+    /// Phase in radians of the frequency response
+    pub enum Phase {}
+    /// Multiple coherence of the outputs
+    pub enum Coherence {}
+    impl UniqueIdentifier for Frequency {
+        type DataType = Vec<f64>;
+    }
+    impl UniqueIdentifier for Magnitude {
+        type DataType = Vec<f64>;
+    }
+    impl UniqueIdentifier for Phase {
+        type DataType = Vec<f64>;
+    }
+    impl UniqueIdentifier for Coherence {
+        type DataType = Vec<f64>;
+    }
+
+    impl FrequencyResponse {
+        /// Saves the frequency, the magnitude, the phase and the coherence to a Parquet file
+        ///
+        /// Each row of the Parquet file corresponds to one frequency,
+        /// see [Arrow::to_parquet] for the location of the file
+        pub fn to_parquet<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+            let mut arrow = Arrow::builder(self.frequencies_hz.len()).no_save().build();
+            let n = self.n_input * self.n_output;
+            <Arrow as Entry<Frequency>>::entry(&mut arrow, 1);
+            <Arrow as Entry<Magnitude>>::entry(&mut arrow, n);
+            <Arrow as Entry<Phase>>::entry(&mut arrow, n);
+            <Arrow as Entry<Coherence>>::entry(&mut arrow, self.n_output);
+            for (((f, m), p), c) in self
+                .frequencies_hz
+                .iter()
+                .zip(self.magnitude())
+                .zip(self.phase())
+                .zip(&self.coherence)
+            {
+                <Arrow as Read<Frequency>>::read(&mut arrow, Data::new(vec![*f]));
+                <Arrow as Read<Magnitude>>::read(&mut arrow, Data::new(m));
+                <Arrow as Read<Phase>>::read(&mut arrow, Data::new(p));
+                <Arrow as Read<Coherence>>::read(&mut arrow, Data::new(c.clone()));
+            }
+            arrow.to_parquet(path)?;
+            Ok(())
+        }
+    }
+}
+#[cfg(feature = "arrow")]
+pub use parquet::{Coherence, Frequency, Magnitude, Phase};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signals::Signal;
+
+    enum Y {}
+    impl UniqueIdentifier for Y {
+        type DataType = Vec<f64>;
+    }
+
+    #[test]
+    fn welch_mimo() {
+        // 2 PRBS excitations, y = [u0[k-1] + 0.5 u1[k], -u1[k]]
+        let (s0, s1) = (
+            Signal::prbs(11, 1., 1).unwrap(),
+            Signal::prbs(9, 1., 1).unwrap(),
+        );
+        let fs = 100.;
+        let mut bode = Bode::<Y>::new(fs, BodeMethod::welch(64));
+        let mut u0 = 0.;
+        for i in 0..4096 {
+            let u = vec![s0.get(i), s1.get(i)];
+            <_ as Read<Excitation<Y>>>::read(&mut bode, Data::new(u.clone()));
+            <_ as Read<Y>>::read(&mut bode, Data::new(vec![u0 + 0.5 * u[1], -u[1]]));
+            bode.update();
+            u0 = u[0];
+        }
+        let frf = bode.frequency_response().unwrap();
+        assert_eq!((frf.n_output, frf.n_input), (2, 2));
+        for (k, f) in frf.frequencies_hz.iter().enumerate() {
+            let h = &frf.response[k];
+            let delay = Complex::from_polar(1., -2. * PI * f / fs);
+            // the delay leaks in between windowed blocks
+            assert!((h[0] - delay).norm() < 5e-2, "{f}: {}", h[0]);
+            assert!((h[1] - 0.5).norm() < 5e-2);
+            assert!(h[2].norm() < 1e-6);
+            assert!((h[3] + 1.).norm() < 1e-6);
+            assert!(frf.coherence[k][0] > 0.95);
+            assert!((frf.coherence[k][1] - 1.).abs() < 1e-6);
+        }
+        let phase = frf.phase();
+        assert!((phase[30][0] + PI * 31. / 32.).abs() < 5e-2);
+    }
+
+    #[cfg(feature = "arrow")]
+    #[test]
+    fn parquet() {
+        let frf = FrequencyResponse {
+            frequencies_hz: vec![1., 2.],
+            n_input: 1,
+            n_output: 1,
+            response: vec![vec![Complex::new(0., 1.)], vec![Complex::new(-2., 0.)]],
+            coherence: vec![vec![1.], vec![0.5]],
+        };
+        let path = std::env::temp_dir().join("bode");
+        frf.to_parquet(&path).unwrap();
+        let mut arrow = gmt_dos_clients_arrow::Arrow::from_parquet(&path).unwrap();
+        let magnitude: Vec<Vec<f64>> = arrow.iter("Magnitude").unwrap().collect();
+        assert_eq!(magnitude, vec![vec![1.], vec![2.]]);
+        let phase: Vec<Vec<f64>> = arrow.iter("Phase").unwrap().collect();
+        assert_eq!(phase, vec![vec![PI / 2.], vec![PI]]);
+    }
+}
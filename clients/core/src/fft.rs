@@ -0,0 +1,45 @@
+//! Fast Fourier transform and spectral window shared by the spectral analysis clients
+
+use num_complex::Complex;
+use std::f64::consts::PI;
+
+/// In-place radix-2 FFT
+///
+/// The length of `x` must be a power of 2
+pub(crate) fn fft(x: &mut [Complex<f64>]) {
+    let n = x.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            x.swap(i, j);
+        }
+    }
+    let mut len = 2;
+    while len <= n {
+        let w = Complex::from_polar(1., -2. * PI / len as f64);
+        for chunk in x.chunks_mut(len) {
+            let mut wk = Complex::new(1., 0.);
+            let (a, b) = chunk.split_at_mut(len / 2);
+            for (a, b) in a.iter_mut().zip(b) {
+                let t = *b * wk;
+                *b = *a - t;
+                *a += t;
+                wk *= w;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+/// Periodic Hann window of `n` samples
+pub(crate) fn hann(n: usize) -> Vec<f64> {
+    (0..n)
+        .map(|i| 0.5 - 0.5 * (2. * PI * i as f64 / n as f64).cos())
+        .collect()
+}
@@ -1,6 +1,6 @@
 use std::{ops::Neg, sync::Arc};
 
-use interface::{Data, Read, UniqueIdentifier, Update, Write};
+use interface::{ClientHooks, Data, Read, UniqueIdentifier, Update, Write};
 
 // #[derive(Default)]
 pub struct Fill<T> {
@@ -20,6 +20,7 @@ impl<T> Fill<T> {
 }
 
 impl<T: Send + Sync> Update for Fill<T> {}
+impl<T: Send + Sync> ClientHooks for Fill<T> {}
 
 impl<T, U> Read<U> for Fill<T>
 where
@@ -0,0 +1,434 @@
+//! # FITS image cube writer
+//!
+//! A client to save a stream of frames into [FITS](https://fits.gsfc.nasa.gov/fits_standard.html) image cubes
+//!
+//! Each frame is appended to a 3D cube of dimensions `width`x`height`x`n_frame`,
+//! the first axis (`NAXIS1`) being the fastest varying index of the frame data.
+//! Frames of type `f32` and `f64` are written as IEEE floating point numbers
+//! with `BITPIX` set to `-32` and `-64`, respectively.
+//!
+//! The header records the sampling time (`TIMEDEL`), the pixel scale (`PIXSCALE`),
+//! the source (`OBJECT`) and the step index of the first frame (`STEP0`).
+//! The step index accounts for the rate of the inputs of the actor the client belongs to.
+//!
+//! A frame that cannot be written to the file is reported as a [FitsError]
+//! by the actor the client belongs to.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use gmt_dos_clients::fits::Fits;
+//!
+//! let fits = Fits::<f32>::new("frames", 512, 512)
+//!     .sampling_time(1e-3)
+//!     .pixel_scale(0.01)
+//!     .source("on-axis star")
+//!     .frames_per_file(100);
+//! ```
+
+use std::{
+    fmt::Display,
+    fs::File,
+    io::{BufWriter, Seek, SeekFrom, Write},
+    marker::PhantomData,
+    path::{Path, PathBuf},
+};
+
+use interface::{ClientHooks, Data, TryRead, TryUpdate, UniqueIdentifier};
+
+const BLOCK: usize = 2880;
+const CARD: usize = 80;
+// position of the NAXIS3 card in the header
+const NAXIS3_CARD: usize = 5;
+
+#[derive(Debug, thiserror::Error)]
+pub enum FitsError {
+    #[error("fits io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("frame size ({0}) do not match the image size ({1})")]
+    Size(usize, usize),
+    #[error("invalid FITS keyword: {0}")]
+    Keyword(String),
+}
+
+type Result<T> = std::result::Result<T, FitsError>;
+
+/// FITS data type
+pub trait FitsData: Copy + Send + Sync {
+    /// FITS `BITPIX` value
+    const BITPIX: i64;
+    fn to_be_bytes(self, buffer: &mut Vec<u8>);
+}
+impl FitsData for f32 {
+    const BITPIX: i64 = -32;
+    fn to_be_bytes(self, buffer: &mut Vec<u8>) {
+        buffer.extend_from_slice(&f32::to_be_bytes(self));
+    }
+}
+impl FitsData for f64 {
+    const BITPIX: i64 = -64;
+    fn to_be_bytes(self, buffer: &mut Vec<u8>) {
+        buffer.extend_from_slice(&f64::to_be_bytes(self));
+    }
+}
+
+/// FITS header keyword value
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Logical(bool),
+    Integer(i64),
+    Float(f64),
+    String(String),
+}
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Self::Logical(value)
+    }
+}
+impl From<i64> for Value {
+    fn from(value: i64) -> Self {
+        Self::Integer(value)
+    }
+}
+impl From<usize> for Value {
+    fn from(value: usize) -> Self {
+        Self::Integer(value as i64)
+    }
+}
+impl From<f64> for Value {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
+    }
+}
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+impl Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Logical(value) => write!(f, "{:>20}", if *value { "T" } else { "F" }),
+            Value::Integer(value) => write!(f, "{value:>20}"),
+            Value::Float(value) => write!(f, "{:>20}", format!("{value:.12E}")),
+            Value::String(value) => {
+                write!(f, "{:<20}", format!("'{:<8}'", value.replace('\'', "''")))
+            }
+        }
+    }
+}
+
+/// Header card
+#[derive(Debug, Clone)]
+struct Card {
+    keyword: String,
+    value: Value,
+    comment: Option<String>,
+}
+impl Card {
+    fn new(keyword: &str, value: impl Into<Value>, comment: Option<&str>) -> Self {
+        Self {
+            keyword: keyword.to_string(),
+            value: value.into(),
+            comment: comment.map(|c| c.to_string()),
+        }
+    }
+    fn to_bytes(&self) -> Result<Vec<u8>> {
+        if self.keyword.is_empty()
+            || self.keyword.len() > 8
+            || !self
+                .keyword
+                .chars()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '-' || c == '_')
+        {
+            return Err(FitsError::Keyword(self.keyword.clone()));
+        }
+        let mut card = format!("{:<8}= {}", self.keyword, self.value);
+        if let Some(comment) = &self.comment {
+            card.push_str(" / ");
+            card.push_str(comment);
+        }
+        let mut card: Vec<u8> = card
+            .chars()
+            .map(|c| {
+                if c.is_ascii() && !c.is_ascii_control() {
+                    c as u8
+                } else {
+                    b'?'
+                }
+            })
+            .take(CARD)
+            .collect();
+        card.resize(CARD, b' ');
+        Ok(card)
+    }
+}
+
+/// FITS image cube writer
+pub struct Fits<T: FitsData> {
+    path: PathBuf,
+    width: usize,
+    height: usize,
+    keywords: Vec<Card>,
+    frames_per_file: Option<usize>,
+    file: Option<BufWriter<File>>,
+    n_file: usize,
+    n_frame: usize,
+    frame_index: usize,
+    inputs_rate: usize,
+    buffer: Vec<u8>,
+    data: PhantomData<T>,
+}
+
+impl<T: FitsData> Fits<T> {
+    /// Creates a new FITS writer
+    ///
+    /// The `width` and `height` of the image must match the frame size.
+    /// The file extension is set to `fits`.
+    pub fn new<P: AsRef<Path>>(path: P, width: usize, height: usize) -> Self {
+        Self {
+            path: path.as_ref().with_extension("fits"),
+            width,
+            height,
+            keywords: vec![Card::new(
+                "ORIGIN",
+                "GMT DOS",
+                Some("GMT Dynamic Optical Simulation"),
+            )],
+            frames_per_file: None,
+            file: None,
+            n_file: 0,
+            n_frame: 0,
+            frame_index: 0,
+            inputs_rate: 1,
+            buffer: Vec::with_capacity(width * height * size_of::<T>()),
+            data: PhantomData,
+        }
+    }
+    /// Sets the sampling time in seconds between 2 frames
+    pub fn sampling_time(self, sampling_time: f64) -> Self {
+        self.keyword("TIMEDEL", sampling_time, Some("sampling time [s]"))
+    }
+    /// Sets the pixel scale in arcsecond
+    pub fn pixel_scale(self, pixel_scale: f64) -> Self {
+        self.keyword("PIXSCALE", pixel_scale, Some("pixel scale [arcsec]"))
+    }
+    /// Sets the name of the source
+    pub fn source<S: Into<String>>(self, source: S) -> Self {
+        self.keyword("OBJECT", source.into(), Some("source"))
+    }
+    /// Adds a keyword to the header
+    ///
+    /// Keywords are at most 8 characters long and made of uppercase letters, digits, `-` and `_`
+    pub fn keyword<V: Into<Value>>(
+        mut self,
+        keyword: &str,
+        value: V,
+        comment: Option<&str>,
+    ) -> Self {
+        self.keywords.retain(|card| card.keyword != keyword);
+        self.keywords.push(Card::new(keyword, value, comment));
+        self
+    }
+    /// Writes a new file every `n` frames
+    ///
+    /// The files are indexed with a `_XXXX` suffix
+    pub fn frames_per_file(mut self, n: usize) -> Self {
+        self.frames_per_file = Some(n);
+        self
+    }
+    /// Returns the path of the current file
+    pub fn path(&self) -> PathBuf {
+        match self.frames_per_file {
+            Some(_) => {
+                let stem = self
+                    .path
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                self.path
+                    .with_file_name(format!("{stem}_{:04}", self.n_file))
+                    .with_extension("fits")
+            }
+            None => self.path.clone(),
+        }
+    }
+    /// Creates a new file and writes the primary header
+    fn create(&mut self) -> Result<()> {
+        let mut cards = vec![
+            Card::new("SIMPLE", true, Some("conforms to FITS standard")),
+            Card::new("BITPIX", T::BITPIX, Some("IEEE floating point")),
+            Card::new("NAXIS", 3usize, None),
+            Card::new("NAXIS1", self.width, Some("image width")),
+            Card::new("NAXIS2", self.height, Some("image height")),
+            Card::new("NAXIS3", 0usize, Some("number of frames")),
+            Card::new(
+                "STEP0",
+                self.frame_index * self.inputs_rate,
+                Some("step index of the first frame"),
+            ),
+        ];
+        cards.extend(self.keywords.iter().cloned());
+        let mut header = Vec::with_capacity(BLOCK);
+        for card in cards {
+            header.extend(card.to_bytes()?);
+        }
+        header.extend(format!("{:<80}", "END").bytes());
+        header.resize(header.len().div_ceil(BLOCK) * BLOCK, b' ');
+        let mut file = BufWriter::new(File::create(self.path())?);
+        file.write_all(&header)?;
+        self.file = Some(file);
+        Ok(())
+    }
+    /// Appends a frame to the cube
+    pub fn append(&mut self, frame: &[T]) -> Result<()> {
+        let size = self.width * self.height;
+        if frame.len() != size {
+            return Err(FitsError::Size(frame.len(), size));
+        }
+        if self.file.is_none() {
+            self.create()?;
+        }
+        self.buffer.clear();
+        frame
+            .iter()
+            .for_each(|&x| FitsData::to_be_bytes(x, &mut self.buffer));
+        if let Some(file) = self.file.as_mut() {
+            file.write_all(&self.buffer)?;
+        }
+        self.n_frame += 1;
+        self.frame_index += 1;
+        if self.frames_per_file.is_some_and(|n| self.n_frame == n) {
+            self.close()?;
+            self.n_file += 1;
+        }
+        Ok(())
+    }
+    /// Completes the current file
+    ///
+    /// Updates the number of frames in the header and pads the data to the FITS block size
+    pub fn close(&mut self) -> Result<()> {
+        let Some(mut file) = self.file.take() else {
+            return Ok(());
+        };
+        let data_size = self.n_frame * self.width * self.height * size_of::<T>();
+        let padding = data_size.div_ceil(BLOCK) * BLOCK - data_size;
+        file.write_all(&vec![0u8; padding])?;
+        file.seek(SeekFrom::Start((NAXIS3_CARD * CARD) as u64))?;
+        file.write_all(&Card::new("NAXIS3", self.n_frame, Some("number of frames")).to_bytes()?)?;
+        file.flush()?;
+        self.n_frame = 0;
+        Ok(())
+    }
+}
+
+impl<T: FitsData> TryUpdate for Fits<T> {
+    type Error = FitsError;
+
+    fn try_update(&mut self) -> Result<&mut Self> {
+        Ok(self)
+    }
+}
+impl<T: FitsData> ClientHooks for Fits<T> {
+    /// Completes the current file if the model is interrupted
+    fn on_shutdown(&mut self) {
+        if let Err(e) = self.close() {
+            log::error!("failed to complete {}: {e}", self.path().display());
+        }
+    }
+    fn set_inputs_rate(&mut self, rate: usize) {
+        self.inputs_rate = rate;
+    }
+}
+
+impl<T, U> TryRead<U> for Fits<T>
+where
+    T: FitsData,
+    U: UniqueIdentifier<DataType = Vec<T>>,
+{
+    type Error = FitsError;
+
+    fn try_read(&mut self, data: Data<U>) -> Result<&mut Self> {
+        self.append(&data)?;
+        Ok(self)
+    }
+}
+
+impl<T: FitsData> Drop for Fits<T> {
+    fn drop(&mut self) {
+        if let Err(e) = self.close() {
+            log::error!("failed to complete {}: {e}", self.path().display());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use interface::UID;
+
+    #[derive(UID)]
+    #[uid(data = Vec<f32>)]
+    enum Frame {}
+
+    #[test]
+    fn cube() {
+        let path = std::env::temp_dir().join("gmt_dos-clients_fits");
+        let mut fits = Fits::<f32>::new(&path, 3, 2)
+            .sampling_time(1e-3)
+            .source("star")
+            .frames_per_file(2);
+        fits.set_inputs_rate(10);
+        for i in 0..5 {
+            <Fits<f32> as TryRead<Frame>>::try_read(
+                &mut fits,
+                Data::new((0..6).map(|j| (6 * i + j) as f32).collect()),
+            )
+            .unwrap();
+        }
+        assert!(matches!(
+            <Fits<f32> as TryRead<Frame>>::try_read(&mut fits, Data::new(vec![0.; 4])),
+            Err(FitsError::Size(4, 6))
+        ));
+        fits.close().unwrap();
+
+        let bytes = std::fs::read(path.with_file_name("gmt_dos-clients_fits_0002.fits")).unwrap();
+        assert_eq!(bytes.len(), 2 * BLOCK);
+        let header = String::from_utf8_lossy(&bytes[..BLOCK]);
+        let cards: Vec<_> = header
+            .as_bytes()
+            .chunks(CARD)
+            .map(String::from_utf8_lossy)
+            .collect();
+        assert_eq!(
+            cards[5].trim(),
+            "NAXIS3  =                    1 / number of frames"
+        );
+        assert_eq!(
+            cards[6].split('/').next().unwrap().trim(),
+            "STEP0   =                   40"
+        );
+        assert!(
+            cards
+                .iter()
+                .any(|card| card.starts_with("OBJECT  = 'star    '"))
+        );
+        let data: Vec<f32> = bytes[BLOCK..BLOCK + 24]
+            .chunks(4)
+            .map(|b| f32::from_be_bytes(b.try_into().unwrap()))
+            .collect();
+        assert_eq!(data, vec![24., 25., 26., 27., 28., 29.]);
+
+        let bytes = std::fs::read(path.with_file_name("gmt_dos-clients_fits_0000.fits")).unwrap();
+        let cards: Vec<_> = bytes[..BLOCK]
+            .chunks(CARD)
+            .map(String::from_utf8_lossy)
+            .collect();
+        assert!(cards[5].starts_with("NAXIS3  =                    2"));
+    }
+}
@@ -6,7 +6,7 @@ use std::fmt::Debug;
 use std::ops::{Add, Mul, Sub};
 use std::sync::Arc;
 
-use interface::{Data, Read, UniqueIdentifier, Update, Write};
+use interface::{ClientHooks, Data, Read, UniqueIdentifier, Update, Write};
 
 /// Fists-order hold
 ///
@@ -80,6 +80,19 @@ where
     for<'a> &'a T: Sub<&'a T, Output = T>,
 {
 }
+impl<T, const NI: usize, const NO: usize> ClientHooks for FirstOrderHold<T, NI, NO>
+where
+    T: Clone
+        + Default
+        + Send
+        + Sync
+        + From<f64>
+        + Mul<Output = T>
+        + for<'a> Add<&'a T, Output = T>
+        + Debug,
+    for<'a> &'a T: Sub<&'a T, Output = T>,
+{
+}
 
 impl<T, U: UniqueIdentifier<DataType = Vec<T>>, const NI: usize, const NO: usize> Read<U>
     for FirstOrderHold<T, NI, NO>
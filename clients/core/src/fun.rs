@@ -1,6 +1,6 @@
 use std::{marker::PhantomData, sync::Arc};
 
-use interface::{Data, Read, UniqueIdentifier, Update, Write};
+use interface::{ClientHooks, Data, Read, UniqueIdentifier, Update, Write};
 
 pub struct Fun<X, Y, F: Fn(&X) -> Y> {
     x: PhantomData<X>,
@@ -33,6 +33,13 @@ where
     F: Send + Sync + Fn(&X) -> Y,
 {
 }
+impl<X, Y, F> ClientHooks for Fun<X, Y, F>
+where
+    X: Send + Sync,
+    Y: Send + Sync,
+    F: Send + Sync + Fn(&X) -> Y,
+{
+}
 
 impl<X, Y, F, U> Read<U> for Fun<X, Y, F>
 where
@@ -1,20 +1,33 @@
-use super::{Data, Read, UniqueIdentifier, Update, Write};
+use super::{ClientHooks, Data, Read, UniqueIdentifier, Update, Write};
 #[cfg(all(feature = "faer", feature = "nalgebra"))]
 use faer_ext::IntoFaer;
 use interface::Size;
 #[cfg(all(feature = "faer", feature = "nalgebra"))]
 use nalgebra as na;
 use num_traits::{One, Zero};
+#[cfg(feature = "faer")]
+use rayon::prelude::*;
 use std::fmt::Debug;
 use std::ops::{AddAssign, Mul, MulAssign};
 use std::sync::Arc;
 
+#[cfg(feature = "faer")]
+mod sparse;
+#[cfg(feature = "faer")]
+pub use sparse::{CompressedSparse, Format, Sparse, SparseError};
+
 pub enum GainKind<T> {
     #[cfg(feature = "faer")]
     Matrix(faer::Mat<T>),
     Vector(Vec<T>),
     #[cfg(feature = "faer")]
     SplitMatrix(Vec<faer::Mat<T>>),
+    /// Sparse matrix in either CSR or CSC format
+    #[cfg(feature = "faer")]
+    Sparse(Sparse<T>),
+    /// Block diagonal gain, the blocks are applied in parallel
+    #[cfg(feature = "faer")]
+    BlockDiagonal(Vec<GainKind<T>>),
 }
 #[cfg(feature = "faer")]
 impl<T> Mul<&[T]> for &GainKind<T>
@@ -28,6 +41,8 @@ where
         + AddAssign
         + Mul
         + MulAssign
+        + Send
+        + Sync
         + faer_traits::RealField,
 {
     type Output = Vec<T>;
@@ -52,6 +67,26 @@ where
                     })
                     .collect()
             }
+            #[cfg(feature = "faer")]
+            GainKind::Sparse(mat) => mat * rhs,
+            #[cfg(feature = "faer")]
+            GainKind::BlockDiagonal(blocks) => {
+                let mut a = 0;
+                let inputs: Vec<_> = blocks
+                    .iter()
+                    .map(|block| {
+                        let n = block.ncols();
+                        let x = &rhs[a..a + n];
+                        a += n;
+                        (block, x)
+                    })
+                    .collect();
+                inputs
+                    .into_par_iter()
+                    .map(|(block, x)| block * x)
+                    .collect::<Vec<_>>()
+                    .concat()
+            }
         }
     }
 }
@@ -101,6 +136,30 @@ impl<'a, T: Clone> From<&'a [faer::MatRef<'a, T>]> for GainKind<T> {
         Self::SplitMatrix(value.into_iter().map(|mat| mat.cloned()).collect())
     }
 }
+#[cfg(feature = "faer")]
+impl<T> From<Sparse<T>> for GainKind<T> {
+    fn from(value: Sparse<T>) -> Self {
+        Self::Sparse(value)
+    }
+}
+#[cfg(feature = "faer")]
+impl<T> From<faer::sparse::SparseRowMat<usize, T>> for GainKind<T> {
+    fn from(value: faer::sparse::SparseRowMat<usize, T>) -> Self {
+        Self::Sparse(value.into())
+    }
+}
+#[cfg(feature = "faer")]
+impl<T> From<faer::sparse::SparseColMat<usize, T>> for GainKind<T> {
+    fn from(value: faer::sparse::SparseColMat<usize, T>) -> Self {
+        Self::Sparse(value.into())
+    }
+}
+#[cfg(feature = "faer")]
+impl<T> From<Vec<GainKind<T>>> for GainKind<T> {
+    fn from(value: Vec<GainKind<T>>) -> Self {
+        Self::BlockDiagonal(value)
+    }
+}
 impl<T> GainKind<T> {
     pub fn ncols(&self) -> usize {
         match self {
@@ -109,6 +168,10 @@ impl<T> GainKind<T> {
             GainKind::Vector(val) => val.len(),
             #[cfg(feature = "faer")]
             GainKind::SplitMatrix(mats) => mats.iter().map(|mat| mat.ncols()).sum(),
+            #[cfg(feature = "faer")]
+            GainKind::Sparse(mat) => mat.ncols(),
+            #[cfg(feature = "faer")]
+            GainKind::BlockDiagonal(blocks) => blocks.iter().map(|block| block.ncols()).sum(),
         }
     }
     pub fn nrows(&self) -> usize {
@@ -118,6 +181,10 @@ impl<T> GainKind<T> {
             GainKind::Vector(val) => val.len(),
             #[cfg(feature = "faer")]
             GainKind::SplitMatrix(mats) => mats.iter().map(|mat| mat.nrows()).sum(),
+            #[cfg(feature = "faer")]
+            GainKind::Sparse(mat) => mat.nrows(),
+            #[cfg(feature = "faer")]
+            GainKind::BlockDiagonal(blocks) => blocks.iter().map(|block| block.nrows()).sum(),
         }
     }
 }
@@ -141,7 +208,9 @@ where
     /// The gain is either a matrix of dimensions `Ny`x`Nu`,
     /// a vector of size `Ny`=`Nu` or a vector or matrices such
     /// as the sum of the number of columns is equal to `Nu` and
-    /// the sum of the number of rows is equal to `Ny`
+    /// the sum of the number of rows is equal to `Ny`.
+    /// The matrix may also be a sparse matrix or a block diagonal matrix
+    /// made of a vector of [GainKind]s
     pub fn new<G: Into<GainKind<T>>>(gain: G) -> Self {
         let gain: GainKind<T> = gain.into();
         Self {
@@ -171,6 +240,22 @@ where
         self.y = Arc::new(&self.gain * self.u.as_slice());
     }
 }
+#[cfg(feature = "faer")]
+impl<T> ClientHooks for Gain<T> where
+    T: Zero
+        + Clone
+        + Copy
+        + PartialEq
+        + Debug
+        + One
+        + AddAssign
+        + Mul
+        + MulAssign
+        + Send
+        + Sync
+        + faer_traits::RealField
+{
+}
 #[cfg(not(feature = "faer"))]
 impl<T> Update for Gain<T>
 where
@@ -180,6 +265,11 @@ where
         self.y = Arc::new(&self.gain * self.u.as_slice());
     }
 }
+#[cfg(not(feature = "faer"))]
+impl<T> ClientHooks for Gain<T> where
+    T: Zero + Clone + Copy + PartialEq + Debug + One + AddAssign + Mul + MulAssign + Send + Sync
+{
+}
 #[cfg(feature = "faer")]
 impl<T, U> Read<U> for Gain<T>
 where
@@ -288,3 +378,25 @@ where
         self.gain.nrows()
     }
 }
+
+#[cfg(all(test, feature = "faer"))]
+mod tests {
+    use super::*;
+    use faer::sparse::Triplet;
+
+    #[test]
+    fn block_diagonal() {
+        let sparse = Sparse::csr(1, 2, &[Triplet::new(0, 1, 3.)]).unwrap();
+        let gain: GainKind<f64> = vec![
+            GainKind::Matrix(faer::mat![[1., 2.], [3., 4.]]),
+            GainKind::Vector(vec![2.]),
+            GainKind::Sparse(sparse),
+        ]
+        .into();
+        assert_eq!((gain.nrows(), gain.ncols()), (4, 5));
+        assert_eq!(
+            &gain * [1., 1., 2., 1., 2.].as_slice(),
+            vec![3., 7., 4., 6.]
+        );
+    }
+}
@@ -0,0 +1,426 @@
+//! Sparse gain matrices
+//!
+//! A [Sparse] gain wraps either a compressed sparse row (CSR) or a compressed sparse column (CSC)
+//! [faer](https://docs.rs/faer) matrix.
+//! Sparse matrices can be loaded from files written with `scipy`:
+//!  * pickle files of a [CompressedSparse] dictionary (`pickle` feature),
+//!  * `npz` files written with `scipy.sparse.save_npz` (`npz` feature),
+//!  * Parquet files with the `row`, `col` and `val` columns of the matrix non-zero entries and
+//!    the matrix shape in the schema metadata with the `nrows` and `ncols` keys (`parquet` feature).
+
+use faer::sparse::{CreationError, SparseColMat, SparseRowMat, Triplet};
+use num_traits::Zero;
+use std::ops::{AddAssign, Mul};
+#[cfg(any(feature = "pickle", feature = "npz", feature = "parquet"))]
+use std::{fs::File, path::Path};
+
+#[derive(Debug, thiserror::Error)]
+pub enum SparseError {
+    #[error("failed to create the sparse matrix")]
+    Creation(#[from] CreationError),
+    #[error("unknown sparse matrix format: {0}")]
+    Format(String),
+    #[error("sparse matrix {0} size ({1}) do not match expected size ({2})")]
+    Size(String, usize, usize),
+    #[error("failed to read the sparse matrix file")]
+    Io(#[from] std::io::Error),
+    #[error("missing {0} in sparse matrix file")]
+    Missing(String),
+    #[cfg(feature = "pickle")]
+    #[error("failed to decode the pickle file")]
+    Pickle(#[from] serde_pickle::Error),
+    #[cfg(feature = "parquet")]
+    #[error("failed to read the Parquet file")]
+    Parquet(#[from] parquet::errors::ParquetError),
+    #[cfg(feature = "parquet")]
+    #[error("failed to process the Arrow record")]
+    Arrow(#[from] apache_arrow::error::ArrowError),
+}
+pub type Result<T> = std::result::Result<T, SparseError>;
+
+/// Sparse matrix compression format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Format {
+    Csr,
+    Csc,
+}
+
+/// Compressed sparse matrix
+///
+/// Follows the layout of `scipy` `csr_matrix` and `csc_matrix`:
+/// for a CSR matrix, `indptr` is of length `nrows+1` and `indices` are column indices,
+/// for a CSC matrix, `indptr` is of length `ncols+1` and `indices` are row indices.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CompressedSparse<T> {
+    pub format: Format,
+    pub shape: (usize, usize),
+    pub indptr: Vec<usize>,
+    pub indices: Vec<usize>,
+    pub data: Vec<T>,
+}
+impl<T: Clone> CompressedSparse<T> {
+    /// Returns the matrix non-zero entries as `(row,col,value)` triplets
+    pub fn triplets(&self) -> Result<Vec<Triplet<usize, usize, T>>> {
+        let (nrows, ncols) = self.shape;
+        let n = match self.format {
+            Format::Csr => nrows,
+            Format::Csc => ncols,
+        };
+        if self.indptr.len() != n + 1 {
+            return Err(SparseError::Size("indptr".into(), self.indptr.len(), n + 1));
+        }
+        if self.indices.len() != self.data.len() {
+            return Err(SparseError::Size(
+                "indices".into(),
+                self.indices.len(),
+                self.data.len(),
+            ));
+        }
+        let nnz = self.indptr[n];
+        if nnz > self.data.len() {
+            return Err(SparseError::Size("data".into(), self.data.len(), nnz));
+        }
+        Ok(self
+            .indptr
+            .windows(2)
+            .enumerate()
+            .flat_map(|(i, w)| {
+                (w[0]..w[1]).map(move |k| match self.format {
+                    Format::Csr => Triplet::new(i, self.indices[k], self.data[k].clone()),
+                    Format::Csc => Triplet::new(self.indices[k], i, self.data[k].clone()),
+                })
+            })
+            .collect())
+    }
+}
+
+/// Sparse matrix gain
+#[derive(Debug, Clone)]
+pub enum Sparse<T> {
+    /// Compressed sparse row matrix
+    Csr(SparseRowMat<usize, T>),
+    /// Compressed sparse column matrix
+    Csc(SparseColMat<usize, T>),
+}
+impl<T> Sparse<T> {
+    pub fn nrows(&self) -> usize {
+        match self {
+            Sparse::Csr(mat) => mat.nrows(),
+            Sparse::Csc(mat) => mat.nrows(),
+        }
+    }
+    pub fn ncols(&self) -> usize {
+        match self {
+            Sparse::Csr(mat) => mat.ncols(),
+            Sparse::Csc(mat) => mat.ncols(),
+        }
+    }
+    /// Returns the number of non-zero entries
+    pub fn nnz(&self) -> usize {
+        match self {
+            Sparse::Csr(mat) => mat.compute_nnz(),
+            Sparse::Csc(mat) => mat.compute_nnz(),
+        }
+    }
+}
+impl<T> Sparse<T>
+where
+    T: faer_traits::ComplexField,
+{
+    /// Creates a CSR matrix from `(row,col,value)` triplets
+    ///
+    /// Duplicated entries are summed
+    pub fn csr(nrows: usize, ncols: usize, triplets: &[Triplet<usize, usize, T>]) -> Result<Self> {
+        Ok(Self::Csr(SparseRowMat::try_new_from_triplets(
+            nrows, ncols, triplets,
+        )?))
+    }
+    /// Creates a CSC matrix from `(row,col,value)` triplets
+    ///
+    /// Duplicated entries are summed
+    pub fn csc(nrows: usize, ncols: usize, triplets: &[Triplet<usize, usize, T>]) -> Result<Self> {
+        Ok(Self::Csc(SparseColMat::try_new_from_triplets(
+            nrows, ncols, triplets,
+        )?))
+    }
+}
+impl<T> TryFrom<CompressedSparse<T>> for Sparse<T>
+where
+    T: faer_traits::ComplexField,
+{
+    type Error = SparseError;
+
+    fn try_from(value: CompressedSparse<T>) -> Result<Self> {
+        let (nrows, ncols) = value.shape;
+        let triplets = value.triplets()?;
+        match value.format {
+            Format::Csr => Self::csr(nrows, ncols, &triplets),
+            Format::Csc => Self::csc(nrows, ncols, &triplets),
+        }
+    }
+}
+impl<T> From<SparseRowMat<usize, T>> for Sparse<T> {
+    fn from(value: SparseRowMat<usize, T>) -> Self {
+        Self::Csr(value)
+    }
+}
+impl<T> From<SparseColMat<usize, T>> for Sparse<T> {
+    fn from(value: SparseColMat<usize, T>) -> Self {
+        Self::Csc(value)
+    }
+}
+impl<T> Mul<&[T]> for &Sparse<T>
+where
+    T: Zero + Copy + AddAssign + Mul<Output = T>,
+{
+    type Output = Vec<T>;
+
+    fn mul(self, rhs: &[T]) -> Self::Output {
+        assert_eq!(
+            self.ncols(),
+            rhs.len(),
+            "input size ({}) do not match sparse matrix # of columns ({})",
+            rhs.len(),
+            self.ncols()
+        );
+        match self {
+            Sparse::Csr(mat) => {
+                let col_idx = mat.col_idx();
+                let val = mat.val();
+                (0..mat.nrows())
+                    .map(|i| {
+                        mat.row_range(i).fold(T::zero(), |mut y, k| {
+                            y += val[k] * rhs[col_idx[k]];
+                            y
+                        })
+                    })
+                    .collect()
+            }
+            Sparse::Csc(mat) => {
+                let row_idx = mat.row_idx();
+                let val = mat.val();
+                let mut y = vec![T::zero(); mat.nrows()];
+                for (j, &u) in rhs.iter().enumerate() {
+                    for k in mat.col_range(j) {
+                        y[row_idx[k]] += val[k] * u;
+                    }
+                }
+                y
+            }
+        }
+    }
+}
+
+impl Sparse<f64> {
+    /// Loads a sparse matrix from a pickle file
+    ///
+    /// The pickle file contains a dictionary with the fields of [CompressedSparse]
+    #[cfg(feature = "pickle")]
+    pub fn from_pickle<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let file = File::open(path)?;
+        let compressed: CompressedSparse<f64> =
+            serde_pickle::from_reader(file, Default::default())?;
+        compressed.try_into()
+    }
+    /// Loads a sparse matrix from a `npz` file written with `scipy.sparse.save_npz`
+    #[cfg(feature = "npz")]
+    pub fn from_npz<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let mut archive = npyz::npz::NpzArchive::open(path)?;
+        let format = match archive
+            .by_name("format")?
+            .ok_or(SparseError::Missing("format".into()))?
+            .into_vec::<Vec<u8>>()?
+            .first()
+            .map(|format| String::from_utf8_lossy(format).to_string())
+            .as_deref()
+        {
+            Some("csr") => Format::Csr,
+            Some("csc") => Format::Csc,
+            format => return Err(SparseError::Format(format.unwrap_or_default().into())),
+        };
+        let shape = npz_indices(&mut archive, "shape")?;
+        let &[nrows, ncols] = shape.as_slice() else {
+            return Err(SparseError::Size("shape".into(), shape.len(), 2));
+        };
+        CompressedSparse {
+            format,
+            shape: (nrows, ncols),
+            indptr: npz_indices(&mut archive, "indptr")?,
+            indices: npz_indices(&mut archive, "indices")?,
+            data: archive
+                .by_name("data")?
+                .ok_or(SparseError::Missing("data".into()))?
+                .into_vec::<f64>()?,
+        }
+        .try_into()
+    }
+    /// Loads a sparse matrix from a Parquet file
+    ///
+    /// The matrix non-zero entries are given by the `row`, `col` and `val` columns and
+    /// the matrix shape by the `nrows` and `ncols` keys of the schema metadata.
+    /// If the metadata is missing, the shape is derived from the largest indices.
+    #[cfg(feature = "parquet")]
+    pub fn from_parquet<P: AsRef<Path>>(path: P) -> Result<Self> {
+        use apache_arrow::{array::AsArray, compute::cast, datatypes::DataType};
+        use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+        let file = File::open(path)?;
+        let builder = ParquetRecordBatchReaderBuilder::try_new(file)?;
+        let metadata = builder.schema().metadata().clone();
+        let mut rows = vec![];
+        let mut cols = vec![];
+        let mut vals = vec![];
+        for record in builder.build()? {
+            let record = record?;
+            let column = |name: &str, data_type: &DataType| {
+                record
+                    .column_by_name(name)
+                    .ok_or_else(|| SparseError::Missing(format!("column {name}")))
+                    .and_then(|array| Ok(cast(array, data_type)?))
+            };
+            rows.extend(
+                column("row", &DataType::UInt64)?
+                    .as_primitive::<apache_arrow::datatypes::UInt64Type>()
+                    .values()
+                    .iter()
+                    .map(|&i| i as usize),
+            );
+            cols.extend(
+                column("col", &DataType::UInt64)?
+                    .as_primitive::<apache_arrow::datatypes::UInt64Type>()
+                    .values()
+                    .iter()
+                    .map(|&i| i as usize),
+            );
+            vals.extend_from_slice(
+                column("val", &DataType::Float64)?
+                    .as_primitive::<apache_arrow::datatypes::Float64Type>()
+                    .values(),
+            );
+        }
+        let size = |key: &str, idx: &[usize]| {
+            metadata
+                .get(key)
+                .and_then(|n| n.parse::<usize>().ok())
+                .unwrap_or_else(|| idx.iter().max().map_or(0, |i| i + 1))
+        };
+        let (nrows, ncols) = (size("nrows", &rows), size("ncols", &cols));
+        let triplets: Vec<_> = rows
+            .into_iter()
+            .zip(cols)
+            .zip(vals)
+            .map(|((i, j), v)| Triplet::new(i, j, v))
+            .collect();
+        Self::csr(nrows, ncols, &triplets)
+    }
+}
+
+#[cfg(feature = "npz")]
+fn npz_indices<R: std::io::Read + std::io::Seek>(
+    archive: &mut npyz::npz::NpzArchive<R>,
+    name: &str,
+) -> Result<Vec<usize>> {
+    let missing = || SparseError::Missing(name.to_string());
+    // scipy stores indices either as 32 or 64 bits integers
+    match archive
+        .by_name(name)?
+        .ok_or_else(missing)?
+        .into_vec::<i64>()
+    {
+        Ok(idx) => Ok(idx.into_iter().map(|i| i as usize).collect()),
+        Err(_) => Ok(archive
+            .by_name(name)?
+            .ok_or_else(missing)?
+            .into_vec::<i32>()?
+            .into_iter()
+            .map(|i| i as usize)
+            .collect()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn triplets() -> Vec<Triplet<usize, usize, f64>> {
+        vec![
+            Triplet::new(0, 0, 1.),
+            Triplet::new(0, 2, 2.),
+            Triplet::new(1, 1, 3.),
+            Triplet::new(2, 0, 4.),
+            Triplet::new(2, 2, 5.),
+        ]
+    }
+
+    #[test]
+    fn csr_csc() {
+        let u = [1., 2., 3.];
+        let csr = Sparse::csr(3, 3, &triplets()).unwrap();
+        let csc = Sparse::csc(3, 3, &triplets()).unwrap();
+        assert_eq!(csr.nnz(), 5);
+        assert_eq!(&csr * u.as_slice(), vec![7., 6., 19.]);
+        assert_eq!(&csc * u.as_slice(), vec![7., 6., 19.]);
+    }
+
+    #[test]
+    fn compressed() {
+        let csc = CompressedSparse {
+            format: Format::Csc,
+            shape: (2, 3),
+            indptr: vec![0, 1, 2, 3],
+            indices: vec![0, 1, 0],
+            data: vec![1., 2., 3.],
+        };
+        let mat = Sparse::try_from(csc).unwrap();
+        assert_eq!((mat.nrows(), mat.ncols()), (2, 3));
+        assert_eq!(&mat * [1., 1., 1.].as_slice(), vec![4., 2.]);
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn parquet() {
+        use apache_arrow::{
+            array::{ArrayRef, Float64Array, UInt32Array},
+            datatypes::{DataType, Field, Schema},
+            record_batch::RecordBatch,
+        };
+        use parquet::arrow::ArrowWriter;
+        use std::{collections::HashMap, sync::Arc};
+
+        let (rows, (cols, vals)): (Vec<u32>, (Vec<u32>, Vec<f64>)) = triplets()
+            .into_iter()
+            .map(|t| (t.row as u32, (t.col as u32, t.val)))
+            .unzip();
+        let schema = Arc::new(
+            Schema::new(vec![
+                Field::new("row", DataType::UInt32, false),
+                Field::new("col", DataType::UInt32, false),
+                Field::new("val", DataType::Float64, false),
+            ])
+            .with_metadata(HashMap::from([
+                ("nrows".to_string(), "3".to_string()),
+                ("ncols".to_string(), "4".to_string()),
+            ])),
+        );
+        let record = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(UInt32Array::from(rows)) as ArrayRef,
+                Arc::new(UInt32Array::from(cols)),
+                Arc::new(Float64Array::from(vals)),
+            ],
+        )
+        .unwrap();
+        let path = std::env::temp_dir().join("gmt_dos-clients_sparse-gain.parquet");
+        let mut writer = ArrowWriter::try_new(File::create(&path).unwrap(), schema, None).unwrap();
+        writer.write(&record).unwrap();
+        writer.close().unwrap();
+
+        let mat = Sparse::from_parquet(&path).unwrap();
+        assert_eq!((mat.nrows(), mat.ncols()), (3, 4));
+        assert_eq!(&mat * [1., 2., 3., 0.].as_slice(), vec![7., 6., 19.]);
+    }
+}
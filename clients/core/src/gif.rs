@@ -10,7 +10,7 @@ use std::{
 };
 
 use gif::{Encoder, EncodingError, Frame as GifFrame};
-use interface::{Read, UniqueIdentifier, Update};
+use interface::{ClientHooks, Read, UniqueIdentifier, Update};
 
 mod frame;
 pub use frame::Frame;
@@ -19,7 +19,7 @@ pub struct Gif<T> {
     width: usize,
     height: usize,
     delay: u16,
-    encoder: Encoder<File>,
+    encoder: Option<Encoder<File>>,
     frame: Frame<T>,
 }
 
@@ -51,7 +51,12 @@ impl<T> FrameBuilder for Gif<T> {
             self.width = height * self.width / self.height;
             self.height = height;
             let file = File::create(self.frame.path())?;
-            self.encoder = Encoder::new(file, self.width as u16, self.height as u16, &[])?;
+            self.encoder = Some(Encoder::new(
+                file,
+                self.width as u16,
+                self.height as u16,
+                &[],
+            )?);
         }
         Ok(self)
     }
@@ -76,7 +81,7 @@ impl<T> Gif<T> {
             width,
             height,
             delay: 100,
-            encoder,
+            encoder: Some(encoder),
             // idx: 0,
             // font: font.into(),
         })
@@ -107,9 +112,29 @@ where
         let mut frame =
             GifFrame::from_rgba_speed(self.width as u16, self.height as u16, &mut q, 10);
         frame.delay = self.delay;
-        self.encoder
-            .write_frame(&frame)
-            .expect("failed to write frame to GIF encoder");
+        if let Some(encoder) = self.encoder.as_mut() {
+            encoder
+                .write_frame(&frame)
+                .expect("failed to write frame to GIF encoder");
+        }
+    }
+}
+impl<T> ClientHooks for Gif<T>
+where
+    T: Send
+        + Sync
+        + PartialOrd
+        + Div<Output = T>
+        + fmt::Debug
+        + Copy
+        + Sub<Output = T>
+        + fmt::LowerExp,
+    f64: From<T>,
+{
+    /// Terminates the GIF file if the model is interrupted
+    fn on_shutdown(&mut self) {
+        // dropping the encoder writes the GIF trailer
+        self.encoder.take();
     }
 }
 // Helper function to draw semi-transparent guide lines
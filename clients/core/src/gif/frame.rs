@@ -13,7 +13,7 @@ use image::{
     {Rgba, RgbaImage},
 };
 use imageproc::drawing::{draw_cross_mut, draw_text_mut};
-use interface::{Read, UniqueIdentifier, Update};
+use interface::{ClientHooks, Read, UniqueIdentifier, Update};
 
 use super::GifError;
 
@@ -227,6 +227,20 @@ where
         self.idx += 1;
     }
 }
+impl<T, F> ClientHooks for Frame<T, F>
+where
+    T: Send
+        + Sync
+        + PartialOrd
+        + Div<Output = T>
+        + fmt::Debug
+        + Copy
+        + Sub<Output = T>
+        + fmt::LowerExp,
+    f64: From<T>,
+    F: Send + Sync + Fn(&T) -> T,
+{
+}
 impl<T, F, U> Read<U> for Frame<T, F>
 where
     T: Send
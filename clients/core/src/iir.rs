@@ -2,7 +2,7 @@
 //!
 //!
 
-use interface::{Data, Read, UniqueIdentifier, Update, Write};
+use interface::{ClientHooks, Data, Read, UniqueIdentifier, Update, Write};
 use std::collections::VecDeque;
 use std::ops::{Mul, Sub};
 
@@ -95,6 +95,10 @@ where
         }
     }
 }
+impl<T> ClientHooks for IIRFilter<T> where
+    T: Send + Sync + Sub<Output = T> + Mul<Output = T> + Copy + std::iter::Sum
+{
+}
 
 impl<T, U> Read<U> for IIRFilter<T>
 where
@@ -8,7 +8,7 @@
 //! `y` will be transform as `y = y - o`.
 //! The offset must be inside an [Option].
 
-use super::{Data, Read, UniqueIdentifier, Update, Write};
+use super::{ClientHooks, Data, Read, UniqueIdentifier, Update, Write};
 use std::{
     fmt::{Debug, Display},
     marker::PhantomData,
@@ -17,6 +17,7 @@ use std::{
 };
 
 /// Integral controller
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Debug)]
 pub struct Integrator<U: UniqueIdentifier> {
     u: Arc<U::DataType>,
@@ -150,6 +151,12 @@ where
         }
     }
 }
+impl<T, U> ClientHooks for Integrator<U>
+where
+    T: Copy + Mul<Output = T> + Sub<Output = T> + SubAssign + AddAssign + Debug + Send + Sync,
+    U: UniqueIdentifier<DataType = Vec<T>>,
+{
+}
 impl<T, U> Read<U> for Integrator<U>
 where
     T: Copy + Mul<Output = T> + Sub<Output = T> + SubAssign + AddAssign + Debug + Send + Sync,
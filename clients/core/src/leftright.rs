@@ -72,7 +72,7 @@ assert_eq!(*merged_data,data);
 
 use std::{marker::PhantomData, ops::Deref, sync::Arc};
 
-use interface::{Data, Left, Read, Right, UniqueIdentifier, Update, Write};
+use interface::{ClientHooks, Data, Left, Read, Right, UniqueIdentifier, Update, Write};
 
 /// Splitting state for [LeftRight] client
 pub enum Split {}
@@ -241,6 +241,14 @@ where
     V: UniqueIdentifier<DataType = Vec<T>>,
 {
 }
+impl<T, U, S, V> ClientHooks for LeftRight<U, S, V>
+where
+    T: Copy + Send + Sync,
+    S: Send + Sync,
+    U: UniqueIdentifier<DataType = Vec<T>>,
+    V: UniqueIdentifier<DataType = Vec<T>>,
+{
+}
 
 impl<T, U, V> Read<U> for LeftRight<U, Split, V>
 where
@@ -54,7 +54,7 @@ let sampler = Sampler::<Vec<f64>, MyIO>::default();
 Creating an alias to an already existing [UniqueIdentifier] (UID)
 ```
 use std::sync::Arc;
-use interface::{Data, Write, UniqueIdentifier,Size, UID, Update};
+use interface::{ClientHooks, Data, Write, UniqueIdentifier,Size, UID, Update};
 
 // Original UID
 #[derive(UID)]
@@ -62,6 +62,7 @@ use interface::{Data, Write, UniqueIdentifier,Size, UID, Update};
 pub enum A {}
 pub struct Client {}
 impl Update for Client {}
+impl ClientHooks for Client {}
 impl Write<A> for Client {
     fn write(&mut self) -> Option<Data<A>> {
         Some(Data::new(10u8))
@@ -98,8 +99,14 @@ println!(
  * `noise` : enable noisy signals
  * `serde` : enable serialization
  * `gif` : enable the gif client
+ * `fits` : enable the FITS image cube client
  * `faer` : enable matrix gain
  * `nalgebra` : enable matrix gain with [nalgebra](https://docs.rs/nalgebra) matrix input
+ * `csv` : enable signal playback from CSV files
+ * `parquet` : enable signal playback and sparse matrix gains from Parquet files
+ * `arrow` : enable the export of frequency responses to Parquet files
+ * `pickle` : enable loading sparse matrix gains from pickle files
+ * `npz` : enable loading sparse matrix gains from `scipy` npz files
 
 [Actor]: https://docs.rs/gmt_dos-actors
 [Update]: https://docs.rs/gmt_dos-actors-clients_interface/latest/gmt_dos_actors-clients_interface/struct.Update.html
@@ -109,11 +116,15 @@ println!(
 */
 
 pub use interface::Tick;
-use interface::{Data, Read, TimerMarker, UniqueIdentifier, Update, Write};
+use interface::{ClientHooks, Data, Read, TimerMarker, UniqueIdentifier, Update, Write};
 use std::mem::take;
 
 pub mod average;
+pub mod bode;
+mod fft;
 pub mod fill;
+#[cfg(feature = "fits")]
+pub mod fits;
 pub mod foh;
 pub mod fun;
 pub mod gain;
@@ -124,15 +135,20 @@ pub mod integrator;
 pub mod leftright;
 pub mod logging;
 pub mod low_pass_filter;
+pub mod lti;
+pub mod math;
 pub mod multiplex;
+pub mod nonlinear;
 pub mod once;
 pub mod operator;
+pub mod pid;
 pub mod print;
 pub mod pulse;
 pub mod sampler;
 pub mod select;
 pub mod signals;
 pub mod smooth;
+pub mod statistics;
 pub mod timer;
 
 /// Concatenates data into a [Vec]
@@ -143,6 +159,7 @@ impl<T: Default> Default for Concat<T> {
     }
 }
 impl<T> Update for Concat<T> where T: Send + Sync {}
+impl<T> ClientHooks for Concat<T> where T: Send + Sync {}
 impl<T, U> Read<U> for Concat<T>
 where
     T: Clone + Default + Send + Sync,
@@ -174,6 +191,7 @@ impl<T> Source<T> {
 }
 impl<T> TimerMarker for Source<T> {}
 impl<T> Update for Source<T> where T: Send + Sync {}
+impl<T> ClientHooks for Source<T> where T: Send + Sync {}
 
 impl<T, V> Write<V> for Source<T>
 where
@@ -1,4 +1,4 @@
-use super::{Data, Read, UniqueIdentifier, Update};
+use super::{ClientHooks, Data, Read, UniqueIdentifier, Update};
 use std::fmt::Display;
 
 /// Simple data logging
@@ -92,6 +92,7 @@ impl<T> Display for Logging<T> {
 }
 
 impl<T> Update for Logging<T> where T: Send + Sync {}
+impl<T> ClientHooks for Logging<T> where T: Send + Sync {}
 impl<T, U> Read<U> for Logging<T>
 where
     T: Clone + Send + Sync,
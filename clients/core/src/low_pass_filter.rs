@@ -5,7 +5,7 @@ use std::{
     sync::Arc,
 };
 
-use interface::{Data, Read, UniqueIdentifier, Update, Write};
+use interface::{ClientHooks, Data, Read, UniqueIdentifier, Update, Write};
 
 #[derive(Debug, Clone)]
 pub struct LowPassFilter<T> {
@@ -58,6 +58,10 @@ where
             });
     }
 }
+impl<T> ClientHooks for LowPassFilter<T> where
+    T: Send + Sync + Sub<Output = T> + Add<Output = T> + Mul<Output = T> + AddAssign + Copy
+{
+}
 
 impl<T, U> Read<U> for LowPassFilter<T>
 where
@@ -0,0 +1,624 @@
+//! # Linear time-invariant system client
+//!
+//! A discrete state-space model `(A, B, C, D)` implementing
+//! `y[k] = C x[k] + D u[k]` and `x[k+1] = A x[k] + B u[k]`.
+//!
+//! A [StateSpace] model is built either from discrete matrices,
+//! from continuous matrices or from a continuous [TransferFunction]
+//! discretized with one of the [Discretization] methods.
+//!
+//! ```
+//! use gmt_dos_clients::lti::{Discretization, TransferFunction};
+//! // 1st order low-pass filter with a 10Hz corner frequency sampled at 1kHz
+//! let w = 2. * std::f64::consts::PI * 10.;
+//! let lpf = TransferFunction::new(vec![w], vec![1., w])
+//!     .discretize(1e3, Discretization::Tustin { prewarp: Some(10.) })?;
+//! # Ok::<(), gmt_dos_clients::lti::LtiError>(())
+//! ```
+
+use std::{fmt::Display, sync::Arc};
+
+use interface::{ClientHooks, Data, Read, UniqueIdentifier, Update, Write};
+use num_complex::Complex64;
+
+#[derive(Debug, thiserror::Error)]
+pub enum LtiError {
+    #[error("state-space dimension mismatch: {0}")]
+    Dimension(String),
+    #[error("improper transfer function: numerator degree {0} > denominator degree {1}")]
+    Improper(usize, usize),
+    #[error("the denominator leading coefficient is zero")]
+    Denominator,
+    #[error("the numerator is zero")]
+    Numerator,
+    #[error("singular matrix in the discretization")]
+    Singular,
+    #[error("the matched pole-zero method only applies to transfer functions")]
+    Matched,
+}
+type Result<T> = std::result::Result<T, LtiError>;
+
+/// Continuous to discrete conversion methods
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Discretization {
+    /// Zero-order hold
+    ZeroOrderHold,
+    /// Bilinear transform with an optional prewarping frequency in Hz
+    Tustin { prewarp: Option<f64> },
+    /// Matched pole-zero mapping with the DC gains matched
+    Matched,
+}
+
+/// Dense row-major matrix
+#[derive(Debug, Clone, PartialEq)]
+struct Mat {
+    rows: usize,
+    cols: usize,
+    data: Vec<f64>,
+}
+impl Mat {
+    fn zeros(rows: usize, cols: usize) -> Self {
+        Self {
+            rows,
+            cols,
+            data: vec![0f64; rows * cols],
+        }
+    }
+    fn identity(n: usize) -> Self {
+        let mut mat = Self::zeros(n, n);
+        (0..n).for_each(|i| mat[(i, i)] = 1f64);
+        mat
+    }
+    fn from_rows(rows: Vec<Vec<f64>>, cols: usize, name: &str) -> Result<Self> {
+        if let Some(row) = rows.iter().find(|row| row.len() != cols) {
+            return Err(LtiError::Dimension(format!(
+                "{name} has a row of length {}, expected {cols}",
+                row.len()
+            )));
+        }
+        Ok(Self {
+            rows: rows.len(),
+            cols,
+            data: rows.into_iter().flatten().collect(),
+        })
+    }
+    fn matmul(&self, other: &Mat) -> Mat {
+        let mut mat = Mat::zeros(self.rows, other.cols);
+        for i in 0..self.rows {
+            for k in 0..self.cols {
+                let a = self[(i, k)];
+                for j in 0..other.cols {
+                    mat[(i, j)] += a * other[(k, j)];
+                }
+            }
+        }
+        mat
+    }
+    /// Returns `self * x` into `y`, `y` being accumulated
+    fn mul_add(&self, x: &[f64], y: &mut [f64]) {
+        self.data
+            .chunks(self.cols.max(1))
+            .zip(y.iter_mut())
+            .for_each(|(row, y)| *y += row.iter().zip(x).map(|(a, x)| a * x).sum::<f64>());
+    }
+    fn scale(mut self, s: f64) -> Mat {
+        self.data.iter_mut().for_each(|x| *x *= s);
+        self
+    }
+    fn add(mut self, other: &Mat) -> Mat {
+        self.data
+            .iter_mut()
+            .zip(&other.data)
+            .for_each(|(x, y)| *x += y);
+        self
+    }
+    fn norm_inf(&self) -> f64 {
+        self.data
+            .chunks(self.cols.max(1))
+            .map(|row| row.iter().map(|x| x.abs()).sum::<f64>())
+            .fold(0f64, f64::max)
+    }
+    /// Solves `self * X = rhs` with Gauss-Jordan elimination and partial pivoting
+    fn solve(&self, rhs: &Mat) -> Result<Mat> {
+        let n = self.rows;
+        let mut a = self.clone();
+        let mut x = rhs.clone();
+        for k in 0..n {
+            let p = (k..n)
+                .max_by(|&i, &j| a[(i, k)].abs().total_cmp(&a[(j, k)].abs()))
+                .unwrap();
+            if a[(p, k)].abs() < f64::EPSILON * a.norm_inf().max(1f64) {
+                return Err(LtiError::Singular);
+            }
+            for j in 0..n {
+                a.data.swap(k * n + j, p * n + j);
+            }
+            for j in 0..x.cols {
+                x.data.swap(k * x.cols + j, p * x.cols + j);
+            }
+            let pivot = a[(k, k)];
+            for i in (0..n).filter(|&i| i != k) {
+                let f = a[(i, k)] / pivot;
+                if f != 0f64 {
+                    for j in 0..n {
+                        a[(i, j)] -= f * a[(k, j)];
+                    }
+                    for j in 0..x.cols {
+                        x[(i, j)] -= f * x[(k, j)];
+                    }
+                }
+            }
+        }
+        for i in 0..n {
+            let pivot = a[(i, i)];
+            for j in 0..x.cols {
+                x[(i, j)] /= pivot;
+            }
+        }
+        Ok(x)
+    }
+    /// Matrix exponential with the scaling and squaring method and a (6,6) Padé approximant
+    fn expm(&self) -> Result<Mat> {
+        let n = self.rows;
+        let norm = self.norm_inf();
+        let s = if norm > 0.5 {
+            (norm / 0.5).log2().ceil() as i32
+        } else {
+            0
+        };
+        let x = self.clone().scale(2f64.powi(-s));
+        let q = 6;
+        let mut c = 1f64;
+        let mut xk = Mat::identity(n);
+        let mut num = Mat::identity(n);
+        let mut den = Mat::identity(n);
+        for k in 1..=q {
+            c *= (q - k + 1) as f64 / (k * (2 * q - k + 1)) as f64;
+            xk = xk.matmul(&x);
+            num = num.add(&xk.clone().scale(c));
+            den = den.add(&xk.clone().scale(if k % 2 == 0 { c } else { -c }));
+        }
+        let mut e = den.solve(&num)?;
+        for _ in 0..s {
+            e = e.matmul(&e);
+        }
+        Ok(e)
+    }
+    fn block(&self, rows: std::ops::Range<usize>, cols: std::ops::Range<usize>) -> Mat {
+        let mut mat = Mat::zeros(rows.len(), cols.len());
+        for (i, r) in rows.enumerate() {
+            for (j, c) in cols.clone().enumerate() {
+                mat[(i, j)] = self[(r, c)];
+            }
+        }
+        mat
+    }
+}
+impl std::ops::Index<(usize, usize)> for Mat {
+    type Output = f64;
+    fn index(&self, (i, j): (usize, usize)) -> &Self::Output {
+        &self.data[i * self.cols + j]
+    }
+}
+impl std::ops::IndexMut<(usize, usize)> for Mat {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut Self::Output {
+        &mut self.data[i * self.cols + j]
+    }
+}
+
+/// Discrete state-space model
+///
+/// The inputs of `n` independent channels are concatenated into the input vector `u`
+/// and likewise for the outputs `y`, each channel having its own state vector
+#[derive(Debug, Clone)]
+pub struct StateSpace {
+    a: Mat,
+    b: Mat,
+    c: Mat,
+    d: Mat,
+    x: Vec<Vec<f64>>,
+    u: Arc<Vec<f64>>,
+    y: Vec<f64>,
+}
+impl Display for StateSpace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "state-space: states={}, inputs={}, outputs={}, channels={}",
+            self.a.rows,
+            self.b.cols,
+            self.c.rows,
+            self.x.len()
+        )
+    }
+}
+impl StateSpace {
+    /// Creates a new discrete state-space model from the rows of the matrices `A`, `B`, `C` and `D`
+    pub fn new(
+        a: Vec<Vec<f64>>,
+        b: Vec<Vec<f64>>,
+        c: Vec<Vec<f64>>,
+        d: Vec<Vec<f64>>,
+    ) -> Result<Self> {
+        let n = a.len();
+        let m = d
+            .first()
+            .map(|row| row.len())
+            .or_else(|| b.first().map(|row| row.len()))
+            .unwrap_or_default();
+        let a = Mat::from_rows(a, n, "A")?;
+        let b = Mat::from_rows(b, m, "B")?;
+        let c = Mat::from_rows(c, n, "C")?;
+        let d = Mat::from_rows(d, m, "D")?;
+        Self::from_mat(a, b, c, d)
+    }
+    /// Creates a new state-space model from continuous matrices `A`, `B`, `C` and `D`
+    /// discretized at the given sampling frequency in Hz
+    ///
+    /// The [Matched](Discretization::Matched) method is only available for [TransferFunction]s
+    pub fn from_continuous(
+        a: Vec<Vec<f64>>,
+        b: Vec<Vec<f64>>,
+        c: Vec<Vec<f64>>,
+        d: Vec<Vec<f64>>,
+        sampling_frequency: f64,
+        method: Discretization,
+    ) -> Result<Self> {
+        let StateSpace { a, b, c, d, .. } = Self::new(a, b, c, d)?;
+        let (a, b, c, d) = match method {
+            Discretization::ZeroOrderHold => zoh(a, b, c, d, sampling_frequency.recip())?,
+            Discretization::Tustin { prewarp } => tustin(a, b, c, d, sampling_frequency, prewarp)?,
+            Discretization::Matched => return Err(LtiError::Matched),
+        };
+        Self::from_mat(a, b, c, d)
+    }
+    fn from_mat(a: Mat, b: Mat, c: Mat, d: Mat) -> Result<Self> {
+        let n = a.rows;
+        if a.cols != n || b.rows != n || c.cols != n || c.rows != d.rows || b.cols != d.cols {
+            return Err(LtiError::Dimension(format!(
+                "A: {}x{}, B: {}x{}, C: {}x{}, D: {}x{}",
+                a.rows, a.cols, b.rows, b.cols, c.rows, c.cols, d.rows, d.cols
+            )));
+        }
+        Ok(Self {
+            x: vec![vec![0f64; n]],
+            u: Arc::new(vec![0f64; d.cols]),
+            y: vec![0f64; d.rows],
+            a,
+            b,
+            c,
+            d,
+        })
+    }
+    /// Sets the number of independent channels (default: 1)
+    pub fn channels(mut self, n: usize) -> Self {
+        self.x = vec![vec![0f64; self.a.rows]; n];
+        self.u = Arc::new(vec![0f64; self.d.cols * n]);
+        self.y = vec![0f64; self.d.rows * n];
+        self
+    }
+    /// Resets the states to zero
+    pub fn reset(&mut self) {
+        self.x.iter_mut().flatten().for_each(|x| *x = 0f64);
+    }
+    /// Returns the number of states, inputs and outputs of a channel
+    pub fn dims(&self) -> (usize, usize, usize) {
+        (self.a.rows, self.d.cols, self.d.rows)
+    }
+}
+
+/// Continuous transfer function
+///
+/// The numerator and denominator polynomials coefficients are given in descending powers of `s`
+#[derive(Debug, Clone)]
+pub struct TransferFunction {
+    num: Vec<f64>,
+    den: Vec<f64>,
+}
+impl TransferFunction {
+    /// Creates a new transfer function from the numerator and denominator polynomials
+    pub fn new(num: Vec<f64>, den: Vec<f64>) -> Self {
+        Self { num, den }
+    }
+    /// Discretizes the transfer function at the given sampling frequency in Hz
+    pub fn discretize(self, sampling_frequency: f64, method: Discretization) -> Result<StateSpace> {
+        let num = trim_leading(&self.num);
+        let den = trim_leading(&self.den);
+        if den.is_empty() {
+            return Err(LtiError::Denominator);
+        }
+        if num.is_empty() {
+            return Err(LtiError::Numerator);
+        }
+        if num.len() > den.len() {
+            return Err(LtiError::Improper(num.len() - 1, den.len() - 1));
+        }
+        let (a, b, c, d) = match method {
+            Discretization::Matched => {
+                let (num, den) = matched(num, den, sampling_frequency.recip());
+                canonical(&num, &den)
+            }
+            Discretization::ZeroOrderHold => {
+                let (a, b, c, d) = canonical(num, den);
+                zoh(a, b, c, d, sampling_frequency.recip())?
+            }
+            Discretization::Tustin { prewarp } => {
+                let (a, b, c, d) = canonical(num, den);
+                tustin(a, b, c, d, sampling_frequency, prewarp)?
+            }
+        };
+        StateSpace::from_mat(a, b, c, d)
+    }
+}
+impl TryFrom<(TransferFunction, f64, Discretization)> for StateSpace {
+    type Error = LtiError;
+    fn try_from(
+        (tf, sampling_frequency, method): (TransferFunction, f64, Discretization),
+    ) -> Result<Self> {
+        tf.discretize(sampling_frequency, method)
+    }
+}
+
+fn trim_leading(poly: &[f64]) -> &[f64] {
+    let i = poly.iter().position(|x| *x != 0f64).unwrap_or(poly.len());
+    &poly[i..]
+}
+
+/// Controllable canonical realization of a proper transfer function
+fn canonical(num: &[f64], den: &[f64]) -> (Mat, Mat, Mat, Mat) {
+    let n = den.len() - 1;
+    let a0 = den[0];
+    let den: Vec<_> = den.iter().map(|x| x / a0).collect();
+    let mut b = vec![0f64; n + 1 - num.len()];
+    b.extend(num.iter().map(|x| x / a0));
+    let mut a = Mat::zeros(n, n);
+    (0..n).for_each(|j| a[(0, j)] = -den[j + 1]);
+    (1..n).for_each(|i| a[(i, i - 1)] = 1f64);
+    let mut bm = Mat::zeros(n, 1);
+    if n > 0 {
+        bm[(0, 0)] = 1f64;
+    }
+    let c = Mat {
+        rows: 1,
+        cols: n,
+        data: (0..n).map(|j| b[j + 1] - den[j + 1] * b[0]).collect(),
+    };
+    let d = Mat {
+        rows: 1,
+        cols: 1,
+        data: vec![b[0]],
+    };
+    (a, bm, c, d)
+}
+
+fn zoh(a: Mat, b: Mat, c: Mat, d: Mat, ts: f64) -> Result<(Mat, Mat, Mat, Mat)> {
+    let (n, m) = (a.rows, b.cols);
+    let mut em = Mat::zeros(n + m, n + m);
+    for i in 0..n {
+        for j in 0..n {
+            em[(i, j)] = a[(i, j)] * ts;
+        }
+        for j in 0..m {
+            em[(i, n + j)] = b[(i, j)] * ts;
+        }
+    }
+    let e = em.expm()?;
+    Ok((e.block(0..n, 0..n), e.block(0..n, n..n + m), c, d))
+}
+
+fn tustin(
+    a: Mat,
+    b: Mat,
+    c: Mat,
+    d: Mat,
+    sampling_frequency: f64,
+    prewarp: Option<f64>,
+) -> Result<(Mat, Mat, Mat, Mat)> {
+    let ts = match prewarp {
+        Some(f) => {
+            let w = 2. * std::f64::consts::PI * f;
+            2. * (0.5 * w / sampling_frequency).tan() / w
+        }
+        None => sampling_frequency.recip(),
+    };
+    let n = a.rows;
+    let ima = Mat::identity(n).add(&a.clone().scale(-0.5 * ts));
+    let ipa = Mat::identity(n).add(&a.scale(0.5 * ts));
+    let ad = ima.solve(&ipa)?;
+    let bd = ima.solve(&b.scale(ts))?;
+    let m = ima.solve(&Mat::identity(n))?;
+    let dd = d.add(&c.matmul(&bd).scale(0.5));
+    let cd = c.matmul(&m);
+    Ok((ad, bd, cd, dd))
+}
+
+/// Matched pole-zero mapping of a continuous transfer function
+///
+/// Returns the discrete numerator and denominator in descending powers of `z`
+fn matched(num: &[f64], den: &[f64], ts: f64) -> (Vec<f64>, Vec<f64>) {
+    // roots at s=0 are mapped exactly to z=1
+    let strip = |poly: &[f64]| {
+        let k = poly.iter().rev().take_while(|x| **x == 0f64).count();
+        (poly[..poly.len() - k].to_vec(), k)
+    };
+    let (num_r, n_zero) = strip(num);
+    let (den_r, n_pole) = strip(den);
+    let map = |poly: &[f64], k: usize| {
+        let mut roots: Vec<_> = roots(poly).into_iter().map(|r| (r * ts).exp()).collect();
+        roots.extend(vec![Complex64::new(1., 0.); k]);
+        roots
+    };
+    let mut zeros = map(&num_r, 0);
+    // zeros at infinity are mapped to z=-1 but one
+    let n_inf = (den.len() - num.len()).saturating_sub(1);
+    zeros.extend(vec![Complex64::new(-1., 0.); n_inf]);
+    let poles = map(&den_r, 0);
+    // DC gain of the transfer functions without the roots at s=0 and z=1
+    let dc_c = num_r[num_r.len() - 1] / den_r[den_r.len() - 1];
+    let eval = |roots: &[Complex64]| {
+        roots
+            .iter()
+            .map(|r| Complex64::new(1., 0.) - r)
+            .product::<Complex64>()
+            .re
+    };
+    let gain = dc_c * eval(&poles) / eval(&zeros) * ts.powi(n_pole as i32 - n_zero as i32);
+    zeros.extend(vec![Complex64::new(1., 0.); n_zero]);
+    let poles: Vec<_> = poles
+        .into_iter()
+        .chain(vec![Complex64::new(1., 0.); n_pole])
+        .collect();
+    let num: Vec<_> = poly(&zeros).into_iter().map(|x| gain * x).collect();
+    (num, poly(&poles))
+}
+
+/// Roots of a polynomial with the Durand-Kerner method
+fn roots(poly: &[f64]) -> Vec<Complex64> {
+    let n = poly.len().saturating_sub(1);
+    if n == 0 {
+        return vec![];
+    }
+    let coefs: Vec<_> = poly
+        .iter()
+        .map(|x| Complex64::new(x / poly[0], 0.))
+        .collect();
+    let eval = |z: Complex64| coefs.iter().fold(Complex64::new(0., 0.), |p, c| p * z + c);
+    let seed = Complex64::new(0.4, 0.9);
+    let mut roots: Vec<_> = (0..n as i32).map(|k| seed.powi(k)).collect();
+    for _ in 0..1000 {
+        let mut delta = 0f64;
+        for i in 0..n {
+            let denom = (0..n)
+                .filter(|&j| j != i)
+                .map(|j| roots[i] - roots[j])
+                .product::<Complex64>();
+            let step = eval(roots[i]) / denom;
+            roots[i] -= step;
+            delta = delta.max(step.norm());
+        }
+        if delta < 1e-14 {
+            break;
+        }
+    }
+    roots
+}
+
+/// Real polynomial coefficients in descending powers from its roots
+fn poly(roots: &[Complex64]) -> Vec<f64> {
+    roots
+        .iter()
+        .fold(vec![Complex64::new(1., 0.)], |p, r| {
+            let mut q = p.clone();
+            q.push(Complex64::new(0., 0.));
+            p.iter().enumerate().for_each(|(i, c)| q[i + 1] -= c * r);
+            q
+        })
+        .into_iter()
+        .map(|c| c.re)
+        .collect()
+}
+
+impl Update for StateSpace {
+    fn update(&mut self) {
+        let (m, p) = (self.d.cols, self.d.rows);
+        for (k, x) in self.x.iter_mut().enumerate() {
+            let u = &self.u[k * m..(k + 1) * m];
+            let y = &mut self.y[k * p..(k + 1) * p];
+            y.iter_mut().for_each(|y| *y = 0f64);
+            self.c.mul_add(x, y);
+            self.d.mul_add(u, y);
+            let mut x_next = vec![0f64; x.len()];
+            self.a.mul_add(x, &mut x_next);
+            self.b.mul_add(u, &mut x_next);
+            *x = x_next;
+        }
+    }
+}
+impl ClientHooks for StateSpace {}
+
+impl<U> Read<U> for StateSpace
+where
+    U: UniqueIdentifier<DataType = Vec<f64>>,
+{
+    fn read(&mut self, data: Data<U>) {
+        self.u = data.into_arc();
+        assert_eq!(
+            self.u.len(),
+            self.d.cols * self.x.len(),
+            "gmt_dos-clients::StateSpace input size error:\nexpected {}, found {}!",
+            self.d.cols * self.x.len(),
+            self.u.len()
+        );
+    }
+}
+
+impl<U> Write<U> for StateSpace
+where
+    U: UniqueIdentifier<DataType = Vec<f64>>,
+{
+    fn write(&mut self) -> Option<Data<U>> {
+        Some(self.y.clone().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    enum U {}
+    impl UniqueIdentifier for U {
+        type DataType = Vec<f64>;
+    }
+
+    fn step(mut ss: StateSpace, n: usize) -> Vec<f64> {
+        (0..n)
+            .map(|_| {
+                <StateSpace as Read<U>>::read(&mut ss, Data::new(vec![1.]));
+                ss.update();
+                <StateSpace as Write<U>>::write(&mut ss).unwrap()[0]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn first_order() -> Result<()> {
+        let tf = TransferFunction::new(vec![1.], vec![1., 1.]);
+        let fs = 10.;
+        let y = step(
+            tf.clone().discretize(fs, Discretization::ZeroOrderHold)?,
+            50,
+        );
+        y.iter()
+            .enumerate()
+            .for_each(|(k, y)| assert!((y - (1. - (-(k as f64) / fs).exp())).abs() < 1e-12));
+        for method in [
+            Discretization::Tustin { prewarp: None },
+            Discretization::Tustin { prewarp: Some(1.) },
+            Discretization::Matched,
+        ] {
+            let y = step(tf.clone().discretize(fs, method)?, 500);
+            assert!((y[499] - 1.).abs() < 1e-9, "{method:?}: {}", y[499]);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn matched_integrator() -> Result<()> {
+        // 1/(s(s+1)) ramps with a unit slope after the transient
+        let tf = TransferFunction::new(vec![1.], vec![1., 1., 0.]);
+        let fs = 100.;
+        let y = step(tf.discretize(fs, Discretization::Matched)?, 2001);
+        let slope = (y[2000] - y[1900]) * fs / 100.;
+        assert!((slope - 1.).abs() < 1e-6, "{slope}");
+        Ok(())
+    }
+
+    #[test]
+    fn zero_numerator() {
+        let tf = TransferFunction::new(vec![0.], vec![1., 1.]);
+        assert!(matches!(
+            tf.discretize(10., Discretization::Matched),
+            Err(LtiError::Numerator)
+        ));
+    }
+}
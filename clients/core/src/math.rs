@@ -0,0 +1,297 @@
+/*!
+# Math expression
+
+A client that evaluates a math expression over several inputs and writes the result to its output.
+
+The expression is parsed once when the client is created and supports:
+ - element-wise arithmetic: `+`, `-`, `*`, `/` and `^`, scalars are broadcasted to vectors,
+ - slicing: `c[3]`, `c[3..9]`, `c[..9]` or `c[3..]`,
+ - concatenation: `[a, b[..2], 1]` or `concat(a, b)`,
+ - reductions: `sum`, `mean`, `norm`, `min` and `max`,
+ - element-wise functions: `abs`, `sqrt`, `exp`, `ln`, `sin`, `cos` and `tan`.
+
+Each variable of the expression is bound to an input UID.
+The expression may be prefixed with the name of the output (`y = ...`).
+The [Display] implementation gives a readable label of the expression,
+[Math::named] pairs the client with this label to name the actor the client belongs to.
+An expression that cannot be evaluated with the inputs, or an input that is not bound
+to any variable, is reported as a [MathError] by the actor.
+
+# Example
+
+```
+use gmt_dos_clients::math::Math;
+use interface::UID;
+
+#[derive(UID)]
+enum A {}
+#[derive(UID)]
+enum B {}
+#[derive(UID)]
+enum C {}
+
+let math = Math::new("y = 0.5*(a+b) - c[3..9]")?
+    .bind::<A>("a")?
+    .bind::<B>("b")?
+    .bind::<C>("c")?;
+assert_eq!(math.to_string(), "y = 0.5*(a + b) - c[3..9]");
+# Ok::<(), gmt_dos_clients::math::MathError>(())
+```
+The expression can also be written without quotes with the [math](crate::math!) macro
+```
+use gmt_dos_clients::math;
+let math = math!(y = sum(abs(a)) / 2)?;
+# Ok::<(), gmt_dos_clients::math::MathError>(())
+```
+*/
+
+use std::{any::TypeId, fmt::Display, str::FromStr, sync::Arc};
+
+use interface::{ClientHooks, Data, TryRead, TryUpdate, TryWrite, UniqueIdentifier};
+
+mod expr;
+use expr::Parser;
+pub use expr::{BinaryOp, Expr, Function, Slice};
+
+#[derive(Debug, thiserror::Error)]
+pub enum MathError {
+    #[error("failed to parse the expression at {0}: {1}")]
+    Parse(usize, String),
+    #[error("unknown function: {0}")]
+    Function(String),
+    #[error("function {0} called with {1} arguments instead of 1")]
+    Arguments(String, usize),
+    #[error("unknown variable: {0}")]
+    Variable(String),
+    #[error("variable {0} is not bound to an input")]
+    Unbound(String),
+    #[error("no data for variable {0}")]
+    NoData(String),
+    #[error("size mismatch in {0}: {1} vs {2}")]
+    Size(String, usize, usize),
+    #[error("slice {0} out of bounds of vector of size {1}")]
+    Slice(String, usize),
+    #[error("input {0} is not bound to any variable of {1}")]
+    Input(String, String),
+    #[error("failed to evaluate {0}")]
+    Eval(String, #[source] Box<MathError>),
+}
+pub type Result<T> = std::result::Result<T, MathError>;
+
+/// Creates a [Math] client from an unquoted expression
+#[macro_export]
+macro_rules! math {
+    ($($expr:tt)+) => {
+        $crate::math::Math::new(stringify!($($expr)+))
+    };
+}
+
+/// Expression variable
+#[derive(Debug)]
+struct Variable {
+    name: String,
+    uid: Option<TypeId>,
+    data: Option<Arc<Vec<f64>>>,
+}
+
+/// Math expression client
+#[derive(Debug)]
+pub struct Math {
+    name: Option<String>,
+    expr: Expr,
+    variables: Vec<Variable>,
+    y: Arc<Vec<f64>>,
+}
+impl Math {
+    /// Parses the expression
+    pub fn new<S: AsRef<str>>(expression: S) -> Result<Self> {
+        let mut parser = Parser::new(expression.as_ref())?;
+        let (name, expr) = parser.parse()?;
+        Ok(Self {
+            name,
+            expr,
+            variables: parser
+                .variables
+                .into_iter()
+                .map(|name| Variable {
+                    name,
+                    uid: None,
+                    data: None,
+                })
+                .collect(),
+            y: Default::default(),
+        })
+    }
+    /// Binds the expression variable `name` to the input `U`
+    pub fn bind<U: UniqueIdentifier + 'static>(mut self, name: &str) -> Result<Self> {
+        let variable = self
+            .variables
+            .iter_mut()
+            .find(|variable| variable.name == name)
+            .ok_or_else(|| MathError::Variable(name.to_string()))?;
+        variable.uid = Some(TypeId::of::<U>());
+        Ok(self)
+    }
+    /// Returns the parsed expression
+    pub fn expr(&self) -> &Expr {
+        &self.expr
+    }
+    /// Returns the names of the expression variables
+    pub fn variables(&self) -> Vec<&str> {
+        self.variables
+            .iter()
+            .map(|variable| variable.name.as_str())
+            .collect()
+    }
+    /// Returns the client paired with the label of the expression
+    ///
+    /// The pair converts into an actor named after the expression
+    /// ```
+    /// use gmt_dos_actors::prelude::*;
+    /// use gmt_dos_clients::math::Math;
+    /// use interface::Who;
+    ///
+    /// let actor: Actor<_> = Math::new("y = sum(a)")?.named().into();
+    /// assert_eq!(actor.who(), "y = sum(a)");
+    /// # Ok::<(), gmt_dos_clients::math::MathError>(())
+    /// ```
+    pub fn named(self) -> (Self, String) {
+        let name = self.to_string();
+        (self, name)
+    }
+    /// Evaluates the expression with the last inputs
+    pub fn eval(&self) -> Result<Vec<f64>> {
+        let vars = self
+            .variables
+            .iter()
+            .map(|variable| {
+                if variable.uid.is_none() {
+                    return Err(MathError::Unbound(variable.name.clone()));
+                }
+                variable
+                    .data
+                    .as_deref()
+                    .map(|data| data.as_slice())
+                    .ok_or_else(|| MathError::NoData(variable.name.clone()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        self.expr.eval(&vars)
+    }
+}
+impl FromStr for Math {
+    type Err = MathError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::new(s)
+    }
+}
+impl Display for Math {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(name) = &self.name {
+            write!(f, "{name} = ")?;
+        }
+        write!(f, "{}", self.expr)
+    }
+}
+
+impl TryUpdate for Math {
+    type Error = MathError;
+
+    fn try_update(&mut self) -> Result<&mut Self> {
+        self.y = Arc::new(
+            self.eval()
+                .map_err(|e| MathError::Eval(format!(r#""{self}""#), Box::new(e)))?,
+        );
+        Ok(self)
+    }
+}
+impl ClientHooks for Math {}
+impl<U> TryRead<U> for Math
+where
+    U: UniqueIdentifier<DataType = Vec<f64>> + 'static,
+{
+    type Error = MathError;
+
+    fn try_read(&mut self, data: Data<U>) -> Result<&mut Self> {
+        let uid = Some(TypeId::of::<U>());
+        let mut bound = false;
+        for variable in self.variables.iter_mut().filter(|v| v.uid == uid) {
+            variable.data = Some(data.as_arc());
+            bound = true;
+        }
+        if !bound {
+            return Err(MathError::Input(
+                std::any::type_name::<U>().to_string(),
+                format!(r#""{self}""#),
+            ));
+        }
+        Ok(self)
+    }
+}
+impl<V> TryWrite<V> for Math
+where
+    V: UniqueIdentifier<DataType = Vec<f64>>,
+{
+    type Error = MathError;
+
+    fn try_write(&mut self) -> Result<Option<Data<V>>> {
+        Ok(Some((&self.y).into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use interface::UID;
+
+    #[derive(UID)]
+    enum A {}
+    #[derive(UID)]
+    enum B {}
+    #[derive(UID)]
+    enum Y {}
+
+    #[test]
+    fn parse() {
+        for (expression, label) in [
+            ("y = 0.5*(a+b) - c[3..9]", "y = 0.5*(a + b) - c[3..9]"),
+            ("-a^2 + (b - c) - d", "-a^2 + (b - c) - d"),
+            ("a - (b - c)", "a - (b - c)"),
+            ("[a, b[..2], 1e-3]*2", "[a, b[..2], 0.001]*2"),
+            ("norm(a[1]-b)/sum(c)", "norm(a[1] - b)/sum(c)"),
+        ] {
+            assert_eq!(Math::new(expression).unwrap().to_string(), label);
+        }
+        assert!(matches!(Math::new("a +"), Err(MathError::Parse(3, _))));
+        assert!(matches!(Math::new("foo(a)"), Err(MathError::Function(_))));
+        assert!(matches!(
+            math!(a * b).unwrap().bind::<A>("c"),
+            Err(MathError::Variable(_))
+        ));
+    }
+
+    #[test]
+    fn eval() {
+        let mut math = Math::new("y = 0.5*(a+b) - [sum(a), mean(b[1..]), norm(a)]")
+            .unwrap()
+            .bind::<A>("a")
+            .unwrap()
+            .bind::<B>("b")
+            .unwrap();
+        <Math as TryRead<A>>::try_read(&mut math, Data::new(vec![3., 4., 0.])).unwrap();
+        <Math as TryRead<B>>::try_read(&mut math, Data::new(vec![1., 2., 4.])).unwrap();
+        math.try_update().unwrap();
+        let y = <Math as TryWrite<Y>>::try_write(&mut math)
+            .unwrap()
+            .unwrap();
+        assert_eq!(*y, vec![2. - 7., 3. - 3., 2. - 5.]);
+        assert!(matches!(
+            <Math as TryRead<Y>>::try_read(&mut math, Data::new(vec![0.])),
+            Err(MathError::Input(..))
+        ));
+        <Math as TryRead<B>>::try_read(&mut math, Data::new(vec![1., 2.])).unwrap();
+        assert!(matches!(math.try_update(), Err(MathError::Eval(..))));
+        let (math, name) = math.named();
+        assert_eq!(name, math.to_string());
+    }
+}
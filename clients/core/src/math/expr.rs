@@ -0,0 +1,527 @@
+use std::{fmt::Display, ops::Range};
+
+use super::{MathError, Result};
+
+/// Element-wise binary operators
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Pow,
+}
+impl BinaryOp {
+    fn precedence(&self) -> u8 {
+        match self {
+            BinaryOp::Add | BinaryOp::Sub => 1,
+            BinaryOp::Mul | BinaryOp::Div => 2,
+            BinaryOp::Pow => 4,
+        }
+    }
+    fn apply(&self, a: f64, b: f64) -> f64 {
+        match self {
+            BinaryOp::Add => a + b,
+            BinaryOp::Sub => a - b,
+            BinaryOp::Mul => a * b,
+            BinaryOp::Div => a / b,
+            BinaryOp::Pow => a.powf(b),
+        }
+    }
+}
+impl Display for BinaryOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let op = match self {
+            BinaryOp::Add => "+",
+            BinaryOp::Sub => "-",
+            BinaryOp::Mul => "*",
+            BinaryOp::Div => "/",
+            BinaryOp::Pow => "^",
+        };
+        write!(f, "{op}")
+    }
+}
+
+/// Functions
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Function {
+    // reductions
+    Sum,
+    Mean,
+    Norm,
+    Min,
+    Max,
+    // element-wise
+    Abs,
+    Sqrt,
+    Exp,
+    Ln,
+    Sin,
+    Cos,
+    Tan,
+    // concatenation
+    Concat,
+}
+impl Function {
+    const NAMES: [(&'static str, Function); 13] = [
+        ("sum", Function::Sum),
+        ("mean", Function::Mean),
+        ("norm", Function::Norm),
+        ("min", Function::Min),
+        ("max", Function::Max),
+        ("abs", Function::Abs),
+        ("sqrt", Function::Sqrt),
+        ("exp", Function::Exp),
+        ("ln", Function::Ln),
+        ("sin", Function::Sin),
+        ("cos", Function::Cos),
+        ("tan", Function::Tan),
+        ("concat", Function::Concat),
+    ];
+    fn from_name(name: &str) -> Option<Self> {
+        Self::NAMES
+            .iter()
+            .find_map(|(n, f)| (*n == name).then_some(*f))
+    }
+    fn name(&self) -> &'static str {
+        Self::NAMES
+            .iter()
+            .find_map(|(n, f)| (f == self).then_some(*n))
+            .unwrap()
+    }
+    fn apply(&self, mut args: Vec<Vec<f64>>) -> Result<Vec<f64>> {
+        if *self == Function::Concat {
+            return Ok(args.concat());
+        }
+        if args.len() != 1 {
+            return Err(MathError::Arguments(self.name().to_string(), args.len()));
+        }
+        let mut x = args.remove(0);
+        let reduce = |x: &[f64], f: fn(f64, f64) -> f64| x.iter().copied().reduce(f);
+        Ok(match self {
+            Function::Sum => vec![x.iter().sum()],
+            Function::Mean => vec![x.iter().sum::<f64>() / x.len() as f64],
+            Function::Norm => vec![x.iter().map(|x| x * x).sum::<f64>().sqrt()],
+            Function::Min => vec![reduce(&x, f64::min).unwrap_or(f64::NAN)],
+            Function::Max => vec![reduce(&x, f64::max).unwrap_or(f64::NAN)],
+            _ => {
+                let f: fn(f64) -> f64 = match self {
+                    Function::Abs => f64::abs,
+                    Function::Sqrt => f64::sqrt,
+                    Function::Exp => f64::exp,
+                    Function::Ln => f64::ln,
+                    Function::Sin => f64::sin,
+                    Function::Cos => f64::cos,
+                    _ => f64::tan,
+                };
+                x.iter_mut().for_each(|x| *x = f(*x));
+                x
+            }
+        })
+    }
+}
+
+/// Vector slice
+#[derive(Debug, Clone, PartialEq)]
+pub enum Slice {
+    Index(usize),
+    Range(Option<usize>, Option<usize>),
+}
+impl Slice {
+    fn range(&self, n: usize) -> Result<Range<usize>> {
+        let range = match *self {
+            Slice::Index(i) => i..i + 1,
+            Slice::Range(start, end) => start.unwrap_or(0)..end.unwrap_or(n),
+        };
+        if range.start > range.end || range.end > n {
+            Err(MathError::Slice(self.to_string(), n))
+        } else {
+            Ok(range)
+        }
+    }
+}
+impl Display for Slice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Slice::Index(i) => write!(f, "[{i}]"),
+            Slice::Range(start, end) => {
+                write!(f, "[")?;
+                if let Some(start) = start {
+                    write!(f, "{start}")?;
+                }
+                write!(f, "..")?;
+                if let Some(end) = end {
+                    write!(f, "{end}")?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+/// Expression abstract syntax tree
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Number(f64),
+    /// Variable name and index into the variable list
+    Variable(String, usize),
+    Neg(Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+    Call(Function, Vec<Expr>),
+    Slice(Box<Expr>, Slice),
+    Concat(Vec<Expr>),
+}
+impl Expr {
+    fn precedence(&self) -> u8 {
+        match self {
+            Expr::Binary(op, _, _) => op.precedence(),
+            Expr::Neg(_) => 3,
+            _ => 5,
+        }
+    }
+    /// Evaluates the expression for the given variable values
+    pub fn eval(&self, vars: &[&[f64]]) -> Result<Vec<f64>> {
+        Ok(match self {
+            Expr::Number(x) => vec![*x],
+            Expr::Variable(_, i) => vars[*i].to_vec(),
+            Expr::Neg(x) => x.eval(vars)?.into_iter().map(|x| -x).collect(),
+            Expr::Binary(op, a, b) => {
+                let a = a.eval(vars)?;
+                let b = b.eval(vars)?;
+                match (a.len(), b.len()) {
+                    (1, _) => b.iter().map(|&b| op.apply(a[0], b)).collect(),
+                    (_, 1) => a.iter().map(|&a| op.apply(a, b[0])).collect(),
+                    (n, m) if n == m => a.iter().zip(&b).map(|(&a, &b)| op.apply(a, b)).collect(),
+                    (n, m) => return Err(MathError::Size(self.to_string(), n, m)),
+                }
+            }
+            Expr::Call(f, args) => f.apply(
+                args.iter()
+                    .map(|arg| arg.eval(vars))
+                    .collect::<Result<Vec<_>>>()?,
+            )?,
+            Expr::Slice(x, slice) => {
+                let x = x.eval(vars)?;
+                x[slice.range(x.len())?].to_vec()
+            }
+            Expr::Concat(items) => items
+                .iter()
+                .map(|item| item.eval(vars))
+                .collect::<Result<Vec<_>>>()?
+                .concat(),
+        })
+    }
+    fn fmt_child(&self, f: &mut std::fmt::Formatter<'_>, parent: u8) -> std::fmt::Result {
+        if self.precedence() < parent {
+            write!(f, "({self})")
+        } else {
+            write!(f, "{self}")
+        }
+    }
+}
+impl Display for Expr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let join = |f: &mut std::fmt::Formatter<'_>, items: &[Expr]| {
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{item}")?;
+            }
+            Ok(())
+        };
+        match self {
+            Expr::Number(x) => write!(f, "{x}"),
+            Expr::Variable(name, _) => write!(f, "{name}"),
+            Expr::Neg(x) => {
+                write!(f, "-")?;
+                x.fmt_child(f, self.precedence())
+            }
+            Expr::Binary(op, a, b) => {
+                let p = op.precedence();
+                // left associative operators but the power
+                let (pa, pb) = if *op == BinaryOp::Pow {
+                    (p + 1, p)
+                } else {
+                    (p, p + 1)
+                };
+                a.fmt_child(f, pa)?;
+                match op {
+                    BinaryOp::Add | BinaryOp::Sub => write!(f, " {op} ")?,
+                    _ => write!(f, "{op}")?,
+                }
+                b.fmt_child(f, pb)
+            }
+            Expr::Call(function, args) => {
+                write!(f, "{}(", function.name())?;
+                join(f, args)?;
+                write!(f, ")")
+            }
+            Expr::Slice(x, slice) => {
+                x.fmt_child(f, 5)?;
+                write!(f, "{slice}")
+            }
+            Expr::Concat(items) => {
+                write!(f, "[")?;
+                join(f, items)?;
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Ident(String),
+    Symbol(&'static str),
+}
+impl Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Number(x) => write!(f, "{x}"),
+            Token::Ident(x) => write!(f, "{x}"),
+            Token::Symbol(x) => write!(f, "{x}"),
+        }
+    }
+}
+
+const SYMBOLS: [&str; 12] = ["..", "+", "-", "*", "/", "^", "(", ")", "[", "]", ",", "="];
+
+fn tokenize(expression: &str) -> Result<Vec<(usize, Token)>> {
+    let mut tokens = vec![];
+    let chars: Vec<char> = expression.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit()
+            || (c == '.' && chars.get(i + 1).is_some_and(|c| c.is_ascii_digit()))
+        {
+            let start = i;
+            while i < chars.len() {
+                let c = chars[i];
+                let exponent_sign = (c == '-' || c == '+') && matches!(chars[i - 1], 'e' | 'E');
+                // stops at a range operator
+                let range = c == '.' && chars.get(i + 1) == Some(&'.');
+                if range
+                    || !(c.is_ascii_digit() || c == '.' || c == 'e' || c == 'E' || exponent_sign)
+                {
+                    break;
+                }
+                i += 1;
+            }
+            let number: String = chars[start..i].iter().collect();
+            let value = number
+                .parse::<f64>()
+                .map_err(|_| MathError::Parse(start, format!("invalid number {number}")))?;
+            tokens.push((start, Token::Number(value)));
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push((start, Token::Ident(chars[start..i].iter().collect())));
+        } else {
+            let rest: String = chars[i..].iter().take(2).collect();
+            let Some(symbol) = SYMBOLS.iter().find(|s| rest.starts_with(*s)) else {
+                return Err(MathError::Parse(i, format!("unexpected character {c}")));
+            };
+            tokens.push((i, Token::Symbol(symbol)));
+            i += symbol.len();
+        }
+    }
+    Ok(tokens)
+}
+
+/// Recursive descent parser
+pub(super) struct Parser {
+    tokens: Vec<(usize, Token)>,
+    pos: usize,
+    len: usize,
+    pub(super) variables: Vec<String>,
+}
+impl Parser {
+    pub(super) fn new(expression: &str) -> Result<Self> {
+        Ok(Self {
+            tokens: tokenize(expression)?,
+            pos: 0,
+            len: expression.len(),
+            variables: vec![],
+        })
+    }
+    /// Parses `[name =] expression`
+    pub(super) fn parse(&mut self) -> Result<(Option<String>, Expr)> {
+        let name = match (self.tokens.first(), self.tokens.get(1)) {
+            (Some((_, Token::Ident(name))), Some((_, Token::Symbol("=")))) => {
+                let name = name.clone();
+                self.pos = 2;
+                Some(name)
+            }
+            _ => None,
+        };
+        let expr = self.expr()?;
+        match self.tokens.get(self.pos) {
+            Some((i, token)) => Err(MathError::Parse(*i, format!("unexpected {token}"))),
+            None => Ok((name, expr)),
+        }
+    }
+    fn offset(&self) -> usize {
+        self.tokens.get(self.pos).map_or(self.len, |(i, _)| *i)
+    }
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(_, token)| token)
+    }
+    fn eat(&mut self, symbol: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Symbol(s)) if *s == symbol) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+    fn expect(&mut self, symbol: &str) -> Result<()> {
+        if self.eat(symbol) {
+            Ok(())
+        } else {
+            Err(MathError::Parse(
+                self.offset(),
+                format!("expected {symbol}"),
+            ))
+        }
+    }
+    fn expr(&mut self) -> Result<Expr> {
+        let mut lhs = self.term()?;
+        loop {
+            let op = if self.eat("+") {
+                BinaryOp::Add
+            } else if self.eat("-") {
+                BinaryOp::Sub
+            } else {
+                return Ok(lhs);
+            };
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(self.term()?));
+        }
+    }
+    fn term(&mut self) -> Result<Expr> {
+        let mut lhs = self.unary()?;
+        loop {
+            let op = if self.eat("*") {
+                BinaryOp::Mul
+            } else if self.eat("/") {
+                BinaryOp::Div
+            } else {
+                return Ok(lhs);
+            };
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(self.unary()?));
+        }
+    }
+    fn unary(&mut self) -> Result<Expr> {
+        if self.eat("-") {
+            Ok(Expr::Neg(Box::new(self.unary()?)))
+        } else if self.eat("+") {
+            self.unary()
+        } else {
+            self.power()
+        }
+    }
+    fn power(&mut self) -> Result<Expr> {
+        let base = self.postfix()?;
+        if self.eat("^") {
+            Ok(Expr::Binary(
+                BinaryOp::Pow,
+                Box::new(base),
+                Box::new(self.unary()?),
+            ))
+        } else {
+            Ok(base)
+        }
+    }
+    fn postfix(&mut self) -> Result<Expr> {
+        let mut expr = self.primary()?;
+        while self.eat("[") {
+            let start = self.index()?;
+            let slice = if self.eat("..") {
+                Slice::Range(start, self.index()?)
+            } else {
+                Slice::Index(
+                    start
+                        .ok_or_else(|| MathError::Parse(self.offset(), "expected index".into()))?,
+                )
+            };
+            self.expect("]")?;
+            expr = Expr::Slice(Box::new(expr), slice);
+        }
+        Ok(expr)
+    }
+    fn index(&mut self) -> Result<Option<usize>> {
+        match self.peek() {
+            Some(&Token::Number(x)) => {
+                if x.fract() != 0. || x < 0. {
+                    return Err(MathError::Parse(
+                        self.offset(),
+                        format!("invalid index {x}"),
+                    ));
+                }
+                self.pos += 1;
+                Ok(Some(x as usize))
+            }
+            _ => Ok(None),
+        }
+    }
+    fn list(&mut self, close: &str) -> Result<Vec<Expr>> {
+        let mut items = vec![];
+        if self.eat(close) {
+            return Ok(items);
+        }
+        loop {
+            items.push(self.expr()?);
+            if self.eat(close) {
+                return Ok(items);
+            }
+            self.expect(",")?;
+        }
+    }
+    fn primary(&mut self) -> Result<Expr> {
+        let offset = self.offset();
+        match self.peek().cloned() {
+            Some(Token::Number(x)) => {
+                self.pos += 1;
+                Ok(Expr::Number(x))
+            }
+            Some(Token::Ident(name)) => {
+                self.pos += 1;
+                if self.eat("(") {
+                    let function = Function::from_name(&name)
+                        .ok_or_else(|| MathError::Function(name.clone()))?;
+                    Ok(Expr::Call(function, self.list(")")?))
+                } else {
+                    let index = match self.variables.iter().position(|v| *v == name) {
+                        Some(index) => index,
+                        None => {
+                            self.variables.push(name.clone());
+                            self.variables.len() - 1
+                        }
+                    };
+                    Ok(Expr::Variable(name, index))
+                }
+            }
+            Some(Token::Symbol("(")) => {
+                self.pos += 1;
+                let expr = self.expr()?;
+                self.expect(")")?;
+                Ok(expr)
+            }
+            Some(Token::Symbol("[")) => {
+                self.pos += 1;
+                Ok(Expr::Concat(self.list("]")?))
+            }
+            Some(token) => Err(MathError::Parse(offset, format!("unexpected {token}"))),
+            None => Err(MathError::Parse(
+                offset,
+                "unexpected end of expression".into(),
+            )),
+        }
+    }
+}
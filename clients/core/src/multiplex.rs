@@ -2,7 +2,7 @@
 
 use std::sync::Arc;
 
-use interface::{Data, Read, UniqueIdentifier, Update, Write};
+use interface::{ClientHooks, Data, Read, UniqueIdentifier, Update, Write};
 use std::any::type_name;
 
 /// Multiplexer
@@ -24,6 +24,7 @@ impl<T: Default> Multiplex<T> {
 }
 
 impl<T: Send + Sync> Update for Multiplex<T> {}
+impl<T: Send + Sync> ClientHooks for Multiplex<T> {}
 impl<T, U> Read<U> for Multiplex<T>
 where
     U: UniqueIdentifier<DataType = Vec<T>>,
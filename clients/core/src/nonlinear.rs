@@ -0,0 +1,205 @@
+//! # Nonlinear elements
+//!
+//! Vectorized nonlinear clients applying, channel by channel, the nonlinearity to the input signal:
+//!  * [Saturation]: clamps the signal within lower and upper limits,
+//!  * [RateLimiter]: bounds the rising and falling rates of the signal,
+//!  * [DeadBand]: zeroes the signal within a dead zone,
+//!  * [Backlash]: models the play between the input and the output,
+//!  * [Quantizer]: rounds the signal to a multiple of the quantization step,
+//!  * [Delay]: delays the signal by a given number of samples.
+//!
+//! The parameters of the nonlinear elements are either the same for all channels
+//! or given per channel.
+//! The number of times each channel hits the limit of the nonlinearity is recorded
+//! and is available with the [Limited] trait.
+//!
+//! ```
+//! use gmt_dos_clients::nonlinear::{Limited, Saturation};
+//! use interface::{Data, Read, Update, Write, UID};
+//!
+//! #[derive(UID)]
+//! enum Stroke {}
+//!
+//! let mut sat = Saturation::new(-1f64, 1f64);
+//! <_ as Read<Stroke>>::read(&mut sat, Data::new(vec![-2., 0.5, 3.]));
+//! sat.update();
+//! let y = <_ as Write<Stroke>>::write(&mut sat).unwrap();
+//! assert_eq!(*y, vec![-1., 0.5, 1.]);
+//! assert_eq!(sat.hits(), &[1, 0, 1]);
+//! ```
+
+mod backlash;
+mod deadband;
+mod delay;
+mod quantizer;
+mod rate_limiter;
+mod saturation;
+
+pub use backlash::Backlash;
+pub use deadband::DeadBand;
+pub use delay::Delay;
+pub use quantizer::Quantizer;
+pub use rate_limiter::RateLimiter;
+pub use saturation::Saturation;
+
+/// Counter of the limit hits of a nonlinear element
+pub trait Limited {
+    /// Returns the number of samples each channel has hit the limit
+    fn hits(&self) -> &[usize];
+    /// Returns the number of samples the limit has been hit, summed over all channels
+    fn total_hits(&self) -> usize {
+        self.hits().iter().sum()
+    }
+    /// Resets the limit hit counters
+    fn reset_hits(&mut self);
+}
+
+/// Returns the parameter of the `i`th channel, a single parameter applies to all channels
+fn at<T: Copy>(param: &[T], i: usize) -> T {
+    if param.len() == 1 { param[0] } else { param[i] }
+}
+
+/// Checks the lengths of the parameter vectors of a nonlinear element
+///
+/// A parameter vector has either a single parameter for all channels or a parameter per channel,
+/// all the per-channel parameter vectors must have `n_channel` parameters or the same length
+/// if the number of channels is not known
+///
+/// # Panics
+///
+/// If a parameter vector is empty or of the wrong length
+fn check_lengths<T>(client: &str, params: &[(&str, &[T])], mut n_channel: Option<usize>) {
+    for (name, param) in params {
+        match (param.len(), n_channel) {
+            (0, _) => panic!("{client} {name} vector is empty"),
+            (1, _) => (),
+            (len, Some(n)) => assert_eq!(
+                len, n,
+                "{client} {name} vector length error: expected {n} found {len}"
+            ),
+            (len, None) => n_channel = Some(len),
+        }
+    }
+}
+
+/// Implements [Read](interface::Read), [Write](interface::Write) and [Limited] for
+/// a nonlinear element with `u`, `y` and `hits` fields and the given parameter vectors
+///
+/// The lengths of the parameter vectors are checked against the number of channels of the input
+macro_rules! impl_nonlinear {
+    ($($client:ident($($param:ident),*)),*) => {
+        $(
+        impl<T, U> ::interface::Read<U> for $client<T>
+        where
+            T: ::num_traits::Float + Send + Sync,
+            U: ::interface::UniqueIdentifier<DataType = Vec<T>>,
+        {
+            fn read(&mut self, data: ::interface::Data<U>) {
+                $crate::nonlinear::check_lengths(
+                    stringify!($client),
+                    &[$((stringify!($param), self.$param.as_slice())),*],
+                    Some(data.len()),
+                );
+                self.hits.resize(data.len(), 0);
+                self.u = data.into_arc();
+            }
+        }
+        impl<T, U> ::interface::Write<U> for $client<T>
+        where
+            T: ::num_traits::Float + Send + Sync,
+            U: ::interface::UniqueIdentifier<DataType = Vec<T>>,
+        {
+            fn write(&mut self) -> Option<::interface::Data<U>> {
+                Some((&self.y).into())
+            }
+        }
+        impl<T> $crate::nonlinear::Limited for $client<T> {
+            fn hits(&self) -> &[usize] {
+                &self.hits
+            }
+            fn reset_hits(&mut self) {
+                self.hits.iter_mut().for_each(|h| *h = 0);
+            }
+        }
+        )*
+    };
+}
+use impl_nonlinear;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use interface::{Data, Read, UID, Update, Write};
+
+    #[derive(UID)]
+    enum U {}
+
+    fn step<C>(client: &mut C, u: Vec<f64>) -> Vec<f64>
+    where
+        C: Read<U> + Write<U> + Update,
+    {
+        <C as Read<U>>::read(client, Data::new(u));
+        client.update();
+        <C as Write<U>>::write(client).unwrap().as_arc().to_vec()
+    }
+
+    #[test]
+    fn rate_limiter() {
+        let mut rl = RateLimiter::new(1f64, 10f64).falling(2.);
+        assert_eq!(step(&mut rl, vec![0., 0.]), vec![0., 0.]);
+        let y = step(&mut rl, vec![1., -1.]);
+        assert!((y[0] - 0.1).abs() < 1e-12 && (y[1] + 0.2).abs() < 1e-12);
+        assert_eq!(rl.hits(), &[1, 1]);
+    }
+
+    #[test]
+    fn deadband() {
+        let mut db = DeadBand::new(-0.5, 0.5);
+        assert_eq!(step(&mut db, vec![-1., 0.2, 2.]), vec![-0.5, 0., 1.5]);
+        assert_eq!(db.hits(), &[0, 1, 0]);
+    }
+
+    #[test]
+    fn backlash() {
+        let mut bl = Backlash::new(1f64);
+        let y: Vec<_> = [0., 0.4, 1., 0.8, -0.2]
+            .into_iter()
+            .map(|u| step(&mut bl, vec![u])[0])
+            .collect();
+        assert_eq!(y, vec![0., 0., 0.5, 0.5, 0.3]);
+        assert_eq!(bl.total_hits(), 2);
+    }
+
+    #[test]
+    fn quantizer() {
+        let mut q = Quantizer::bits(2, -1f64, 1f64);
+        assert_eq!(step(&mut q, vec![0.3, -0.8, 2.]), vec![0.5, -1., 0.5]);
+        assert_eq!(q.hits(), &[0, 0, 1]);
+        let levels: std::collections::BTreeSet<_> = (-20..=20)
+            .map(|i| step(&mut q, vec![i as f64 * 0.1])[0].to_bits())
+            .collect();
+        assert_eq!(levels.len(), 4);
+        q.reset_hits();
+        assert_eq!(q.total_hits(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Saturation upper vector length error: expected 2 found 3")]
+    fn saturation_lengths() {
+        Saturation::vector(vec![-1f64, -2.], vec![1., 2., 3.]);
+    }
+
+    #[test]
+    #[should_panic(expected = "DeadBand lower vector length error: expected 3 found 2")]
+    fn deadband_channels() {
+        let mut db = DeadBand::vector(vec![-1f64, -2.], vec![1.]);
+        step(&mut db, vec![0., 0., 0.]);
+    }
+
+    #[test]
+    fn delay() {
+        let mut d = Delay::new(2);
+        let y: Vec<_> = (1..=4).map(|i| step(&mut d, vec![i as f64])[0]).collect();
+        assert_eq!(y, vec![0., 0., 1., 2.]);
+    }
+}
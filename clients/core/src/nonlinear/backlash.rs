@@ -0,0 +1,68 @@
+use super::{at, check_lengths, impl_nonlinear};
+use num_traits::Float;
+use std::sync::Arc;
+
+/// Backlash client
+///
+/// The output of each channel follows the input only once the input has crossed
+/// the play (or gap) of the given width, the output is held otherwise.
+/// The output is initialized with the first input.
+/// A hit is counted for each sample the output is held.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Default)]
+pub struct Backlash<T> {
+    width: Vec<T>,
+    u: Arc<Vec<T>>,
+    y: Arc<Vec<T>>,
+    hits: Vec<usize>,
+}
+impl<T: Float> Backlash<T> {
+    /// Creates a new [Backlash] client with the same play for all channels
+    pub fn new(width: T) -> Self {
+        Self::vector(vec![width])
+    }
+    /// Creates a new [Backlash] client with per-channel plays
+    ///
+    /// # Panics
+    ///
+    /// If the vector is empty
+    pub fn vector(width: Vec<T>) -> Self {
+        check_lengths("Backlash", &[("width", width.as_slice())], None);
+        Self {
+            width,
+            u: Default::default(),
+            y: Default::default(),
+            hits: Vec::new(),
+        }
+    }
+}
+impl<T: Float + Send + Sync> interface::Update for Backlash<T> {
+    fn update(&mut self) {
+        if self.y.len() != self.u.len() {
+            self.y = self.u.clone();
+            return;
+        }
+        self.y = Arc::new(
+            self.u
+                .iter()
+                .zip(self.y.iter())
+                .zip(&mut self.hits)
+                .enumerate()
+                .map(|(i, ((&u, &y), hits))| {
+                    let half = at(&self.width, i) / (T::one() + T::one());
+                    if u - y > half {
+                        u - half
+                    } else if u - y < -half {
+                        u + half
+                    } else {
+                        *hits += 1;
+                        y
+                    }
+                })
+                .collect(),
+        );
+    }
+}
+impl<T: Float + Send + Sync> interface::ClientHooks for Backlash<T> {}
+
+impl_nonlinear!(Backlash(width));
@@ -0,0 +1,73 @@
+use super::{at, check_lengths, impl_nonlinear};
+use num_traits::Float;
+use std::sync::Arc;
+
+/// Dead band client
+///
+/// Zeroes each channel of the input signal within the `[lower, upper]` dead zone,
+/// outside the dead zone the output is offset by either the lower or the upper bound.
+/// A hit is counted for each sample within the dead zone.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Default)]
+pub struct DeadBand<T> {
+    lower: Vec<T>,
+    upper: Vec<T>,
+    u: Arc<Vec<T>>,
+    y: Arc<Vec<T>>,
+    hits: Vec<usize>,
+}
+impl<T: Float> DeadBand<T> {
+    /// Creates a new [DeadBand] client with the same dead zone for all channels
+    pub fn new(lower: T, upper: T) -> Self {
+        Self::vector(vec![lower], vec![upper])
+    }
+    /// Creates a new [DeadBand] client with the dead zone `[-width/2, width/2]`
+    pub fn symmetric(width: T) -> Self {
+        let half = width / (T::one() + T::one());
+        Self::new(-half, half)
+    }
+    /// Creates a new [DeadBand] client with per-channel dead zones
+    ///
+    /// # Panics
+    ///
+    /// If a vector is empty or if the per-channel vectors have different lengths
+    pub fn vector(lower: Vec<T>, upper: Vec<T>) -> Self {
+        check_lengths(
+            "DeadBand",
+            &[("lower", lower.as_slice()), ("upper", upper.as_slice())],
+            None,
+        );
+        Self {
+            lower,
+            upper,
+            u: Default::default(),
+            y: Default::default(),
+            hits: Vec::new(),
+        }
+    }
+}
+impl<T: Float + Send + Sync> interface::Update for DeadBand<T> {
+    fn update(&mut self) {
+        self.y = Arc::new(
+            self.u
+                .iter()
+                .zip(&mut self.hits)
+                .enumerate()
+                .map(|(i, (&u, hits))| {
+                    let (lower, upper) = (at(&self.lower, i), at(&self.upper, i));
+                    if u > upper {
+                        u - upper
+                    } else if u < lower {
+                        u - lower
+                    } else {
+                        *hits += 1;
+                        T::zero()
+                    }
+                })
+                .collect(),
+        );
+    }
+}
+impl<T: Float + Send + Sync> interface::ClientHooks for DeadBand<T> {}
+
+impl_nonlinear!(DeadBand(lower, upper));
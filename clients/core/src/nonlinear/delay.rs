@@ -0,0 +1,59 @@
+use interface::{ClientHooks, Data, Read, UniqueIdentifier, Update, Write};
+use std::{collections::VecDeque, sync::Arc};
+
+/// Transport delay client
+///
+/// Delays the input signal by a given number of samples,
+/// the output is zero until the delay line is filled
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Default)]
+pub struct Delay<T> {
+    n_sample: usize,
+    line: VecDeque<Arc<Vec<T>>>,
+    u: Arc<Vec<T>>,
+    y: Arc<Vec<T>>,
+}
+impl<T> Delay<T> {
+    /// Creates a new [Delay] client of `n_sample` samples
+    pub fn new(n_sample: usize) -> Self {
+        Self {
+            n_sample,
+            line: VecDeque::with_capacity(n_sample + 1),
+            u: Default::default(),
+            y: Default::default(),
+        }
+    }
+    /// Returns the delay in number of samples
+    pub fn n_sample(&self) -> usize {
+        self.n_sample
+    }
+}
+impl<T: Clone + Default + Send + Sync> Update for Delay<T> {
+    fn update(&mut self) {
+        self.line.push_back(self.u.clone());
+        self.y = if self.line.len() > self.n_sample {
+            self.line.pop_front().unwrap()
+        } else {
+            Arc::new(vec![T::default(); self.u.len()])
+        };
+    }
+}
+impl<T: Clone + Default + Send + Sync> ClientHooks for Delay<T> {}
+impl<T, U> Read<U> for Delay<T>
+where
+    T: Clone + Default + Send + Sync,
+    U: UniqueIdentifier<DataType = Vec<T>>,
+{
+    fn read(&mut self, data: Data<U>) {
+        self.u = data.into_arc();
+    }
+}
+impl<T, U> Write<U> for Delay<T>
+where
+    T: Clone + Default + Send + Sync,
+    U: UniqueIdentifier<DataType = Vec<T>>,
+{
+    fn write(&mut self) -> Option<Data<U>> {
+        Some((&self.y).into())
+    }
+}
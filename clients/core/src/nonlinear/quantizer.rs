@@ -0,0 +1,85 @@
+use super::{at, check_lengths, impl_nonlinear};
+use num_traits::Float;
+use std::sync::Arc;
+
+/// Quantizer client
+///
+/// Rounds each channel of the input signal to the nearest multiple of the quantization step,
+/// the multiples being counted from the origin of the quantization levels (default: 0).
+/// If a range is set, the output is saturated within the range and a hit is counted
+/// for each saturated sample.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Default)]
+pub struct Quantizer<T> {
+    step: Vec<T>,
+    origin: T,
+    range: Option<(T, T)>,
+    u: Arc<Vec<T>>,
+    y: Arc<Vec<T>>,
+    hits: Vec<usize>,
+}
+impl<T: Float> Quantizer<T> {
+    /// Creates a new [Quantizer] client with the same quantization step for all channels
+    pub fn new(step: T) -> Self {
+        Self::vector(vec![step])
+    }
+    /// Creates a new [Quantizer] client with per-channel quantization steps
+    ///
+    /// # Panics
+    ///
+    /// If the vector is empty
+    pub fn vector(step: Vec<T>) -> Self {
+        check_lengths("Quantizer", &[("step", step.as_slice())], None);
+        Self {
+            step,
+            origin: T::zero(),
+            range: None,
+            u: Default::default(),
+            y: Default::default(),
+            hits: Vec::new(),
+        }
+    }
+    /// Creates a new [Quantizer] client for a `n_bit` converter with the `[lower, upper]` full scale range
+    ///
+    /// The 2^`n_bit` quantization levels are `lower + k (upper - lower) / 2^n_bit`
+    /// with `k` in `[0, 2^n_bit - 1]`
+    pub fn bits(n_bit: u32, lower: T, upper: T) -> Self {
+        let levels = T::from(1u64 << n_bit).unwrap();
+        let step = (upper - lower) / levels;
+        Self {
+            origin: lower,
+            ..Self::new(step)
+        }
+        .range(lower, upper - step)
+    }
+    /// Saturates the output within the `[lower, upper]` range
+    pub fn range(mut self, lower: T, upper: T) -> Self {
+        self.range = Some((lower, upper));
+        self
+    }
+}
+impl<T: Float + Send + Sync> interface::Update for Quantizer<T> {
+    fn update(&mut self) {
+        self.y = Arc::new(
+            self.u
+                .iter()
+                .zip(&mut self.hits)
+                .enumerate()
+                .map(|(i, (&u, hits))| {
+                    let step = at(&self.step, i);
+                    let y = self.origin + ((u - self.origin) / step).round() * step;
+                    match self.range {
+                        Some((lower, upper)) if y < lower || y > upper => {
+                            *hits += 1;
+                            y.max(lower).min(upper)
+                        }
+                        _ => y,
+                    }
+                })
+                .collect(),
+        );
+    }
+}
+impl<T: Float + Send + Sync> interface::ClientHooks for Quantizer<T> {}
+
+impl_nonlinear!(Quantizer(step));
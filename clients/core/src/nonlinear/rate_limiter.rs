@@ -0,0 +1,96 @@
+use super::{at, check_lengths, impl_nonlinear};
+use num_traits::Float;
+use std::sync::Arc;
+
+/// Rate limiter client
+///
+/// Bounds the rising and falling rates, in units per second, of each channel of the input signal.
+/// The output is initialized with the first input.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Default)]
+pub struct RateLimiter<T> {
+    rising: Vec<T>,
+    falling: Vec<T>,
+    ts: T,
+    u: Arc<Vec<T>>,
+    y: Arc<Vec<T>>,
+    hits: Vec<usize>,
+}
+impl<T: Float> RateLimiter<T> {
+    /// Creates a new [RateLimiter] client sampled at the given frequency in Hz
+    /// with the same rising and falling rates for all channels
+    pub fn new(rate: T, sampling_frequency: T) -> Self {
+        Self::vector(vec![rate], sampling_frequency)
+    }
+    /// Creates a new [RateLimiter] client sampled at the given frequency in Hz
+    /// with per-channel rising and falling rates
+    ///
+    /// # Panics
+    ///
+    /// If the vector is empty
+    pub fn vector(rate: Vec<T>, sampling_frequency: T) -> Self {
+        check_lengths("RateLimiter", &[("rising", rate.as_slice())], None);
+        Self {
+            rising: rate.clone(),
+            falling: rate,
+            ts: sampling_frequency.recip(),
+            u: Default::default(),
+            y: Default::default(),
+            hits: Vec::new(),
+        }
+    }
+    /// Sets the same falling rate for all channels
+    pub fn falling(self, rate: T) -> Self {
+        self.falling_vector(vec![rate])
+    }
+    /// Sets per-channel falling rates
+    ///
+    /// # Panics
+    ///
+    /// If the vector is empty or if the per-channel rising and falling rates have different lengths
+    pub fn falling_vector(mut self, rate: Vec<T>) -> Self {
+        check_lengths(
+            "RateLimiter",
+            &[
+                ("rising", self.rising.as_slice()),
+                ("falling", rate.as_slice()),
+            ],
+            None,
+        );
+        self.falling = rate;
+        self
+    }
+}
+impl<T: Float + Send + Sync> interface::Update for RateLimiter<T> {
+    fn update(&mut self) {
+        if self.y.len() != self.u.len() {
+            self.y = self.u.clone();
+            return;
+        }
+        self.y = Arc::new(
+            self.u
+                .iter()
+                .zip(self.y.iter())
+                .zip(&mut self.hits)
+                .enumerate()
+                .map(|(i, ((&u, &y), hits))| {
+                    let rising = at(&self.rising, i) * self.ts;
+                    let falling = at(&self.falling, i) * self.ts;
+                    let delta = u - y;
+                    if delta > rising {
+                        *hits += 1;
+                        y + rising
+                    } else if delta < -falling {
+                        *hits += 1;
+                        y - falling
+                    } else {
+                        u
+                    }
+                })
+                .collect(),
+        );
+    }
+}
+impl<T: Float + Send + Sync> interface::ClientHooks for RateLimiter<T> {}
+
+impl_nonlinear!(RateLimiter(rising, falling));
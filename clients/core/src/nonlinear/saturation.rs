@@ -0,0 +1,66 @@
+use super::{at, check_lengths, impl_nonlinear};
+use num_traits::Float;
+use std::sync::Arc;
+
+/// Saturation client
+///
+/// Clamps each channel of the input signal within the `[lower, upper]` range
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Default)]
+pub struct Saturation<T> {
+    lower: Vec<T>,
+    upper: Vec<T>,
+    u: Arc<Vec<T>>,
+    y: Arc<Vec<T>>,
+    hits: Vec<usize>,
+}
+impl<T: Float> Saturation<T> {
+    /// Creates a new [Saturation] client with the same limits for all channels
+    pub fn new(lower: T, upper: T) -> Self {
+        Self::vector(vec![lower], vec![upper])
+    }
+    /// Creates a new [Saturation] client with the limits `[-limit, limit]`
+    pub fn symmetric(limit: T) -> Self {
+        Self::new(-limit, limit)
+    }
+    /// Creates a new [Saturation] client with per-channel limits
+    ///
+    /// # Panics
+    ///
+    /// If a vector is empty or if the per-channel vectors have different lengths
+    pub fn vector(lower: Vec<T>, upper: Vec<T>) -> Self {
+        check_lengths(
+            "Saturation",
+            &[("lower", lower.as_slice()), ("upper", upper.as_slice())],
+            None,
+        );
+        Self {
+            lower,
+            upper,
+            u: Default::default(),
+            y: Default::default(),
+            hits: Vec::new(),
+        }
+    }
+}
+impl<T: Float + Send + Sync> interface::Update for Saturation<T> {
+    fn update(&mut self) {
+        self.y = Arc::new(
+            self.u
+                .iter()
+                .zip(&mut self.hits)
+                .enumerate()
+                .map(|(i, (&u, hits))| {
+                    let y = u.max(at(&self.lower, i)).min(at(&self.upper, i));
+                    if y != u {
+                        *hits += 1;
+                    }
+                    y
+                })
+                .collect(),
+        );
+    }
+}
+impl<T: Float + Send + Sync> interface::ClientHooks for Saturation<T> {}
+
+impl_nonlinear!(Saturation(lower, upper));
@@ -31,7 +31,7 @@ assert_eq!(*written_data, None);
 
 use std::ops::Deref;
 
-use interface::{Data, Read, UniqueIdentifier, Update, Write};
+use interface::{ClientHooks, Data, Read, UniqueIdentifier, Update, Write};
 
 /// Once client
 ///
@@ -53,6 +53,7 @@ impl<U: UniqueIdentifier> Default for Once<U> {
 }
 
 impl<U: UniqueIdentifier> Update for Once<U> {}
+impl<U: UniqueIdentifier> ClientHooks for Once<U> {}
 
 impl<U> Read<U> for Once<U>
 where
@@ -6,7 +6,7 @@ use std::{
 };
 
 use gmt_dos_clients_optics_state::{MirrorState, OpticalState};
-use interface::{Data, Left, Read, Right, UniqueIdentifier, Update, Write};
+use interface::{ClientHooks, Data, Left, Read, Right, UniqueIdentifier, Update, Write};
 
 #[derive(Default, Debug, Clone)]
 pub enum OperatorKind {
@@ -115,6 +115,10 @@ where
         );
     }
 }
+impl<T> ClientHooks for Operator<Vec<T>, Plus> where
+    T: Copy + Add<Output = T> + Send + Sync + Debug + Default
+{
+}
 impl<T> Update for Operator<Vec<T>, Minus>
 where
     T: Copy + Sub<Output = T> + Send + Sync + Debug + Default,
@@ -151,22 +155,29 @@ where
         );
     }
 }
+impl<T> ClientHooks for Operator<Vec<T>, Minus> where
+    T: Copy + Sub<Output = T> + Send + Sync + Debug + Default
+{
+}
 
 impl Update for Operator<MirrorState, Plus> {
     fn update(&mut self) {
         self.output = Arc::new(&*self.left + &*self.right)
     }
 }
+impl ClientHooks for Operator<MirrorState, Plus> {}
 impl Update for Operator<OpticalState, Plus> {
     fn update(&mut self) {
         self.output = Arc::new(&*self.left + &*self.right)
     }
 }
+impl ClientHooks for Operator<OpticalState, Plus> {}
 impl Update for Operator<MirrorState, Minus> {
     fn update(&mut self) {
         self.output = Arc::new(&*self.left - &*self.right)
     }
 }
+impl ClientHooks for Operator<MirrorState, Minus> {}
 
 impl<T, U, K> Read<Left<U>> for Operator<T, K>
 where
@@ -0,0 +1,392 @@
+//! # PID controller client
+//!
+//! A vectorized PID controller implementing, for each channel, the relationship
+//! between the measurement `y`, the setpoint `r` and the output `u`:
+//! `u = sat(kp (b r - y) + ki ∫(r - y) dt + kd d(c r - y)/dt)`
+//! where `b` and `c` are the setpoint weights of the proportional and derivative terms.
+//! The setpoint is zero unless it is set with the [Setpoint] input.
+//!
+//! The derivative term is low-pass filtered with the transfer function `kd s / (1 + s/N)`.
+//! If the output is saturated, the integral term is protected against windup
+//! by either clamping the integration or back-calculation (see [AntiWindup]).
+//!
+//! The gains can be changed at run time with the [Gains] input,
+//! the integral term is then adjusted to keep the output continuous (bumpless transfer).
+
+use super::{ClientHooks, Data, Read, UniqueIdentifier, Update, Write};
+use num_traits::Float;
+use std::{
+    fmt::{Debug, Display},
+    marker::PhantomData,
+    sync::Arc,
+};
+
+/// Integrator anti-windup methods
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum AntiWindup<T> {
+    /// No anti-windup
+    #[default]
+    None,
+    /// Stops the integration when it drives the output further into saturation
+    Clamping,
+    /// Feeds back the saturation excess to the integrator with the given tracking time constant in seconds
+    BackCalculation(T),
+}
+
+/// PID controller gains
+///
+/// The gains are either per-channel or, if the vectors have a single element, the same for all channels
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PidGains<T> {
+    pub kp: Vec<T>,
+    pub ki: Vec<T>,
+    pub kd: Vec<T>,
+}
+
+/// PID controller of the measurement `U`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug)]
+pub struct Pid<T, U> {
+    kp: Vec<T>,
+    ki: Vec<T>,
+    kd: Vec<T>,
+    b: T,
+    c: T,
+    n: Option<T>,
+    limits: Option<(T, T)>,
+    anti_windup: AntiWindup<T>,
+    ts: T,
+    y: Arc<Vec<T>>,
+    r: Arc<Vec<T>>,
+    ep: Vec<T>,
+    ed: Option<Vec<T>>,
+    integral: Vec<T>,
+    derivative: Vec<T>,
+    u: Vec<T>,
+    uid: PhantomData<U>,
+}
+impl<T: Display + Debug, U> Display for Pid<T, U> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // the gains of the 1st channel, if any
+        let first = |gains: &[T]| {
+            gains
+                .first()
+                .map_or("-".to_string(), |gain| gain.to_string())
+        };
+        write!(
+            f,
+            "PID controller (kp={},ki={},kd={},saturation={:?},anti-windup={:?})",
+            first(&self.kp),
+            first(&self.ki),
+            first(&self.kd),
+            self.limits,
+            self.anti_windup
+        )
+    }
+}
+fn broadcast<T: Copy>(value: Vec<T>, n: usize, name: &str) -> Vec<T> {
+    match value.len() {
+        1 => vec![value[0]; n],
+        len => {
+            assert_eq!(
+                len, n,
+                "{name} gain vector length error: expected {n} found {len}"
+            );
+            value
+        }
+    }
+}
+impl<T: Float, U> Pid<T, U> {
+    /// Creates a new PID controller for `n_data` channels sampled at the given frequency in Hz
+    ///
+    /// All the gains are zero
+    pub fn new(n_data: usize, sampling_frequency: T) -> Self {
+        let zeros = vec![T::zero(); n_data];
+        Self {
+            kp: zeros.clone(),
+            ki: zeros.clone(),
+            kd: zeros.clone(),
+            b: T::one(),
+            c: T::one(),
+            n: None,
+            limits: None,
+            anti_windup: AntiWindup::None,
+            ts: sampling_frequency.recip(),
+            y: Arc::new(zeros.clone()),
+            r: Arc::new(zeros.clone()),
+            ep: zeros.clone(),
+            ed: None,
+            integral: zeros.clone(),
+            derivative: zeros.clone(),
+            u: zeros,
+            uid: PhantomData,
+        }
+    }
+    /// Sets the same proportional, integral and derivative gains for all channels
+    pub fn gain(self, kp: T, ki: T, kd: T) -> Self {
+        self.gain_vector(vec![kp], vec![ki], vec![kd])
+    }
+    /// Sets the per-channel proportional, integral and derivative gains
+    pub fn gain_vector(mut self, kp: Vec<T>, ki: Vec<T>, kd: Vec<T>) -> Self {
+        let n = self.u.len();
+        self.kp = broadcast(kp, n, "proportional");
+        self.ki = broadcast(ki, n, "integral");
+        self.kd = broadcast(kd, n, "derivative");
+        self
+    }
+    /// Sets the setpoint weights `b` and `c` of the proportional and derivative terms (default: 1)
+    pub fn setpoint_weights(mut self, b: T, c: T) -> Self {
+        self.b = b;
+        self.c = c;
+        self
+    }
+    /// Filters the derivative term with a first order low-pass filter of bandwidth `n` in rad/s
+    pub fn derivative_filter(mut self, n: T) -> Self {
+        self.n = Some(n);
+        self
+    }
+    /// Saturates the output within the `[lower, upper]` range
+    pub fn saturation(mut self, lower: T, upper: T) -> Self {
+        self.limits = Some((lower, upper));
+        self
+    }
+    /// Sets the integrator anti-windup method
+    pub fn anti_windup(mut self, anti_windup: AntiWindup<T>) -> Self {
+        self.anti_windup = anti_windup;
+        self
+    }
+    /// Changes the gains without bumping the output
+    ///
+    /// The integral term absorbs the changes of the proportional and derivative terms
+    pub fn set_gains(&mut self, gains: PidGains<T>) -> &mut Self {
+        let n = self.u.len();
+        let PidGains { kp, ki, kd } = gains;
+        let kp = broadcast(kp, n, "proportional");
+        let kd = broadcast(kd, n, "derivative");
+        for i in 0..n {
+            let derivative = if self.kd[i].is_zero() {
+                T::zero()
+            } else {
+                self.derivative[i] * kd[i] / self.kd[i]
+            };
+            self.integral[i] =
+                self.integral[i] + (self.kp[i] - kp[i]) * self.ep[i] + self.derivative[i]
+                    - derivative;
+            self.derivative[i] = derivative;
+        }
+        self.kp = kp;
+        self.ki = broadcast(ki, n, "integral");
+        self.kd = kd;
+        self
+    }
+    /// Resets the integral and derivative terms
+    pub fn reset(&mut self) {
+        self.integral.iter_mut().for_each(|x| *x = T::zero());
+        self.derivative.iter_mut().for_each(|x| *x = T::zero());
+        self.ed = None;
+    }
+    fn saturate(&self, v: T) -> T {
+        self.limits
+            .map_or(v, |(lower, upper)| v.max(lower).min(upper))
+    }
+}
+
+impl<T, U> Update for Pid<T, U>
+where
+    T: Float + Send + Sync,
+    U: UniqueIdentifier<DataType = Vec<T>>,
+{
+    fn update(&mut self) {
+        let ts = self.ts;
+        let ed_prev = self.ed.take();
+        let mut ed = Vec::with_capacity(self.u.len());
+        for i in 0..self.u.len() {
+            let (y, r) = (self.y[i], self.r[i]);
+            let e = r - y;
+            self.ep[i] = self.b * r - y;
+            ed.push(self.c * r - y);
+            let p = self.kp[i] * self.ep[i];
+            let d = if let Some(ed_prev) = ed_prev.as_ref() {
+                let de = ed[i] - ed_prev[i];
+                match self.n {
+                    Some(n) => (self.derivative[i] + self.kd[i] * n * de) / (T::one() + n * ts),
+                    None => self.kd[i] * de / ts,
+                }
+            } else {
+                T::zero()
+            };
+            self.derivative[i] = d;
+            let integral = self.integral[i] + self.ki[i] * ts * e;
+            let v = p + integral + d;
+            let u = self.saturate(v);
+            self.integral[i] = match self.anti_windup {
+                AntiWindup::None => integral,
+                AntiWindup::Clamping => {
+                    if u != v && (v - u) * self.ki[i] * e > T::zero() {
+                        self.integral[i]
+                    } else {
+                        integral
+                    }
+                }
+                AntiWindup::BackCalculation(tt) => integral + ts / tt * (u - v),
+            };
+            self.u[i] = self.saturate(p + self.integral[i] + d);
+        }
+        self.ed = Some(ed);
+    }
+}
+impl<T, U> ClientHooks for Pid<T, U>
+where
+    T: Float + Send + Sync,
+    U: UniqueIdentifier<DataType = Vec<T>>,
+{
+}
+
+impl<T, U> Read<U> for Pid<T, U>
+where
+    T: Float + Send + Sync,
+    U: UniqueIdentifier<DataType = Vec<T>>,
+{
+    fn read(&mut self, data: Data<U>) {
+        self.y = data.into_arc();
+        assert_eq!(
+            self.y.len(),
+            self.u.len(),
+            "gmt_dos-clients::Pid input size error\nexpected {}, found {}",
+            self.u.len(),
+            self.y.len()
+        );
+    }
+}
+
+impl<T, U, V> Write<V> for Pid<T, U>
+where
+    T: Float + Send + Sync,
+    U: UniqueIdentifier<DataType = Vec<T>>,
+    V: UniqueIdentifier<DataType = Vec<T>>,
+{
+    fn write(&mut self) -> Option<Data<V>> {
+        Some(Data::new(self.u.clone()))
+    }
+}
+
+/// Setpoint `r` of the measurement `U`
+pub struct Setpoint<U>(PhantomData<U>);
+impl<U: UniqueIdentifier> UniqueIdentifier for Setpoint<U> {
+    type DataType = U::DataType;
+}
+
+impl<T, U> Read<Setpoint<U>> for Pid<T, U>
+where
+    T: Float + Send + Sync,
+    U: UniqueIdentifier<DataType = Vec<T>>,
+{
+    fn read(&mut self, data: Data<Setpoint<U>>) {
+        self.r = data.into_arc();
+        assert_eq!(
+            self.r.len(),
+            self.u.len(),
+            "gmt_dos-clients::Pid setpoint size error\nexpected {}, found {}",
+            self.u.len(),
+            self.r.len()
+        );
+    }
+}
+
+/// New gains of the PID controller of the measurement `U`
+pub struct Gains<U>(PhantomData<U>);
+impl<T, U> UniqueIdentifier for Gains<U>
+where
+    T: Send + Sync,
+    U: UniqueIdentifier<DataType = Vec<T>>,
+{
+    type DataType = PidGains<T>;
+}
+
+impl<T, U> Read<Gains<U>> for Pid<T, U>
+where
+    T: Float + Send + Sync,
+    U: UniqueIdentifier<DataType = Vec<T>>,
+{
+    fn read(&mut self, data: Data<Gains<U>>) {
+        self.set_gains((*data).clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    enum Y {}
+    impl UniqueIdentifier for Y {
+        type DataType = Vec<f64>;
+    }
+
+    /// Runs the controller in closed-loop with an integrator plant `x += u ts`
+    fn closed_loop(pid: &mut Pid<f64, Y>, x: &mut f64, r: f64, n: usize) -> Vec<f64> {
+        (0..n)
+            .map(|_| {
+                <Pid<f64, Y> as Read<Y>>::read(pid, Data::new(vec![*x]));
+                <Pid<f64, Y> as Read<Setpoint<Y>>>::read(pid, Data::new(vec![r]));
+                pid.update();
+                let u = <Pid<f64, Y> as Write<Y>>::write(pid).unwrap()[0];
+                *x += u * 1e-3;
+                u
+            })
+            .collect()
+    }
+
+    #[test]
+    fn anti_windup() {
+        let overshoot = |anti_windup| {
+            let mut pid = Pid::<f64, Y>::new(1, 1e3)
+                .gain(5., 20., 0.)
+                .saturation(-1., 1.)
+                .anti_windup(anti_windup);
+            let mut x = 0.;
+            let mut x_max = x;
+            for _ in 0..10_000 {
+                closed_loop(&mut pid, &mut x, 1., 1);
+                x_max = x_max.max(x);
+            }
+            (x_max, x)
+        };
+        let (windup, _) = overshoot(AntiWindup::None);
+        for anti_windup in [AntiWindup::Clamping, AntiWindup::BackCalculation(0.1)] {
+            let (x_max, x) = overshoot(anti_windup);
+            assert!(x_max < windup, "{anti_windup:?}: {x_max} vs {windup}");
+            assert!((x - 1.).abs() < 1e-3, "{anti_windup:?}: {x}");
+        }
+    }
+
+    #[test]
+    fn bumpless() {
+        let mut pid = Pid::<f64, Y>::new(1, 1e3)
+            .gain(2., 10., 0.01)
+            .derivative_filter(100.);
+        let mut x = 0.;
+        let u = closed_loop(&mut pid, &mut x, 1., 100);
+        let u_before = u[99];
+        <Pid<f64, Y> as Read<Gains<Y>>>::read(
+            &mut pid,
+            Data::new(PidGains {
+                kp: vec![4.],
+                ki: vec![5.],
+                kd: vec![0.02],
+            }),
+        );
+        let u = closed_loop(&mut pid, &mut x, 1., 1);
+        assert!((u[0] - u_before).abs() < 1e-2, "{} vs {u_before}", u[0]);
+    }
+
+    #[test]
+    fn display_without_channels() {
+        let pid = Pid::<f64, Y>::new(0, 1e3).gain(1., 2., 3.);
+        assert!(
+            pid.to_string()
+                .starts_with("PID controller (kp=-,ki=-,kd=-")
+        );
+    }
+}
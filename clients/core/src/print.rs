@@ -17,7 +17,7 @@ let print_data = Print::<f64>::default();
 
 use std::sync::Arc;
 
-use interface::{Data, Read, UniqueIdentifier, Update};
+use interface::{ClientHooks, Data, Read, UniqueIdentifier, Update};
 
 /// Print the data to the command line
 #[derive(Debug, Default)]
@@ -69,6 +69,7 @@ where
         }
     }
 }
+impl<T> ClientHooks for Print<T> where T: Send + Sync + std::fmt::Debug {}
 
 impl<T, U> Read<U> for Print<T>
 where
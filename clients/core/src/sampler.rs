@@ -1,4 +1,4 @@
-use super::{Data, Read, UniqueIdentifier, Update, Write};
+use super::{ClientHooks, Data, Read, UniqueIdentifier, Update, Write};
 use std::{marker::PhantomData, sync::Arc};
 
 /// Rate transitionner
@@ -48,6 +48,13 @@ where
     V: UniqueIdentifier<DataType = T>,
 {
 }
+impl<T, U, V> ClientHooks for Sampler<T, U, V>
+where
+    T: Send + Sync,
+    U: UniqueIdentifier<DataType = T>,
+    V: UniqueIdentifier<DataType = T>,
+{
+}
 impl<T, U, V> Read<U> for Sampler<T, U, V>
 where
     T: Send + Sync,
@@ -27,7 +27,7 @@ let select = Select::<f64>::new(1..3);
 use std::{marker::PhantomData, ops::Range, sync::Arc};
 
 use interface::{
-    Data, Read, UniqueIdentifier, Update, Write,
+    ClientHooks, Data, Read, UniqueIdentifier, Update, Write,
     units::{Arcsec, Mas, MuM, NM, UnitsConversion},
 };
 
@@ -71,6 +71,7 @@ impl<T> Select<T> {
 }
 
 impl<T: Send + Sync> Update for Select<T> {}
+impl<T: Send + Sync> ClientHooks for Select<T> {}
 
 impl<T, U> Read<U> for Select<T>
 where
@@ -113,6 +114,7 @@ impl<S> USelect<S> {
 }
 
 impl<S: Send + Sync> Update for USelect<S> {}
+impl<S: Send + Sync> ClientHooks for USelect<S> {}
 
 impl<U, S: Send + Sync> Read<U> for USelect<S>
 where
@@ -1,10 +1,13 @@
-use super::{Data, TimerMarker, UniqueIdentifier, Update, Write};
+use super::{ClientHooks, Data, TimerMarker, UniqueIdentifier, Update, Write};
 // use linya::{Bar, Progress};
 use std::ops::Add;
 
 #[cfg(feature = "noise")]
 use rand_distr::{Distribution, Normal, NormalError};
 
+mod playback;
+pub use playback::{Interpolation, TimeSeries};
+
 /// Signal types
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone)]
@@ -39,6 +42,38 @@ pub enum Signal {
     #[cfg(feature = "noise")]
     #[cfg_attr(feature = "serde", serde(skip))]
     WhiteNoise(Normal<f64>),
+    /// Pseudo-random binary sequence of ±`amplitude`, each bit is held for `hold` samples
+    ///
+    /// See [Signal::prbs]
+    Prbs {
+        amplitude: f64,
+        hold: usize,
+        sequence: Vec<bool>,
+    },
+    /// Sum of sinusoids with phases in radians
+    ///
+    /// See [Signal::multisine]
+    Multisine {
+        sampling_frequency_hz: f64,
+        frequencies_hz: Vec<f64>,
+        amplitudes: Vec<f64>,
+        phases: Vec<f64>,
+    },
+    /// Colored noise made of sinusoids with random phases and amplitudes
+    /// derived from a power spectral density
+    ///
+    /// See [Signal::colored_noise]
+    ColoredNoise {
+        sampling_frequency_hz: f64,
+        frequencies_hz: Vec<f64>,
+        amplitudes: Vec<f64>,
+        phases: Vec<f64>,
+    },
+    /// Playback of a [TimeSeries]
+    Playback {
+        sampling_frequency_hz: f64,
+        time_series: TimeSeries,
+    },
     /// A symphony?
     Composite(Vec<Signal>),
 }
@@ -71,8 +106,140 @@ impl Signal {
             Ok(self)
         }
     }
+    /// Creates a colored noise from a one-sided power spectral density table
+    ///
+    /// The PSD table is a list of (frequency in Hz, PSD in units²/Hz) pairs with increasing frequencies.
+    /// The PSD is interpolated in log-log space on a frequency grid of resolution `frequency_resolution_hz`
+    /// up to the Nyquist frequency, and is zero outside the table.
+    /// The noise is the sum of sinusoids, one per grid frequency, with random phases;
+    /// it is periodic with a period of `1/frequency_resolution_hz`.
+    /// The phases are reproducible if a `seed` is given.
+    pub fn colored_noise(
+        psd: &[(f64, f64)],
+        sampling_frequency_hz: f64,
+        frequency_resolution_hz: f64,
+        seed: Option<u64>,
+    ) -> Result<Self, SignalsError> {
+        use rand::{RngExt, SeedableRng, rngs::StdRng};
+
+        if psd.len() < 2 || psd.windows(2).any(|x| x[1].0 <= x[0].0) {
+            return Err(SignalsError::Psd);
+        }
+        let mut rng =
+            seed.map_or_else(|| StdRng::from_rng(&mut rand::rng()), StdRng::seed_from_u64);
+        let f_max = psd[psd.len() - 1].0.min(0.5 * sampling_frequency_hz);
+        let df = frequency_resolution_hz;
+        let (frequencies_hz, amplitudes): (Vec<_>, Vec<_>) = (1..)
+            .map(|k| k as f64 * df)
+            .take_while(|&f| f <= f_max)
+            .filter(|&f| f >= psd[0].0)
+            .map(|f| (f, (2. * interpolate(psd, f) * df).sqrt()))
+            .unzip();
+        let phases = frequencies_hz
+            .iter()
+            .map(|_| 2. * std::f64::consts::PI * rng.random::<f64>())
+            .collect();
+        Ok(Signal::ColoredNoise {
+            sampling_frequency_hz,
+            frequencies_hz,
+            amplitudes,
+            phases,
+        })
+    }
+}
+/// Interpolates the PSD table in log-log space, or linearly if any value is not positive
+#[cfg(feature = "noise")]
+fn interpolate(psd: &[(f64, f64)], f: f64) -> f64 {
+    let i = psd.partition_point(|&(fi, _)| fi <= f);
+    if i == 0 || (i == psd.len() && f > psd[i - 1].0) {
+        return 0.;
+    }
+    if i == psd.len() {
+        return psd[i - 1].1;
+    }
+    let ((f0, s0), (f1, s1)) = (psd[i - 1], psd[i]);
+    if f0 > 0. && s0 > 0. && s1 > 0. {
+        (s0.ln() + (s1 / s0).ln() * (f / f0).ln() / (f1 / f0).ln()).exp()
+    } else {
+        s0 + (s1 - s0) * (f - f0) / (f1 - f0)
+    }
 }
+
+/// Feedback taps of maximal length linear feedback shift registers of order 2 to 20
+const PRBS_TAPS: [u32; 19] = [
+    0b11,
+    0b110,
+    0b1100,
+    0b1_0100,
+    0b11_0000,
+    0b110_0000,
+    0b1011_1000,
+    0b1_0001_0000,
+    0b10_0100_0000,
+    0b101_0000_0000,
+    0b1110_0000_1000,
+    0b1_1100_1000_0000,
+    0b11_1000_0000_0010,
+    0b110_0000_0000_0000,
+    0b1101_0000_0000_1000,
+    0b1_0010_0000_0000_0000,
+    0b10_0000_0100_0000_0000,
+    0b111_0010_0000_0000_0000,
+    0b1001_0000_0000_0000_0000,
+];
+
 impl Signal {
+    /// Creates a pseudo-random binary sequence of ±`amplitude`
+    ///
+    /// The sequence is generated with a maximal length linear feedback shift register
+    /// of the given `order` (2 to 20), its period is `2^order-1` bits
+    /// and each bit is held for `hold` samples.
+    pub fn prbs(order: u32, amplitude: f64, hold: usize) -> Result<Self, SignalsError> {
+        if !(2..=20).contains(&order) {
+            return Err(SignalsError::PrbsOrder(order));
+        }
+        let taps = PRBS_TAPS[order as usize - 2];
+        let mask = (1u32 << order) - 1;
+        let mut state = 1u32;
+        let sequence = (0..mask)
+            .map(|_| {
+                let bit = (state & taps).count_ones() & 1;
+                state = ((state << 1) | bit) & mask;
+                bit == 1
+            })
+            .collect();
+        Ok(Signal::Prbs {
+            amplitude,
+            hold: hold.max(1),
+            sequence,
+        })
+    }
+    /// Creates a multisine of the given frequencies, each sinusoid with the same `amplitude`
+    ///
+    /// The phases of the sinusoids are the Schroeder phases `-πk(k-1)/K`, `k=1,...,K`,
+    /// that minimize the crest factor of the multisine
+    pub fn multisine(amplitude: f64, sampling_frequency_hz: f64, frequencies_hz: Vec<f64>) -> Self {
+        let n = frequencies_hz.len() as f64;
+        let phases = (1..=frequencies_hz.len())
+            .map(|k| {
+                let k = k as f64;
+                -std::f64::consts::PI * k * (k - 1.) / n
+            })
+            .collect();
+        Signal::Multisine {
+            sampling_frequency_hz,
+            amplitudes: vec![amplitude; frequencies_hz.len()],
+            frequencies_hz,
+            phases,
+        }
+    }
+    /// Creates the playback of a [TimeSeries] sampled at the given frequency
+    pub fn playback(time_series: TimeSeries, sampling_frequency_hz: f64) -> Self {
+        Signal::Playback {
+            sampling_frequency_hz,
+            time_series,
+        }
+    }
     /// Returns the signal value at step `i`
     pub fn get(&self, i: usize) -> f64 {
         use Signal::*;
@@ -117,12 +284,48 @@ impl Signal {
             }
             #[cfg(feature = "noise")]
             WhiteNoise(noise) => noise.sample(&mut rand::rng()),
+            Prbs {
+                amplitude,
+                hold,
+                sequence,
+            } => {
+                if sequence[(i / hold) % sequence.len()] {
+                    *amplitude
+                } else {
+                    -amplitude
+                }
+            }
+            Multisine {
+                sampling_frequency_hz,
+                frequencies_hz,
+                amplitudes,
+                phases,
+            }
+            | ColoredNoise {
+                sampling_frequency_hz,
+                frequencies_hz,
+                amplitudes,
+                phases,
+            } => {
+                let t = i as f64 / sampling_frequency_hz;
+                frequencies_hz
+                    .iter()
+                    .zip(amplitudes)
+                    .zip(phases)
+                    .map(|((f, a), p)| a * (2f64 * std::f64::consts::PI * f * t + p).sin())
+                    .sum()
+            }
+            Playback {
+                sampling_frequency_hz,
+                time_series,
+            } => time_series.get(i as f64 / sampling_frequency_hz),
             Composite(signals) => signals.iter().map(|signal| signal.get(i)).sum(),
         }
     }
 }
 
 /// Multiplex signals generator
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone)]
 pub struct Signals {
     size: usize,
@@ -236,6 +439,7 @@ impl Add for Signal {
 }
 impl TimerMarker for Signals {}
 impl Update for Signals {}
+impl ClientHooks for Signals {}
 impl<U: UniqueIdentifier<DataType = Vec<f64>>> Write<U> for Signals {
     fn write(&mut self) -> Option<Data<U>> {
         // log::debug!("write {:?}", self.size);
@@ -254,6 +458,29 @@ impl<U: UniqueIdentifier<DataType = Vec<f64>>> Write<U> for Signals {
 pub enum SignalsError {
     #[error("Two many signal channels, should be only 1")]
     OneSignal,
+    #[error("PRBS order must be within 2 and 20, found {0}")]
+    PrbsOrder(u32),
+    #[error("PSD table must have at least 2 points with increasing frequencies")]
+    Psd,
+    #[error("time series is empty or time stamps ({0}) and values ({1}) sizes do not match")]
+    TimeSeries(usize, usize),
+    #[error("time series time stamps are not increasing")]
+    TimeStamps,
+    #[error("time series column {0} not found")]
+    Column(String),
+    #[error("failed to read time series")]
+    Io(#[from] std::io::Error),
+    #[error("failed to parse time series value")]
+    Parse(#[from] std::num::ParseFloatError),
+    #[cfg(feature = "csv")]
+    #[error("failed to read CSV time series")]
+    Csv(#[from] csv::Error),
+    #[cfg(feature = "parquet")]
+    #[error("failed to read Parquet time series")]
+    Parquet(#[from] parquet::errors::ParquetError),
+    #[cfg(feature = "parquet")]
+    #[error("failed to convert Parquet time series")]
+    Arrow(#[from] apache_arrow::error::ArrowError),
 }
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone)]
@@ -277,6 +504,7 @@ impl TryFrom<Signals> for OneSignal {
     }
 }
 impl Update for OneSignal {}
+impl ClientHooks for OneSignal {}
 impl<U: UniqueIdentifier<DataType = f64>> Write<U> for OneSignal {
     fn write(&mut self) -> Option<Data<U>> {
         if self.step < self.n_step {
@@ -289,3 +517,51 @@ impl<U: UniqueIdentifier<DataType = f64>> Write<U> for OneSignal {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prbs() {
+        let signal = Signal::prbs(7, 2., 3).unwrap();
+        let bits: Vec<_> = (0..127 * 3).step_by(3).map(|i| signal.get(i)).collect();
+        assert!(bits.iter().all(|x| x.abs() == 2.));
+        assert_eq!(bits.iter().filter(|&&x| x > 0.).count(), 64);
+        assert_eq!(signal.get(1), signal.get(0));
+        assert_eq!(signal.get(127 * 3 + 5), signal.get(5));
+    }
+
+    #[test]
+    fn playback() {
+        let time_series = TimeSeries::new(vec![0., 1., 2.], vec![0., 10., 0.]).unwrap();
+        let signal = Signal::playback(time_series.clone(), 4.) + Signal::Constant(1.);
+        let y: Vec<_> = (0..10).map(|i| signal.get(i)).collect();
+        assert_eq!(y, vec![1., 3.5, 6., 8.5, 11., 8.5, 6., 3.5, 1., 1.]);
+        let signal = Signal::playback(time_series.interpolation(Interpolation::Hold), 4.);
+        assert_eq!(signal.get(7), 10.);
+    }
+
+    #[cfg(feature = "noise")]
+    #[test]
+    fn colored_noise() {
+        let psd = [(1., 1e-2), (100., 1e-2)];
+        let signal = Signal::colored_noise(&psd, 1000., 0.5, Some(7)).unwrap();
+        let n = 2000;
+        let var = (0..n).map(|i| signal.get(i).powi(2)).sum::<f64>() / n as f64;
+        assert!((var - 0.995).abs() < 1e-9, "{var}");
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn csv_playback() {
+        let path = std::env::temp_dir().join("signals_playback.csv");
+        std::fs::write(&path, "time, x\n0.0, 1.0\n0.5, 2.0\n").unwrap();
+        let time_series = TimeSeries::from_csv(&path, "time", "x").unwrap();
+        assert_eq!(time_series.get(0.25), 1.5);
+        assert!(matches!(
+            TimeSeries::from_csv(&path, "time", "y"),
+            Err(SignalsError::Column(_))
+        ));
+    }
+}
@@ -0,0 +1,138 @@
+use super::SignalsError;
+#[cfg(any(feature = "csv", feature = "parquet"))]
+use std::path::Path;
+
+/// Interpolation of a [TimeSeries] in between samples
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Interpolation {
+    /// Linear interpolation between the 2 nearest samples
+    #[default]
+    Linear,
+    /// Holds the value of the previous sample
+    Hold,
+}
+
+/// Time series played back by [Signal::Playback](super::Signal::Playback)
+///
+/// Before the 1st sample and after the last sample, the time series is
+/// equal to the value of the 1st and last samples, respectively.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Default)]
+pub struct TimeSeries {
+    time_s: Vec<f64>,
+    values: Vec<f64>,
+    interpolation: Interpolation,
+}
+impl TimeSeries {
+    /// Creates a time series from the sample time stamps in seconds and the sample values
+    ///
+    /// The time stamps must be increasing
+    pub fn new(time_s: Vec<f64>, values: Vec<f64>) -> Result<Self, SignalsError> {
+        if time_s.is_empty() || time_s.len() != values.len() {
+            return Err(SignalsError::TimeSeries(time_s.len(), values.len()));
+        }
+        if time_s.windows(2).any(|t| t[1] <= t[0]) {
+            return Err(SignalsError::TimeStamps);
+        }
+        Ok(Self {
+            time_s,
+            values,
+            interpolation: Interpolation::default(),
+        })
+    }
+    /// Creates a time series from samples taken at the given rate in Hz
+    pub fn uniform(values: Vec<f64>, rate_hz: f64) -> Result<Self, SignalsError> {
+        let time_s = (0..values.len()).map(|i| i as f64 / rate_hz).collect();
+        Self::new(time_s, values)
+    }
+    /// Sets the [Interpolation] method
+    pub fn interpolation(mut self, interpolation: Interpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+    /// Returns the duration of the time series in seconds
+    pub fn duration(&self) -> f64 {
+        self.time_s[self.time_s.len() - 1] - self.time_s[0]
+    }
+    /// Returns the value of the time series at time `t` in seconds
+    pub fn get(&self, t: f64) -> f64 {
+        let i = self.time_s.partition_point(|&ti| ti <= t);
+        if i == 0 {
+            return self.values[0];
+        }
+        if i == self.time_s.len() {
+            return self.values[i - 1];
+        }
+        match self.interpolation {
+            Interpolation::Hold => self.values[i - 1],
+            Interpolation::Linear => {
+                let (t0, t1) = (self.time_s[i - 1], self.time_s[i]);
+                let (y0, y1) = (self.values[i - 1], self.values[i]);
+                y0 + (y1 - y0) * (t - t0) / (t1 - t0)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "csv")]
+impl TimeSeries {
+    /// Loads a time series from the `time` and `value` columns of a CSV file with headers
+    pub fn from_csv<P: AsRef<Path>>(
+        path: P,
+        time: &str,
+        value: &str,
+    ) -> Result<Self, SignalsError> {
+        let mut reader = csv::Reader::from_path(path)?;
+        let headers = reader.headers()?.clone();
+        let column = |name: &str| {
+            headers
+                .iter()
+                .position(|header| header.trim() == name)
+                .ok_or_else(|| SignalsError::Column(name.to_string()))
+        };
+        let (i_time, i_value) = (column(time)?, column(value)?);
+        let mut time_s = vec![];
+        let mut values = vec![];
+        for record in reader.records() {
+            let record = record?;
+            let field = |i: usize| record.get(i).unwrap_or_default().trim().parse::<f64>();
+            time_s.push(field(i_time)?);
+            values.push(field(i_value)?);
+        }
+        Self::new(time_s, values)
+    }
+}
+
+#[cfg(feature = "parquet")]
+impl TimeSeries {
+    /// Loads a time series from the `time` and `value` numeric columns of a Parquet file
+    pub fn from_parquet<P: AsRef<Path>>(
+        path: P,
+        time: &str,
+        value: &str,
+    ) -> Result<Self, SignalsError> {
+        use apache_arrow::{array::Float64Array, compute::cast, datatypes::DataType};
+        use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+        let file = std::fs::File::open(path)?;
+        let reader = ParquetRecordBatchReaderBuilder::try_new(file)?.build()?;
+        let mut time_s = vec![];
+        let mut values = vec![];
+        for batch in reader {
+            let batch = batch?;
+            for (name, data) in [(time, &mut time_s), (value, &mut values)] {
+                let column = batch
+                    .column_by_name(name)
+                    .ok_or_else(|| SignalsError::Column(name.to_string()))?;
+                let column = cast(column, &DataType::Float64)?;
+                let column = column
+                    .as_any()
+                    .downcast_ref::<Float64Array>()
+                    .ok_or_else(|| SignalsError::Column(name.to_string()))?;
+                data.extend(column.iter().map(|x| x.unwrap_or(f64::NAN)));
+            }
+        }
+        Self::new(time_s, values)
+    }
+}
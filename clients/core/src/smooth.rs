@@ -1,4 +1,4 @@
-use super::{Data, Read, UniqueIdentifier, Update, Write};
+use super::{ClientHooks, Data, Read, UniqueIdentifier, Update, Write};
 
 /// Smooth a signal with a time varying [Weight] input
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -18,6 +18,7 @@ impl Smooth {
     }
 }
 impl Update for Smooth {}
+impl ClientHooks for Smooth {}
 /// Weight signal
 pub enum Weight {}
 impl UniqueIdentifier for Weight {
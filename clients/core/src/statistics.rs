@@ -0,0 +1,476 @@
+//! # Streaming statistics
+//!
+//! [Statistics] accumulates the statistics of each channel of the input signal `U`
+//! over either the full run or a sliding window of samples ([Window]):
+//!  * the mean, the variance and the RMS,
+//!  * the minimum and the maximum,
+//!  * the percentiles, approximated with the P² algorithm over the full run
+//!    or computed exactly over a sliding window,
+//!  * the Welch averaged power spectral density (periodogram).
+//!
+//! Each statistic is written to an output UID wrapping the input UID, e.g. [`Rms<U>`](Rms),
+//! so it can be logged or scoped, and [Statistics::summary] returns a [Summary] of all the statistics
+//! that can be serialized at the end of a run.
+//!
+//! ```
+//! use gmt_dos_clients::statistics::{Rms, Statistics, Window};
+//! use interface::{Data, Read, UID, Update, Write};
+//!
+//! #[derive(UID)]
+//! enum U {}
+//!
+//! let mut stats = Statistics::<U>::new()
+//!     .window(Window::Sliding(2))
+//!     .percentiles(vec![50.]);
+//! for x in [1., -3., 4.] {
+//!     <_ as Read<U>>::read(&mut stats, Data::new(vec![x]));
+//!     stats.update();
+//! }
+//! let rms = <_ as Write<Rms<U>>>::write(&mut stats).unwrap();
+//! assert_eq!(*rms, vec![12.5f64.sqrt()]);
+//! let summary = stats.summary();
+//! assert_eq!((summary.min[0], summary.max[0]), (-3., 4.));
+//! assert_eq!(summary.percentiles[0].1, vec![0.5]);
+//! ```
+
+use super::{ClientHooks, Data, Read, UniqueIdentifier, Update, Write};
+use std::{marker::PhantomData, sync::Arc};
+
+mod order;
+mod p_square;
+mod periodogram;
+mod sliding;
+use p_square::PSquare;
+use periodogram::Periodogram;
+use sliding::Sliding;
+
+/// Statistics window
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Window {
+    /// All the samples since the start of the run
+    #[default]
+    Full,
+    /// The given number of most recent samples
+    Sliding(usize),
+}
+
+/// Running moments of a channel
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Moments {
+    n: usize,
+    mean: f64,
+    m2: f64,
+    min: f64,
+    max: f64,
+}
+impl Default for Moments {
+    fn default() -> Self {
+        Self {
+            n: 0,
+            mean: 0.,
+            m2: 0.,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+}
+impl Moments {
+    /// Welford update
+    fn add(&mut self, x: f64) {
+        self.n += 1;
+        let delta = x - self.mean;
+        self.mean += delta / self.n as f64;
+        self.m2 += delta * (x - self.mean);
+        self.min = self.min.min(x);
+        self.max = self.max.max(x);
+    }
+    /// Reverse Welford update, the minimum and the maximum are not updated
+    fn remove(&mut self, x: f64) {
+        if self.n <= 1 {
+            *self = Self::default();
+            return;
+        }
+        self.n -= 1;
+        let delta = x - self.mean;
+        self.mean -= delta / self.n as f64;
+        self.m2 = (self.m2 - delta * (x - self.mean)).max(0.);
+    }
+    fn variance(&self) -> f64 {
+        if self.n > 0 {
+            self.m2 / self.n as f64
+        } else {
+            0.
+        }
+    }
+    fn rms(&self) -> f64 {
+        (self.mean * self.mean + self.variance()).sqrt()
+    }
+}
+
+/// Streaming statistics of the input signal `U`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+pub struct Statistics<U> {
+    window: Window,
+    percentiles: Vec<f64>,
+    u: Arc<Vec<f64>>,
+    n_sample: usize,
+    moments: Vec<Moments>,
+    estimators: Vec<Vec<PSquare>>,
+    sliding: Vec<Sliding>,
+    periodogram: Option<Periodogram>,
+    uid: PhantomData<U>,
+}
+impl<U> Default for Statistics<U> {
+    fn default() -> Self {
+        Self {
+            window: Window::default(),
+            percentiles: vec![],
+            u: Default::default(),
+            n_sample: 0,
+            moments: vec![],
+            estimators: vec![],
+            sliding: vec![],
+            periodogram: None,
+            uid: PhantomData,
+        }
+    }
+}
+impl<U> Statistics<U> {
+    /// Creates a new statistics client over the full run
+    pub fn new() -> Self {
+        Default::default()
+    }
+    /// Sets the statistics [Window]
+    pub fn window(mut self, window: Window) -> Self {
+        if let Window::Sliding(n) = window {
+            assert!(n > 0, "gmt_dos-clients::Statistics: empty sliding window");
+        }
+        self.window = window;
+        self.periodogram = self
+            .periodogram
+            .take()
+            .map(|p| p.window_size(self.window_size()));
+        self
+    }
+    /// Sets the percentiles (between 0 and 100) to estimate
+    pub fn percentiles(mut self, percentiles: Vec<f64>) -> Self {
+        self.percentiles = percentiles;
+        self
+    }
+    /// Enables the periodogram averaged over blocks of `n_fft` samples (a power of 2)
+    /// of the signal sampled at the given frequency in Hz
+    pub fn periodogram(mut self, n_fft: usize, sampling_frequency_hz: f64) -> Self {
+        self.periodogram = Some(Periodogram::new(
+            n_fft,
+            sampling_frequency_hz,
+            self.window_size(),
+        ));
+        self
+    }
+    fn window_size(&self) -> Option<usize> {
+        match self.window {
+            Window::Full => None,
+            Window::Sliding(n) => Some(n),
+        }
+    }
+    /// Returns the number of samples processed since the start of the run
+    pub fn n_sample(&self) -> usize {
+        self.n_sample
+    }
+    /// Returns the number of channels
+    pub fn n_channel(&self) -> usize {
+        self.u.len()
+    }
+    fn window_moments(&self) -> Vec<Moments> {
+        match self.window {
+            Window::Full => self.moments.clone(),
+            Window::Sliding(_) => self.sliding.iter().map(Sliding::moments).collect(),
+        }
+    }
+    /// Returns the mean of each channel
+    pub fn mean(&self) -> Vec<f64> {
+        self.window_moments().iter().map(|m| m.mean).collect()
+    }
+    /// Returns the variance of each channel
+    pub fn variance(&self) -> Vec<f64> {
+        self.window_moments().iter().map(|m| m.variance()).collect()
+    }
+    /// Returns the RMS of each channel
+    pub fn rms(&self) -> Vec<f64> {
+        self.window_moments().iter().map(|m| m.rms()).collect()
+    }
+    /// Returns the minimum of each channel
+    pub fn min(&self) -> Vec<f64> {
+        self.window_moments().iter().map(|m| m.min).collect()
+    }
+    /// Returns the maximum of each channel
+    pub fn max(&self) -> Vec<f64> {
+        self.window_moments().iter().map(|m| m.max).collect()
+    }
+    /// Returns, for each percentile, the percentile of each channel
+    pub fn percentile(&self) -> Vec<Vec<f64>> {
+        match self.window {
+            Window::Full => (0..self.percentiles.len())
+                .map(|i| self.estimators.iter().map(|e| e[i].value()).collect())
+                .collect(),
+            Window::Sliding(_) => self
+                .percentiles
+                .iter()
+                .map(|p| self.sliding.iter().map(|s| s.quantile(p / 100.)).collect())
+                .collect(),
+        }
+    }
+    /// Returns the frequencies in Hz and the periodogram of each channel
+    pub fn psd(&self) -> Option<(Vec<f64>, Vec<Vec<f64>>)> {
+        self.periodogram
+            .as_ref()
+            .map(|p| (p.frequencies(), p.psd(self.n_channel())))
+    }
+    /// Returns a [Summary] of the statistics
+    pub fn summary(&self) -> Summary {
+        let moments = self.window_moments();
+        Summary {
+            window: self.window,
+            n_sample: self.n_sample,
+            mean: moments.iter().map(|m| m.mean).collect(),
+            variance: moments.iter().map(|m| m.variance()).collect(),
+            rms: moments.iter().map(|m| m.rms()).collect(),
+            min: moments.iter().map(|m| m.min).collect(),
+            max: moments.iter().map(|m| m.max).collect(),
+            percentiles: self
+                .percentiles
+                .iter()
+                .cloned()
+                .zip(self.percentile())
+                .collect(),
+            psd: self.psd(),
+        }
+    }
+}
+
+/// Summary of the [Statistics] of each channel
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Default)]
+pub struct Summary {
+    pub window: Window,
+    /// Number of samples since the start of the run
+    pub n_sample: usize,
+    pub mean: Vec<f64>,
+    pub variance: Vec<f64>,
+    pub rms: Vec<f64>,
+    pub min: Vec<f64>,
+    pub max: Vec<f64>,
+    /// Percentiles and the corresponding values of each channel
+    pub percentiles: Vec<(f64, Vec<f64>)>,
+    /// Frequencies in Hz and periodogram of each channel
+    pub psd: Option<(Vec<f64>, Vec<Vec<f64>>)>,
+}
+
+impl<U> Update for Statistics<U>
+where
+    U: UniqueIdentifier<DataType = Vec<f64>>,
+{
+    fn update(&mut self) {
+        if self.u.is_empty() {
+            return;
+        }
+        self.n_sample += 1;
+        if let Some(periodogram) = self.periodogram.as_mut() {
+            periodogram.add(&self.u);
+        }
+        match self.window {
+            Window::Full => {
+                if self.moments.is_empty() {
+                    self.moments = vec![Moments::default(); self.u.len()];
+                    self.estimators = vec![
+                        self.percentiles
+                            .iter()
+                            .map(|p| PSquare::new(p / 100.))
+                            .collect();
+                        self.u.len()
+                    ];
+                }
+                for ((m, e), &x) in self
+                    .moments
+                    .iter_mut()
+                    .zip(self.estimators.iter_mut())
+                    .zip(self.u.iter())
+                {
+                    m.add(x);
+                    e.iter_mut().for_each(|e| e.add(x));
+                }
+            }
+            Window::Sliding(n) => {
+                if self.sliding.is_empty() {
+                    self.sliding =
+                        vec![Sliding::new(n, !self.percentiles.is_empty()); self.u.len()];
+                }
+                for (s, &x) in self.sliding.iter_mut().zip(self.u.iter()) {
+                    s.push(x);
+                }
+            }
+        }
+    }
+}
+impl<U> ClientHooks for Statistics<U> where U: UniqueIdentifier<DataType = Vec<f64>> {}
+impl<U> Read<U> for Statistics<U>
+where
+    U: UniqueIdentifier<DataType = Vec<f64>>,
+{
+    fn read(&mut self, data: Data<U>) {
+        self.u = data.into_arc();
+    }
+}
+
+macro_rules! impl_statistic {
+    ($($(#[$meta:meta])* $uid:ident => $stat:ident),*) => {
+        $(
+        $(#[$meta])*
+        pub struct $uid<U>(PhantomData<U>);
+        impl<U: UniqueIdentifier> UniqueIdentifier for $uid<U> {
+            type DataType = Vec<f64>;
+        }
+        impl<U> Write<$uid<U>> for Statistics<U>
+        where
+            U: UniqueIdentifier<DataType = Vec<f64>>,
+        {
+            fn write(&mut self) -> Option<Data<$uid<U>>> {
+                Some(Data::new(self.$stat()))
+            }
+        }
+        )*
+    };
+}
+impl_statistic! {
+    /// Mean of each channel of `U`
+    Mean => mean,
+    /// Variance of each channel of `U`
+    Variance => variance,
+    /// RMS of each channel of `U`
+    Rms => rms,
+    /// Minimum of each channel of `U`
+    Min => min,
+    /// Maximum of each channel of `U`
+    Max => max
+}
+
+/// Percentiles of each channel of `U`, the percentiles of the 1st channel come first
+pub struct Percentiles<U>(PhantomData<U>);
+impl<U: UniqueIdentifier> UniqueIdentifier for Percentiles<U> {
+    type DataType = Vec<f64>;
+}
+impl<U> Write<Percentiles<U>> for Statistics<U>
+where
+    U: UniqueIdentifier<DataType = Vec<f64>>,
+{
+    fn write(&mut self) -> Option<Data<Percentiles<U>>> {
+        let percentile = self.percentile();
+        Some(Data::new(
+            (0..self.n_channel())
+                .flat_map(|c| percentile.iter().map(move |p| p[c]))
+                .collect(),
+        ))
+    }
+}
+/// Periodogram of each channel of `U`, the periodogram of the 1st channel comes first
+pub struct Psd<U>(PhantomData<U>);
+impl<U: UniqueIdentifier> UniqueIdentifier for Psd<U> {
+    type DataType = Vec<f64>;
+}
+impl<U> Write<Psd<U>> for Statistics<U>
+where
+    U: UniqueIdentifier<DataType = Vec<f64>>,
+{
+    fn write(&mut self) -> Option<Data<Psd<U>>> {
+        Some(Data::new(
+            self.psd()
+                .map(|(_, psd)| psd.into_iter().flatten().collect())
+                .unwrap_or_default(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    enum U {}
+    impl UniqueIdentifier for U {
+        type DataType = Vec<f64>;
+    }
+
+    fn run(stats: &mut Statistics<U>, data: impl Iterator<Item = Vec<f64>>) {
+        for x in data {
+            <_ as Read<U>>::read(stats, Data::new(x));
+            stats.update();
+        }
+    }
+
+    #[test]
+    fn full() {
+        let mut stats = Statistics::<U>::new().percentiles(vec![10., 50., 90.]);
+        // deterministic permutation of 0..1000
+        run(
+            &mut stats,
+            (0..1000).map(|i| vec![((i * 617) % 1000) as f64, 2.]),
+        );
+        let summary = stats.summary();
+        assert_eq!(summary.n_sample, 1000);
+        assert!((summary.mean[0] - 499.5).abs() < 1e-9);
+        assert!((summary.variance[0] - (1e6 - 1.) / 12.).abs() < 1e-6);
+        assert_eq!((summary.min[0], summary.max[0]), (0., 999.));
+        assert!((summary.rms[1] - 2.).abs() < 1e-12);
+        for (p, x) in summary.percentiles {
+            assert!((x[0] - 10. * p).abs() < 10., "{p}%: {}", x[0]);
+            assert!((x[1] - 2.).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn sliding() {
+        let n = 50;
+        let mut stats = Statistics::<U>::new()
+            .window(Window::Sliding(n))
+            .percentiles(vec![10., 50., 90.]);
+        let data: Vec<f64> = (0..1000).map(|i| ((i * 617) % 1000) as f64).collect();
+        for (k, &x) in data.iter().enumerate() {
+            run(&mut stats, std::iter::once(vec![x]));
+            let window = &data[(k + 1).saturating_sub(n)..=k];
+            let mean = window.iter().sum::<f64>() / window.len() as f64;
+            assert!((stats.mean()[0] - mean).abs() < 1e-9);
+            assert_eq!(
+                (stats.min()[0], stats.max()[0]),
+                (
+                    window.iter().cloned().fold(f64::INFINITY, f64::min),
+                    window.iter().cloned().fold(f64::NEG_INFINITY, f64::max)
+                )
+            );
+            let mut sorted = window.to_vec();
+            sorted.sort_by(f64::total_cmp);
+            assert_eq!(stats.percentile()[1][0], p_square::quantile(&sorted, 0.5));
+        }
+    }
+
+    #[test]
+    fn periodogram() {
+        // the PSD integrates to the variance
+        let fs = 100.;
+        let mut stats = Statistics::<U>::new()
+            .window(Window::Sliding(1024))
+            .periodogram(128, fs);
+        run(
+            &mut stats,
+            (0..4096).map(|i| vec![3. * (2. * std::f64::consts::PI * 12.5 * i as f64 / fs).sin()]),
+        );
+        let (f, psd) = stats.psd().unwrap();
+        let df = f[1] - f[0];
+        let var = psd[0].iter().sum::<f64>() * df;
+        assert!((var - 4.5).abs() < 1e-6, "{var}");
+        let peak = psd[0].iter().cloned().fold(0., f64::max);
+        assert_eq!(psd[0][16], peak);
+        assert_eq!(stats.variance().len(), 1);
+    }
+}
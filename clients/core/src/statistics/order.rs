@@ -0,0 +1,165 @@
+const NIL: usize = usize::MAX;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+struct Node {
+    value: f64,
+    priority: u64,
+    size: usize,
+    left: usize,
+    right: usize,
+}
+
+/// Order statistics of a multiset of samples
+///
+/// The samples are stored in a treap where each node records the size of its subtree,
+/// so inserting, removing and selecting the `k`th smallest sample are `O(log n)`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+pub(super) struct OrderStatistics {
+    nodes: Vec<Node>,
+    free: Vec<usize>,
+    root: usize,
+    seed: u64,
+}
+impl Default for OrderStatistics {
+    fn default() -> Self {
+        Self {
+            nodes: vec![],
+            free: vec![],
+            root: NIL,
+            seed: 0x9E37_79B9_7F4A_7C15,
+        }
+    }
+}
+impl OrderStatistics {
+    /// Returns the number of samples
+    pub fn len(&self) -> usize {
+        self.size(self.root)
+    }
+    /// Inserts a sample
+    pub fn insert(&mut self, value: f64) {
+        // xorshift priorities
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 7;
+        self.seed ^= self.seed << 17;
+        let node = Node {
+            value,
+            priority: self.seed,
+            size: 1,
+            left: NIL,
+            right: NIL,
+        };
+        let i = match self.free.pop() {
+            Some(i) => {
+                self.nodes[i] = node;
+                i
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        };
+        let (left, right) = self.split(self.root, value);
+        let left = self.merge(left, i);
+        self.root = self.merge(left, right);
+    }
+    /// Removes one occurrence of a sample
+    pub fn remove(&mut self, value: f64) {
+        self.root = self.remove_from(self.root, value);
+    }
+    /// Returns the `k`th smallest sample
+    pub fn select(&self, mut k: usize) -> f64 {
+        let mut t = self.root;
+        loop {
+            let node = &self.nodes[t];
+            let n_left = self.size(node.left);
+            match k.cmp(&n_left) {
+                std::cmp::Ordering::Less => t = node.left,
+                std::cmp::Ordering::Equal => return node.value,
+                std::cmp::Ordering::Greater => {
+                    k -= n_left + 1;
+                    t = node.right;
+                }
+            }
+        }
+    }
+    /// Quantile `p` with linear interpolation
+    pub fn quantile(&self, p: f64) -> f64 {
+        let n = self.len();
+        if n == 0 {
+            return f64::NAN;
+        }
+        let h = p * (n - 1) as f64;
+        let (i, f) = (h.floor() as usize, h.fract());
+        let x = self.select(i);
+        if i + 1 < n {
+            x + f * (self.select(i + 1) - x)
+        } else {
+            x
+        }
+    }
+    fn size(&self, t: usize) -> usize {
+        if t == NIL { 0 } else { self.nodes[t].size }
+    }
+    fn resize(&mut self, t: usize) {
+        let size = 1 + self.size(self.nodes[t].left) + self.size(self.nodes[t].right);
+        self.nodes[t].size = size;
+    }
+    /// Splits the subtree `t` into the samples smaller than `value` and the others
+    fn split(&mut self, t: usize, value: f64) -> (usize, usize) {
+        if t == NIL {
+            return (NIL, NIL);
+        }
+        if self.nodes[t].value.total_cmp(&value).is_lt() {
+            let (left, right) = self.split(self.nodes[t].right, value);
+            self.nodes[t].right = left;
+            self.resize(t);
+            (t, right)
+        } else {
+            let (left, right) = self.split(self.nodes[t].left, value);
+            self.nodes[t].left = right;
+            self.resize(t);
+            (left, t)
+        }
+    }
+    /// Merges the subtrees `a` and `b`, the samples of `a` being smaller than the samples of `b`
+    fn merge(&mut self, a: usize, b: usize) -> usize {
+        if a == NIL {
+            return b;
+        }
+        if b == NIL {
+            return a;
+        }
+        if self.nodes[a].priority > self.nodes[b].priority {
+            self.nodes[a].right = self.merge(self.nodes[a].right, b);
+            self.resize(a);
+            a
+        } else {
+            self.nodes[b].left = self.merge(a, self.nodes[b].left);
+            self.resize(b);
+            b
+        }
+    }
+    fn remove_from(&mut self, t: usize, value: f64) -> usize {
+        if t == NIL {
+            return NIL;
+        }
+        match value.total_cmp(&self.nodes[t].value) {
+            std::cmp::Ordering::Equal => {
+                self.free.push(t);
+                self.merge(self.nodes[t].left, self.nodes[t].right)
+            }
+            std::cmp::Ordering::Less => {
+                self.nodes[t].left = self.remove_from(self.nodes[t].left, value);
+                self.resize(t);
+                t
+            }
+            std::cmp::Ordering::Greater => {
+                self.nodes[t].right = self.remove_from(self.nodes[t].right, value);
+                self.resize(t);
+                t
+            }
+        }
+    }
+}
@@ -0,0 +1,99 @@
+/// P² streaming quantile estimator
+///
+/// R. Jain and I. Chlamtac, "The P² algorithm for dynamic calculation of quantiles
+/// and histograms without storing observations", Communications of the ACM, 28(10), 1985
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+pub(super) struct PSquare {
+    p: f64,
+    q: Vec<f64>,
+    n: [f64; 5],
+    np: [f64; 5],
+    dn: [f64; 5],
+}
+impl PSquare {
+    /// Creates a new estimator of the quantile `p` within [0,1]
+    pub fn new(p: f64) -> Self {
+        Self {
+            p,
+            q: Vec::with_capacity(5),
+            n: [0., 1., 2., 3., 4.],
+            np: [0., 2. * p, 4. * p, 2. + 2. * p, 4.],
+            dn: [0., p / 2., p, (1. + p) / 2., 1.],
+        }
+    }
+    /// Adds an observation
+    pub fn add(&mut self, x: f64) {
+        if self.q.len() < 5 {
+            self.q.push(x);
+            if self.q.len() == 5 {
+                self.q.sort_by(f64::total_cmp);
+            }
+            return;
+        }
+        let q = &mut self.q;
+        let k = if x < q[0] {
+            q[0] = x;
+            0
+        } else if x >= q[4] {
+            q[4] = x;
+            3
+        } else {
+            (0..4).find(|&i| x < q[i + 1]).unwrap()
+        };
+        self.n[k + 1..].iter_mut().for_each(|n| *n += 1.);
+        self.np
+            .iter_mut()
+            .zip(&self.dn)
+            .for_each(|(np, dn)| *np += dn);
+        for i in 1..4 {
+            let d = self.np[i] - self.n[i];
+            if (d >= 1. && self.n[i + 1] - self.n[i] > 1.)
+                || (d <= -1. && self.n[i - 1] - self.n[i] < -1.)
+            {
+                let d = d.signum();
+                let qp = self.parabolic(i, d);
+                self.q[i] = if self.q[i - 1] < qp && qp < self.q[i + 1] {
+                    qp
+                } else {
+                    self.linear(i, d)
+                };
+                self.n[i] += d;
+            }
+        }
+    }
+    fn parabolic(&self, i: usize, d: f64) -> f64 {
+        let (q, n) = (&self.q, &self.n);
+        q[i] + d / (n[i + 1] - n[i - 1])
+            * ((n[i] - n[i - 1] + d) * (q[i + 1] - q[i]) / (n[i + 1] - n[i])
+                + (n[i + 1] - n[i] - d) * (q[i] - q[i - 1]) / (n[i] - n[i - 1]))
+    }
+    fn linear(&self, i: usize, d: f64) -> f64 {
+        let j = if d > 0. { i + 1 } else { i - 1 };
+        self.q[i] + d * (self.q[j] - self.q[i]) / (self.n[j] - self.n[i])
+    }
+    /// Returns the quantile estimate
+    pub fn value(&self) -> f64 {
+        if self.q.len() < 5 {
+            let mut q = self.q.clone();
+            q.sort_by(f64::total_cmp);
+            quantile(&q, self.p)
+        } else {
+            self.q[2]
+        }
+    }
+}
+
+/// Quantile `p` of sorted data with linear interpolation
+pub(super) fn quantile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return f64::NAN;
+    }
+    let h = p * (sorted.len() - 1) as f64;
+    let (i, f) = (h.floor() as usize, h.fract());
+    if i + 1 < sorted.len() {
+        sorted[i] + f * (sorted[i + 1] - sorted[i])
+    } else {
+        sorted[i]
+    }
+}
@@ -0,0 +1,109 @@
+use crate::fft::{fft, hann};
+use num_complex::Complex;
+use std::collections::VecDeque;
+
+/// Welch averaged periodogram of blocks of `n_fft` samples overlapping by 50%
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+pub(super) struct Periodogram {
+    sampling_frequency_hz: f64,
+    window: Vec<f64>,
+    block: VecDeque<Vec<f64>>,
+    // periodograms of the blocks within the sliding window
+    blocks: VecDeque<Vec<Vec<f64>>>,
+    n_block: Option<usize>,
+    sum: Vec<Vec<f64>>,
+    n_average: usize,
+}
+impl Periodogram {
+    pub fn new(n_fft: usize, sampling_frequency_hz: f64, window_size: Option<usize>) -> Self {
+        assert!(
+            n_fft.is_power_of_two() && n_fft > 1,
+            "gmt_dos-clients::Statistics: the periodogram size must be a power of 2, found {n_fft}"
+        );
+        Self {
+            sampling_frequency_hz,
+            window: hann(n_fft),
+            block: VecDeque::with_capacity(n_fft),
+            blocks: VecDeque::new(),
+            n_block: None,
+            sum: vec![],
+            n_average: 0,
+        }
+        .window_size(window_size)
+    }
+    /// Averages the periodograms of the blocks within the last `window_size` samples
+    pub fn window_size(mut self, window_size: Option<usize>) -> Self {
+        let n_fft = self.n_fft();
+        self.n_block = window_size.map(|n| (2 * n / n_fft).saturating_sub(1).max(1));
+        self
+    }
+    pub fn n_fft(&self) -> usize {
+        self.window.len()
+    }
+    /// Returns the frequencies in Hz of the periodogram
+    pub fn frequencies(&self) -> Vec<f64> {
+        let n = self.n_fft();
+        (0..=n / 2)
+            .map(|k| k as f64 * self.sampling_frequency_hz / n as f64)
+            .collect()
+    }
+    pub fn add(&mut self, x: &[f64]) {
+        self.block.push_back(x.to_vec());
+        let n = self.n_fft();
+        if self.block.len() < n {
+            return;
+        }
+        let scale = self.sampling_frequency_hz * self.window.iter().map(|w| w * w).sum::<f64>();
+        let psd: Vec<Vec<f64>> = (0..x.len())
+            .map(|c| {
+                let mut y: Vec<_> = self
+                    .block
+                    .iter()
+                    .zip(&self.window)
+                    .map(|(x, w)| Complex::new(x[c] * w, 0.))
+                    .collect();
+                fft(&mut y);
+                y[..=n / 2]
+                    .iter()
+                    .enumerate()
+                    .map(|(k, y)| {
+                        let one_sided = if k == 0 || k == n / 2 { 1. } else { 2. };
+                        one_sided * y.norm_sqr() / scale
+                    })
+                    .collect()
+            })
+            .collect();
+        self.block.drain(..n / 2);
+        if self.sum.is_empty() {
+            self.sum = vec![vec![0.; n / 2 + 1]; x.len()];
+        }
+        accumulate(&mut self.sum, &psd, 1.);
+        self.n_average += 1;
+        if let Some(n_block) = self.n_block {
+            self.blocks.push_back(psd);
+            if self.blocks.len() > n_block {
+                let psd = self.blocks.pop_front().unwrap();
+                accumulate(&mut self.sum, &psd, -1.);
+                self.n_average -= 1;
+            }
+        }
+    }
+    /// Returns the averaged periodogram of each channel, or zeros if no block has been processed yet
+    pub fn psd(&self, n_channel: usize) -> Vec<Vec<f64>> {
+        if self.n_average == 0 {
+            return vec![vec![0.; self.n_fft() / 2 + 1]; n_channel];
+        }
+        let n = self.n_average as f64;
+        self.sum
+            .iter()
+            .map(|psd| psd.iter().map(|p| (p / n).max(0.)).collect())
+            .collect()
+    }
+}
+
+fn accumulate(sum: &mut [Vec<f64>], psd: &[Vec<f64>], sign: f64) {
+    sum.iter_mut()
+        .zip(psd)
+        .for_each(|(s, p)| s.iter_mut().zip(p).for_each(|(s, p)| *s += sign * p));
+}
@@ -0,0 +1,92 @@
+use super::{Moments, order::OrderStatistics};
+use std::collections::VecDeque;
+
+/// Statistics of a channel over a sliding window of samples
+///
+/// The moments are updated as the samples enter and leave the window,
+/// the minimum and the maximum are at the front of monotonic queues
+/// and the percentiles are selected from the [OrderStatistics] of the window samples.
+/// The moments are recomputed from the window samples each time the window has been renewed,
+/// to prevent rounding errors from accumulating.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+pub(super) struct Sliding {
+    size: usize,
+    samples: VecDeque<f64>,
+    moments: Moments,
+    n_sample: usize,
+    // (sample index, sample) in increasing (min) and decreasing (max) order
+    min: VecDeque<(usize, f64)>,
+    max: VecDeque<(usize, f64)>,
+    order: Option<OrderStatistics>,
+}
+impl Sliding {
+    /// Creates a new sliding window of `size` samples,
+    /// with the order statistics if `percentiles` is true
+    pub fn new(size: usize, percentiles: bool) -> Self {
+        Self {
+            size,
+            samples: VecDeque::with_capacity(size + 1),
+            moments: Moments::default(),
+            n_sample: 0,
+            min: VecDeque::new(),
+            max: VecDeque::new(),
+            order: percentiles.then(OrderStatistics::default),
+        }
+    }
+    /// Adds a sample to the window, removing the oldest sample if the window is full
+    pub fn push(&mut self, x: f64) {
+        let i = self.n_sample;
+        self.n_sample += 1;
+        self.samples.push_back(x);
+        self.moments.add(x);
+        while self.min.back().is_some_and(|&(_, y)| y >= x) {
+            self.min.pop_back();
+        }
+        self.min.push_back((i, x));
+        while self.max.back().is_some_and(|&(_, y)| y <= x) {
+            self.max.pop_back();
+        }
+        self.max.push_back((i, x));
+        if let Some(order) = self.order.as_mut() {
+            order.insert(x);
+        }
+        if self.samples.len() > self.size {
+            let Some(y) = self.samples.pop_front() else {
+                return;
+            };
+            if let Some(order) = self.order.as_mut() {
+                order.remove(y);
+            }
+            let oldest = i + 1 - self.size;
+            while self.min.front().is_some_and(|&(j, _)| j < oldest) {
+                self.min.pop_front();
+            }
+            while self.max.front().is_some_and(|&(j, _)| j < oldest) {
+                self.max.pop_front();
+            }
+            if oldest.is_multiple_of(self.size) {
+                self.moments = self.samples.iter().fold(Moments::default(), |mut m, &x| {
+                    m.add(x);
+                    m
+                });
+            } else {
+                self.moments.remove(y);
+            }
+        }
+    }
+    /// Returns the moments of the window samples
+    pub fn moments(&self) -> Moments {
+        Moments {
+            min: self.min.front().map_or(f64::INFINITY, |&(_, x)| x),
+            max: self.max.front().map_or(f64::NEG_INFINITY, |&(_, x)| x),
+            ..self.moments.clone()
+        }
+    }
+    /// Returns the quantile `p` of the window samples
+    pub fn quantile(&self, p: f64) -> f64 {
+        self.order
+            .as_ref()
+            .map_or(f64::NAN, |order| order.quantile(p))
+    }
+}
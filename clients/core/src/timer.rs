@@ -1,5 +1,5 @@
 use super::Progress;
-use interface::{Data, Tick, Update, Write};
+use interface::{ClientHooks, Data, Tick, Update, Write};
 
 /// Simple digital timer
 #[derive(Default, Debug)]
@@ -50,6 +50,7 @@ where
         self.tick -= 1;
     }
 }
+impl<T> ClientHooks for Timer<T> where T: Progress + Send + Sync {}
 
 impl<T> Write<Tick> for Timer<T>
 where
@@ -1,5 +1,5 @@
 use faer::Mat;
-use interface::{Data, Read, UniqueIdentifier, Update, Write, filing::Codec};
+use interface::{ClientHooks, Data, Read, UniqueIdentifier, Update, Write, filing::Codec};
 use serde::{Deserialize, Serialize};
 use std::{
     fmt::{Display, Formatter},
@@ -479,6 +479,12 @@ where
         );
     }
 }
+impl<M, C> ClientHooks for Reconstructor<M, C>
+where
+    M: Modality + Default + Send + Sync,
+    C: CalibProps<M> + Default + Send + Sync,
+{
+}
 
 impl<M, C, U> Read<U> for Reconstructor<M, C>
 where
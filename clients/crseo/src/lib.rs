@@ -27,7 +27,7 @@ pub use sensor::SensorBuilder;
 use std::ops::{Deref, DerefMut};
 
 pub use crseo;
-use interface::{doublet::UidTuple, Data, TimerMarker, UniqueIdentifier, Update, Write};
+use interface::{doublet::UidTuple, ClientHooks, Data, TimerMarker, UniqueIdentifier, Update, Write};
 
 mod error;
 pub use error::{CeoError, Result};
@@ -102,6 +102,7 @@ impl<P: Processing + Send + Sync> Update for Processor<P> {
     //     self.processing();
     // }
 }
+impl<P: Processing + Send + Sync> ClientHooks for Processor<P> {}
 
 impl<P, T> Write<T> for Processor<P>
 where
@@ -4,7 +4,7 @@ use std::{
 };
 
 use crseo::CrseoError;
-use interface::{Data, Read, UniqueIdentifier, Update, Write};
+use interface::{ClientHooks, Data, Read, UniqueIdentifier, Update, Write};
 
 #[derive(Debug, thiserror::Error)]
 pub enum CalibratingError {
@@ -50,6 +50,13 @@ where
     //     &self.calibrator * &self.data
     // }
 }
+impl<C: Calibrating + Send + Sync> ClientHooks for Calibration<C>
+where
+    <C as Calibrating>::ProcessorData: Sync + Send,
+    <C as Calibrating>::Output: Send + Sync,
+    // for<'a> &'a C: Mul<&'a C::ProcessorData, Output = ()>,
+{
+}
 
 impl<C: Calibrating + Send + Sync, T: UniqueIdentifier<DataType = C::ProcessorData>> Read<T>
     for Calibration<C>
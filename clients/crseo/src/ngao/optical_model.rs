@@ -6,7 +6,7 @@ use crseo::{
 };
 use gmt_dos_clients_domeseeing::{DomeSeeing, DomeSeeingError};
 use gmt_dos_clients_io::optics::SegmentD7Piston;
-use interface::{select::Selector, Data, TimerMarker, UniqueIdentifier, Units, Update, Write};
+use interface::{select::Selector, ClientHooks, Data, TimerMarker, UniqueIdentifier, Units, Update, Write};
 
 use crate::{DetectorFrame, OpticalModelBuilder};
 
@@ -88,6 +88,12 @@ where
         }
     }
 }
+impl<T> ClientHooks for OpticalModel<T>
+where
+    T: SegmentWiseSensor,
+    OpticalModel<T>: Send + Sync,
+{
+}
 
 // impl<T> Write<GuideStar> for OpticalModel<T> {
 //     fn write(&mut self) -> Option<Data<GuideStar>> {
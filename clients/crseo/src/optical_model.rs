@@ -3,7 +3,7 @@ use crate::{
     sensors::{NoSensor, SensorPropagation},
 };
 use crseo::{Atmosphere, FromBuilder, Gmt, PSSnEstimates, Source};
-use interface::{Units, Update};
+use interface::{ClientHooks, Units, Update};
 
 pub mod builder;
 mod imaging;
@@ -115,3 +115,4 @@ impl<T: SensorPropagation> Update for OpticalModel<T> {
         }
     }
 }
+impl<T: SensorPropagation> ClientHooks for OpticalModel<T> {}
@@ -8,7 +8,7 @@ use crseo::{
     builders::{CentroidingBuilder, ImagingBuilder},
 };
 use gmt_dos_clients_io::optics::{Dev, Frame};
-use interface::{Data, Read, UniqueIdentifier, Update, Write};
+use interface::{ClientHooks, Data, Read, UniqueIdentifier, Update, Write};
 use std::{fmt::Display, marker::PhantomData, sync::Arc};
 
 #[derive(Debug, thiserror::Error)]
@@ -189,6 +189,7 @@ impl<K: CentroidKind> Update for CentroidsProcessing<K> {
         });
     }
 }
+impl<K: CentroidKind> ClientHooks for CentroidsProcessing<K> {}
 
 impl<K: CentroidKind> Read<Frame<Dev>> for CentroidsProcessing<K> {
     fn read(&mut self, data: Data<Frame<Dev>>) {
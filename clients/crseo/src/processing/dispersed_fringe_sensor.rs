@@ -5,7 +5,7 @@ use gmt_dos_clients_io::optics::{
     Dev,
     dispersed_fringe_sensor::{DfsFftFrame, Intercepts},
 };
-use interface::{Data, Read, Update, Write};
+use interface::{ClientHooks, Data, Read, Update, Write};
 use serde::{Deserialize, Serialize};
 
 use crate::sensors::DispersedFringeSensor;
@@ -292,6 +292,7 @@ impl Update for DispersedFringeSensorProcessing {
         self.intercept();
     }
 }
+impl ClientHooks for DispersedFringeSensorProcessing {}
 
 impl Read<DfsFftFrame<Dev>> for DispersedFringeSensorProcessing {
     fn read(&mut self, data: Data<DfsFftFrame<Dev>>) {
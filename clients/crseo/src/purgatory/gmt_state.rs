@@ -42,6 +42,7 @@ impl From<(crate::clients::arrow_client::Arrow, usize, Option<usize>)> for GmtSt
 }
 
 impl crate::Update for GmtState {}
+impl crate::ClientHooks for GmtState {}
 #[cfg(feature = "fem")]
 impl crate::io::Write<fem::fem_io::OSSM1Lcl> for GmtState {
     fn write(&mut self) -> Option<std::sync::Arc<crate::io::Data<fem::fem_io::OSSM1Lcl>>> {
@@ -2,7 +2,7 @@ use crate::ngao::GuideStar;
 use gmt_dos_clients_io::optics::{
     SegmentD7Piston, SegmentDWfe, SegmentPiston, SegmentWfe, SegmentWfeRms, WfeRms,
 };
-use interface::{Data, Read, Size, UniqueIdentifier, Update, Write};
+use interface::{ClientHooks, Data, Read, Size, UniqueIdentifier, Update, Write};
 
 /// Optical metrics derived from the wavefront map
 ///
@@ -17,6 +17,7 @@ pub struct WavefrontStats<const N_SRC: usize = 1> {
 }
 
 impl<const N_SRC: usize> Update for WavefrontStats<N_SRC> {}
+impl<const N_SRC: usize> ClientHooks for WavefrontStats<N_SRC> {}
 
 impl<const N_SRC: usize> Read<GuideStar> for WavefrontStats<N_SRC> {
     fn read(&mut self, data: Data<GuideStar>) {
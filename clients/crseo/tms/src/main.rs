@@ -1,6 +1,6 @@
 use crseo::FromBuilder;
 use gmt_dos_actors::prelude::*;
-use gmt_dos_clients::interface::{Data, TimerMarker, Update, Write};
+use gmt_dos_clients::interface::{ClientHooks, Data, TimerMarker, Update, Write};
 use gmt_dos_clients::{Signal, Signals, Tick, Timer};
 use gmt_dos_clients_arrow::Arrow;
 use gmt_dos_clients_crseo::{OpticalModel, PointingError, SegmentTipTilt, TipTilt};
@@ -30,6 +30,7 @@ impl PointingErrorRandomGenerator {
     }
 }
 impl Update for PointingErrorRandomGenerator {}
+impl ClientHooks for PointingErrorRandomGenerator {}
 impl Write<PointingError> for PointingErrorRandomGenerator {
     fn write(&mut self) -> Option<Data<PointingError>> {
         let zen = self.normal.sample(&mut self.rng);
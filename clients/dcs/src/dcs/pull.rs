@@ -1,6 +1,6 @@
 use std::io;
 
-use interface::{UniqueIdentifier, Update, Write};
+use interface::{ClientHooks, UniqueIdentifier, Update, Write};
 
 use crate::{Connector, DcsData, Pull};
 
@@ -24,6 +24,12 @@ where
         }
     }
 }
+impl<S, D, const B: usize> ClientHooks for Dcs<Pull, S, D, B>
+where
+    S: Connector<Pull> + io::Read + Send + Sync,
+    D: Default + DcsData + Send + Sync,
+{
+}
 
 impl<U: DcsIO + UniqueIdentifier, S, D, const B: usize> Write<U> for Dcs<Pull, S, D, B>
 where
@@ -1,6 +1,6 @@
 use std::io;
 
-use interface::{Read, UniqueIdentifier, Update};
+use interface::{ClientHooks, Read, UniqueIdentifier, Update};
 
 use crate::{Connector, DcsData, Push};
 
@@ -23,6 +23,12 @@ where
         }
     }
 }
+impl<S, D, const B: usize> ClientHooks for Dcs<Push, S, D, B>
+where
+    S: Connector<Push> + io::Write + Send + Sync,
+    D: Default + DcsData + Send + Sync,
+{
+}
 
 impl<U: DcsIO + UniqueIdentifier, S, D, const B: usize> Read<U> for Dcs<Push, S, D, B>
 where
@@ -1,7 +1,7 @@
 use std::{collections::VecDeque, sync::Arc, time::Duration};
 
 use gmt_dos_clients_io::mount::{AverageMountEncoders, MountSetPoint};
-use interface::{Read, UID, UniqueIdentifier, Update, Write};
+use interface::{ClientHooks, Read, UID, UniqueIdentifier, Update, Write};
 use tai_time::{MonotonicTime, TaiClock};
 
 use crate::DcsIO;
@@ -24,6 +24,7 @@ pub enum OcsMountTrajectory {}
 impl DcsIO for OcsMountTrajectory {}
 
 impl Update for MountTrajectory {}
+impl ClientHooks for MountTrajectory {}
 
 impl<U: UniqueIdentifier<DataType = Vec<f64>>> Write<U> for MountTrajectory {
     fn write(&mut self) -> Option<interface::Data<U>> {
@@ -78,6 +79,7 @@ pub struct RelativeMountTrajectory {
 pub enum RelativeMountAxes {}
 
 impl Update for RelativeMountTrajectory {}
+impl ClientHooks for RelativeMountTrajectory {}
 
 impl Read<OcsMountTrajectory> for RelativeMountTrajectory {
     fn read(&mut self, data: interface::Data<OcsMountTrajectory>) {
@@ -2,7 +2,7 @@ use domeseeing::DomeSeeingOpd;
 use dos_actors::{
     io::{Data, Read, Write},
     prelude::*,
-    Update,
+    ClientHooks, Update,
 };
 use std::{fs::File, sync::Arc};
 
@@ -79,6 +79,7 @@ impl KarhunenLoeve {
     }
 }
 impl Update for KarhunenLoeve {}
+impl ClientHooks for KarhunenLoeve {}
 impl Read<DomeSeeingOpd> for KarhunenLoeve {
     /// Processing of the dome seeing OPD
     fn read(&mut self, data: Arc<Data<DomeSeeingOpd>>) {
@@ -1,7 +1,7 @@
 use dos_actors::{
     io::{Data, Read},
     prelude::*,
-    Update,
+    ClientHooks, Update,
 };
 use std::sync::Arc;
 
@@ -18,6 +18,7 @@ impl Std {
     }
 }
 impl Update for Std {}
+impl ClientHooks for Std {}
 impl<U: UniqueIdentifier<Data = Vec<f64>>> Read<U> for Std {
     fn read(&mut self, data: Arc<Data<U>>) {
         let (mut sum_squared, mut sum) =
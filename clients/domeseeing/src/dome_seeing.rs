@@ -1,5 +1,5 @@
 use gmt_dos_clients_io::domeseeing::DomeSeeingOpd;
-use interface::{Data, Size, Update, Write};
+use interface::{ClientHooks, Data, Size, Update, Write};
 #[cfg(feature = "npyz")]
 use std::io;
 #[cfg(not(feature = "object_store"))]
@@ -199,6 +199,7 @@ impl Update for DomeSeeing {
         self.opd = Iterator::next(self).map(|opd| opd.into());
     }
 }
+impl ClientHooks for DomeSeeing {}
 
 impl Size<DomeSeeingOpd> for DomeSeeing {
     fn len(&self) -> usize {
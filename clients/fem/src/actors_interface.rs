@@ -16,7 +16,7 @@ pub mod prelude {
     pub use std::sync::Arc;
 }
 
-use interface::{Left, Right, Units};
+use interface::{ClientHooks, Left, Right, Units};
 use prelude::*;
 
 #[cfg(all(fem, any(cfd2021, cfd2025)))]
@@ -42,6 +42,32 @@ where
         self.next();
     }
 }
+impl<S> ClientHooks for DiscreteModalSolver<S>
+where
+    DiscreteModalSolver<S>: Iterator,
+    S: Solver + Default + Send + Sync,
+{
+    /// Returns the FEM repository given by the `FEM_REPO` environment variable,
+    /// the number of modes and the FEM inputs and outputs
+    fn provenance(&self) -> Vec<(String, String)> {
+        let fem_types = |types: Vec<String>| format!("[{}]", types.join(", "));
+        std::env::var("FEM_REPO")
+            .map(|repo| ("FEM_REPO".to_string(), repo))
+            .into_iter()
+            .chain([
+                ("n_mode".to_string(), self.state_space.len().to_string()),
+                (
+                    "inputs".to_string(),
+                    fem_types(self.ins.iter().map(|x| x.fem_type()).collect()),
+                ),
+                (
+                    "outputs".to_string(),
+                    fem_types(self.outs.iter().map(|x| x.fem_type()).collect()),
+                ),
+            ])
+            .collect()
+    }
+}
 
 #[cfg(all(fem, m1, m2))]
 pub use gmt_dos_clients_optics_state::{M1State, M2State, OpticalState, OpticsState};
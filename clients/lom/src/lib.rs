@@ -18,7 +18,7 @@ use gmt_dos_clients_io::{
 use gmt_dos_clients_optics_state::{M1State, M2State};
 use gmt_dos_clients_optics_state::{OpticalState, OpticsState};
 use gmt_lom::{LOM, LinearOpticalModelError, Loader};
-use interface::{self, Data, Size, Units, Update, Write};
+use interface::{self, ClientHooks, Data, Size, Units, Update, Write};
 
 mod optical_sensitivity;
 pub use optical_sensitivity::OpticalSensitivities;
@@ -69,6 +69,7 @@ impl Update for LinearOpticalModel {
             .collect();
     }
 }
+impl ClientHooks for LinearOpticalModel {}
 impl interface::Read<OpticsState> for LinearOpticalModel
 where
     LinearOpticalModel: interface::Read<M1State> + interface::Read<M2State>,
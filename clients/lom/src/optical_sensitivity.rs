@@ -30,6 +30,7 @@ impl<const N: usize> OpticalSensitivities<N> {
 }
 
 impl<const N: usize> interface::Update for OpticalSensitivities<N> {}
+impl<const N: usize> interface::ClientHooks for OpticalSensitivities<N> {}
 
 impl<const N: usize> interface::Read<M2ASMVoiceCoilsMotion> for OpticalSensitivities<N> {
     fn read(&mut self, data: Data<M2ASMVoiceCoilsMotion>) {
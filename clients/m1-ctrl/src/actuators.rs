@@ -5,7 +5,7 @@ use center::CenterActuatorsController;
 #[cfg(any(not(m1_hp_force_extension), feature = "explicit-loadcells"))]
 use center_as_design::CenterActuatorsController;
 use gmt_dos_clients_io::gmt_m1::segment;
-use interface::{Data, Read, Size, Update, Write};
+use interface::{ClientHooks, Data, Read, Size, Update, Write};
 #[cfg(all(m1_hp_force_extension, not(feature = "explicit-loadcells")))]
 use outer::OuterActuatorsController;
 #[cfg(any(not(m1_hp_force_extension), feature = "explicit-loadcells"))]
@@ -96,6 +96,7 @@ impl<const ID: u8> Update for Actuators<ID> {
         self.step();
     }
 }
+impl<const ID: u8> ClientHooks for Actuators<ID> {}
 
 impl<const ID: u8> Read<segment::BarycentricForce<ID>> for Actuators<ID> {
     fn read(&mut self, data: Data<segment::BarycentricForce<ID>>) {
@@ -3,7 +3,7 @@ use gmt_dos_clients_io::gmt_m1::segment::{HardpointsForces, RBM};
 use hardpoints_dynamics::HardpointsDynamics;
 #[cfg(any(not(m1_hp_force_extension), feature = "explicit-loadcells"))]
 use hardpoints_dynamics_as_design::HardpointsDynamics;
-use interface::{Data, Read, Size, Update, Write};
+use interface::{ClientHooks, Data, Read, Size, Update, Write};
 
 mod loadcell;
 pub use loadcell::LoadCells;
@@ -71,6 +71,7 @@ impl<const ID: u8> Update for Hardpoints<ID> {
         self.dynamics.step();
     }
 }
+impl<const ID: u8> ClientHooks for Hardpoints<ID> {}
 
 impl<const ID: u8> Write<HardpointsForces<ID>> for Hardpoints<ID> {
     fn write(&mut self) -> Option<Data<HardpointsForces<ID>>> {
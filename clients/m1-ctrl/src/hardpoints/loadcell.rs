@@ -1,7 +1,7 @@
 use std::marker::PhantomData;
 
 use gmt_dos_clients_io::gmt_m1::segment;
-use interface::{Data, Read, Size, Update, Write};
+use interface::{ClientHooks, Data, Read, Size, Update, Write};
 use serde::{Deserialize, Serialize};
 
 use crate::Calibration;
@@ -139,6 +139,7 @@ impl<const ID: u8> Update for LoadCells<ID> {
         }
     }
 }
+impl<const ID: u8> ClientHooks for LoadCells<ID> {}
 
 impl<const ID: u8> Read<segment::HardpointsForces<ID>> for LoadCells<ID> {
     fn read(&mut self, data: Data<segment::HardpointsForces<ID>>) {
@@ -1,7 +1,7 @@
 use gmt_dos_clients_io::gmt_m2::asm::segment::{
     AsmCommand, FluidDampingForces, VoiceCoilsForces, VoiceCoilsMotion,
 };
-use interface::{Data, Read, Size, Update, Write};
+use interface::{ClientHooks, Data, Read, Size, Update, Write};
 use rayon::prelude::*;
 
 use gmt_m2_ctrl_asm_pid_damping::AsmPidDamping;
@@ -105,6 +105,7 @@ impl<const ID: u8> Update for AsmSegmentInnerController<ID> {
         }
     }
 }
+impl<const ID: u8> ClientHooks for AsmSegmentInnerController<ID> {}
 
 impl<const ID: u8> Read<AsmCommand<ID>> for AsmSegmentInnerController<ID> {
     fn read(&mut self, data: Data<AsmCommand<ID>>) {
@@ -44,7 +44,7 @@ Solving `J` for `p3` gives `p3 = Ap1 + Bp2` where
 */
 
 use gmt_dos_clients_io::gmt_m2::asm::segment::{AsmCommand, FaceSheetFigure};
-use interface::{Data, Read, Update, Write};
+use interface::{ClientHooks, Data, Read, Update, Write};
 use nalgebra::{DMatrix, DMatrixView, DVector};
 use std::{fmt::Display, ops::Mul, sync::Arc};
 
@@ -215,6 +215,7 @@ impl Update for Preprocessor {
             .map(|x| Arc::new(x));
     }
 }
+impl ClientHooks for Preprocessor {}
 impl Write<AsmCommand<7>> for Preprocessor {
     fn write(&mut self) -> Option<Data<AsmCommand<7>>> {
         self.positions.clone().as_ref().map(|x| x.into())
@@ -4,7 +4,7 @@ use gmt_dos_clients_io::gmt_m2::fsm::segment::{FsmCommand, PiezoForces, PiezoNod
 use gmt_m2_ctrl_fsm_piezo_7::FsmPiezo7;
 use gmt_m2_ctrl_fsm_piezo_135::FsmPiezo135;
 use gmt_m2_ctrl_fsm_piezo_246::FsmPiezo246;
-use interface::{Data, Read, Size, Update, Write};
+use interface::{ClientHooks, Data, Read, Size, Update, Write};
 use serde::{Deserialize, Serialize};
 
 /// Piezostack actuator controller
@@ -117,6 +117,7 @@ impl<const ID: u8> Update for FsmSegmentInnerController<ID> {
         self.piezo.y(&mut self.forces);
     }
 }
+impl<const ID: u8> ClientHooks for FsmSegmentInnerController<ID> {}
 
 impl<const ID: u8> Size<PiezoNodes<ID>> for FsmSegmentInnerController<ID> {
     fn len(&self) -> usize {
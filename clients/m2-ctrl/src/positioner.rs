@@ -1,7 +1,7 @@
 use gmt_dos_clients_fem::{Model, Switch};
 use gmt_dos_clients_io::gmt_m2::{M2PositionerForces, M2PositionerNodes, M2RigidBodyMotions};
 use gmt_fem::FEM;
-use interface::{Data, Read, Update, Write};
+use interface::{ClientHooks, Data, Read, Update, Write};
 use nalgebra as na;
 use serde::{Deserialize, Serialize};
 
@@ -124,6 +124,7 @@ impl Update for Positioners {
             });
     }
 }
+impl ClientHooks for Positioners {}
 
 impl Read<M2RigidBodyMotions> for Positioners {
     fn read(&mut self, data: Data<M2RigidBodyMotions>) {
@@ -18,7 +18,7 @@ use gmt_dos_clients_io::{
 use gmt_dos_clients_m2_ctrl::{ASMS, positioner::AsmsPositioners};
 use gmt_dos_clients_mount::Mount;
 use gmt_fem::FEM;
-use interface::{Data, Read, UID, UniqueIdentifier, Update, Write};
+use interface::{ClientHooks, Data, Read, UID, UniqueIdentifier, Update, Write};
 use matio_rs::MatFile;
 use nalgebra as na;
 use std::{env, path::Path, sync::Arc, time::Instant};
@@ -42,6 +42,7 @@ pub enum RBMCmd {}
 pub enum ActuatorCmd {}
 
 impl Update for Multiplex {}
+impl ClientHooks for Multiplex {}
 impl<U: UniqueIdentifier<DataType = Vec<f64>>> Read<U> for Multiplex {
     fn read(&mut self, data: Data<U>) {
         self.data = data.into_arc();
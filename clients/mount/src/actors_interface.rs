@@ -1,7 +1,7 @@
 use std::ptr;
 
 use gmt_dos_clients_io::mount::{MountEncoders, MountSetPoint, MountTorques};
-use interface::{Data, Read, Size, Update, Write};
+use interface::{ClientHooks, Data, Read, Size, Update, Write};
 
 use crate::Mount;
 
@@ -57,6 +57,7 @@ impl Update for Mount {
         self.drive.step();
     }
 }
+impl ClientHooks for Mount {}
 #[cfg(mount = "FDR")]
 impl Size<MountTorques> for Mount {
     fn len(&self) -> usize {
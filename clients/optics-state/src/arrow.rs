@@ -1,3 +1,30 @@
+/*!
+# Optical state logging
+
+The [OpticalState](crate::OpticalState) is logged with the [SerdeArrow] logger into a single column
+named after the UID, e.g. `OpticsState` for [OpticsState](crate::OpticsState),
+with a `Struct` entry for each mirror and, for each segment, the rigid body motions,
+the modal coefficients and the zero point.
+
+```
+use gmt_dos_clients_optics_state::{OpticalState, OpticsState, MirrorState, arrow::SerdeArrow};
+use interface::{Data, TryRead};
+
+let mut logging = SerdeArrow::builder()
+    .filename("optical_state")
+    .no_save()
+    .build();
+let state = OpticalState::new(MirrorState::from_rbms(&[0.; 42]), MirrorState::from_rbms(&[0.; 42]));
+<SerdeArrow as TryRead<OpticsState>>::try_read(&mut logging, Data::new(state))?;
+# Ok::<(), Box<dyn std::error::Error>>(())
+```
+
+The deprecated [OpticalStateArrow] logger writes the rigid body motions and the modal coefficients
+into the flat `M1RigidBodyMotions`, `M2RigidBodyMotions` and `M1ModeShapes` columns.
+*/
+
+pub use gmt_dos_clients_arrow::{SerdeArrow, SerdeArrowBuilder};
+
 use std::marker::PhantomData;
 
 use gmt_dos_clients_arrow::{Arrow, ArrowBuilder};
@@ -5,11 +32,12 @@ use gmt_dos_clients_io::{
     gmt_m1::{M1ModeShapes, M1RigidBodyMotions},
     gmt_m2::M2RigidBodyMotions,
 };
-use interface::{Data, Entry, Read, UniqueIdentifier, Update};
+use interface::{ClientHooks, Data, Entry, Read, UniqueIdentifier, Update};
 
 use crate::{M1State, M2State, OpticsState};
 
-/// [OpticsStateArrow] builder
+/// [OpticalStateArrow] builder
+#[deprecated = "use the SerdeArrow logger instead"]
 pub struct OpticalStateArrowBuilder<M1 = M1State, M2 = M2State>
 where
     M1: UniqueIdentifier,
@@ -20,6 +48,7 @@ where
     m2: PhantomData<M2>,
 }
 
+#[allow(deprecated)]
 impl<M1: UniqueIdentifier, M2: UniqueIdentifier> OpticalStateArrowBuilder<M1, M2> {
     /// Sets the parquet file name
     pub fn file_name<S: Into<String>>(self, file_name: S) -> Self {
@@ -30,6 +59,8 @@ impl<M1: UniqueIdentifier, M2: UniqueIdentifier> OpticalStateArrowBuilder<M1, M2
     }
 }
 
+/// [OpticalState](crate::OpticalState) Arrow logger into flat columns
+#[deprecated = "use the SerdeArrow logger instead"]
 pub struct OpticalStateArrow<M1 = M1State, M2 = M2State>
 where
     M1: UniqueIdentifier,
@@ -39,6 +70,7 @@ where
     m1: PhantomData<M1>,
     m2: PhantomData<M2>,
 }
+#[allow(deprecated)]
 impl<M1: UniqueIdentifier, M2: UniqueIdentifier> OpticalStateArrow<M1, M2> {
     /// Creates an instance of [OpticalStateArrowBuilder]
     pub fn builder() -> OpticalStateArrowBuilder<M1, M2> {
@@ -49,6 +81,7 @@ impl<M1: UniqueIdentifier, M2: UniqueIdentifier> OpticalStateArrow<M1, M2> {
         }
     }
 }
+#[allow(deprecated)]
 impl OpticalStateArrowBuilder<M1State, ()> {
     /// Builds an instance of [OpticalStateArrow]
     pub fn build(self, n_bm: usize) -> OpticalStateArrow<M1State, ()> {
@@ -62,6 +95,7 @@ impl OpticalStateArrowBuilder<M1State, ()> {
         }
     }
 }
+#[allow(deprecated)]
 impl OpticalStateArrowBuilder<M1State, M2RigidBodyMotions> {
     /// Builds an instance of [OpticalStateArrow]
     pub fn build(self, n_bm: usize) -> OpticalStateArrow<M1State, M2RigidBodyMotions> {
@@ -77,8 +111,12 @@ impl OpticalStateArrowBuilder<M1State, M2RigidBodyMotions> {
     }
 }
 
+#[allow(deprecated)]
 impl<M1: UniqueIdentifier, M2: UniqueIdentifier> Update for OpticalStateArrow<M1, M2> {}
+#[allow(deprecated)]
+impl<M1: UniqueIdentifier, M2: UniqueIdentifier> ClientHooks for OpticalStateArrow<M1, M2> {}
 
+#[allow(deprecated)]
 impl Read<OpticsState> for OpticalStateArrow<M1State, M2RigidBodyMotions> {
     fn read(&mut self, data: Data<OpticsState>) {
         let m1_rbms: Vec<_> = data
@@ -108,6 +146,7 @@ impl Read<OpticsState> for OpticalStateArrow<M1State, M2RigidBodyMotions> {
     }
 }
 
+#[allow(deprecated)]
 impl Read<OpticsState> for OpticalStateArrow<M1State, ()> {
     fn read(&mut self, data: Data<OpticsState>) {
         let m1_rbms: Vec<_> = data
@@ -1,6 +1,6 @@
 use std::ops::Add;
 
-use interface::{Data, Left, Read, Right, TimerMarker, Update, Write};
+use interface::{ClientHooks, Data, Left, Read, Right, TimerMarker, Update, Write};
 
 use super::{M1State, M2State, OpticsState};
 
@@ -91,6 +91,7 @@ impl OpticalState {
 }
 
 impl Update for OpticalState {}
+impl ClientHooks for OpticalState {}
 
 impl Read<M1State> for OpticalState {
     fn read(&mut self, data: Data<M1State>) {
@@ -6,11 +6,12 @@ use gmt_dos_clients_io::{
     gmt_m1::{self, M1ModeShapes, M1RigidBodyMotions},
     gmt_m2::M2RigidBodyMotions,
 };
-use interface::{Data, Left, Read, Right, Update, Write};
+use interface::{ClientHooks, Data, Left, Read, Right, Update, Write};
 
 use crate::{M1State, M2State, MirrorState, SegmentState};
 
 impl Update for MirrorState {}
+impl ClientHooks for MirrorState {}
 
 impl Read<M1State> for MirrorState {
     fn read(&mut self, data: Data<M1State>) {
@@ -4,7 +4,7 @@ use gmt_dos_actors::prelude::*;
 use gmt_dos_clients::signals::{Signal, Signals};
 use gmt_dos_clients_scope::server::Shot;
 use gmt_dos_clients_transceiver::Monitor;
-use interface::{Data, Read, UniqueIdentifier, Update, Write};
+use interface::{ClientHooks, Data, Read, UniqueIdentifier, Update, Write};
 
 pub struct SinSin {
     size: [usize; 2],
@@ -31,6 +31,7 @@ impl SinSin {
 }
 
 impl Update for SinSin {}
+impl ClientHooks for SinSin {}
 
 pub enum Wave {}
 impl UniqueIdentifier for Wave {
@@ -76,6 +76,7 @@ pub fn scopehub(_args: TokenStream, input: TokenStream) -> TokenStream {
             }
         }
         impl ::interface::Update for #hub {}
+        impl ::interface::ClientHooks for #hub {}
         #(
         impl ::interface::Read<#signal_ty> for #hub {
             fn read(&mut self, data: ::interface::Data<#signal_ty>)  {
@@ -150,6 +150,12 @@ where
     K: crate::ScopeKind + Send + Sync,
 {
 }
+impl<FU, K> interface::ClientHooks for XScope<FU, K>
+where
+    FU: UniqueIdentifier,
+    K: crate::ScopeKind + Send + Sync,
+{
+}
 
 impl<FU: UniqueIdentifier, K> std::fmt::Display for XScope<FU, K> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -45,7 +45,7 @@ use gmt_dos_clients_windloads::{
 };
 use gmt_fem::FEM;
 use interface::{
-    Read, Tick, Update, Write,
+    ClientHooks, Read, Tick, Update, Write,
     filing::Filing,
     units::{Mas, NM},
 };
@@ -245,6 +245,7 @@ pub struct MountCommand {
 impl Update for MountCommand {
     fn update(&mut self) {}
 }
+impl ClientHooks for MountCommand {}
 
 impl Read<SegmentTipTilt> for MountCommand {
     fn read(&mut self, data: interface::Data<SegmentTipTilt>) {
@@ -1,5 +1,5 @@
 use anyhow::{Context, Result};
-use interface::{Data, Read, UniqueIdentifier, Update, Write};
+use interface::{ClientHooks, Data, Read, UniqueIdentifier, Update, Write};
 use matio_rs::MatFile;
 use nalgebra::{DMatrix, DVector};
 use std::{env, mem, path::Path, sync::Arc};
@@ -34,6 +34,7 @@ impl Update for HexToRbm {
         );
     }
 }
+impl ClientHooks for HexToRbm {}
 
 impl<U: UniqueIdentifier<DataType = Vec<f64>>> Read<U> for HexToRbm {
     fn read(&mut self, data: Data<U>) {
@@ -1,6 +1,6 @@
 use anyhow::{Context, Result};
 use gmt_dos_clients_io::gmt_m2::{M2EdgeSensors, asm::M2ASMReferenceBodyNodes};
-use interface::{Data, Read, Update, Write};
+use interface::{ClientHooks, Data, Read, Update, Write};
 use matio_rs::MatFile;
 use std::{env, path::Path, sync::Arc};
 
@@ -70,6 +70,7 @@ impl Update for M2EdgeSensorsToRbm {
         self.data = Arc::new(data);
     }
 }
+impl ClientHooks for M2EdgeSensorsToRbm {}
 impl Read<M2ASMReferenceBodyNodes> for M2EdgeSensorsToRbm {
     fn read(&mut self, data: Data<M2ASMReferenceBodyNodes>) {
         self.rbms = data.into_arc();
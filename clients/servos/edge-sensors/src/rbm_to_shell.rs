@@ -1,6 +1,6 @@
 use anyhow::{Context, Result};
 use gmt_dos_clients_io::gmt_m1::M1EdgeSensors;
-use interface::{Data, Read, UniqueIdentifier, Update, Write};
+use interface::{ClientHooks, Data, Read, UniqueIdentifier, Update, Write};
 use io::M2EdgeSensorsAsRbms;
 use matio_rs::MatFile;
 use na::{DMatrix, DVector};
@@ -128,6 +128,7 @@ impl Update for RbmToShell {
         }
     }
 }
+impl ClientHooks for RbmToShell {}
 
 impl Read<M2EdgeSensorsAsRbms> for RbmToShell {
     fn read(&mut self, data: Data<M2EdgeSensorsAsRbms>) {
@@ -2,7 +2,7 @@ use std::ops::{Deref, DerefMut};
 
 use gmt_dos_clients_io::gmt_m2::M2RigidBodyMotions;
 use gmt_dos_clients_lom::LinearOpticalModel;
-use interface::{Data, Read, Update, Write};
+use interface::{ClientHooks, Data, Read, Update, Write};
 use io::M2SegmentPiston;
 
 #[derive(Debug, Clone)]
@@ -33,6 +33,7 @@ impl Update for M2Lom {
         self.0.update();
     }
 }
+impl ClientHooks for M2Lom {}
 
 impl Read<M2RigidBodyMotions> for M2Lom {
     fn read(&mut self, data: Data<M2RigidBodyMotions>) {
@@ -2,7 +2,7 @@ use std::ops::{Deref, DerefMut};
 
 use gmt_dos_clients_io::gmt_m2::asm::M2ASMReferenceBodyNodes;
 use gmt_dos_clients_lom::LinearOpticalModel;
-use interface::{Data, Read, Update, Write};
+use interface::{ClientHooks, Data, Read, Update, Write};
 use io::M2RBSegmentPiston;
 
 #[derive(Debug, Clone)]
@@ -33,6 +33,7 @@ impl Update for M2RBLom {
         self.0.update();
     }
 }
+impl ClientHooks for M2RBLom {}
 
 impl Read<M2ASMReferenceBodyNodes> for M2RBLom {
     fn read(&mut self, data: Data<M2ASMReferenceBodyNodes>) {
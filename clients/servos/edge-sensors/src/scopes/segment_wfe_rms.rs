@@ -16,7 +16,7 @@ use gmt_dos_actors::{
 use gmt_dos_clients_io::{gmt_m2::asm::M2ASMVoiceCoilsMotion, optics::SegmentWfeRms};
 use gmt_dos_clients_lom::LinearOpticalModel;
 use gmt_dos_clients_scope::server::{Monitor, Scope, XScope};
-use interface::{Data, Read, Update, Write};
+use interface::{ClientHooks, Data, Read, Update, Write};
 use io::{M1SegmentWfeRms, M2RBSegmentWfeRms, M2SegmentMeanActuator, M2SegmentWfeRms};
 pub use m1_lom::M1Lom;
 pub use m2_lom::M2Lom;
@@ -29,6 +29,7 @@ pub struct M2SegmentActuatorAverage {
     data: Arc<Vec<Arc<Vec<f64>>>>,
 }
 impl Update for M2SegmentActuatorAverage {}
+impl ClientHooks for M2SegmentActuatorAverage {}
 impl Read<M2ASMVoiceCoilsMotion> for M2SegmentActuatorAverage {
     fn read(&mut self, data: Data<M2ASMVoiceCoilsMotion>) {
         self.data = data.into_arc();
@@ -2,7 +2,7 @@ use std::ops::{Deref, DerefMut};
 
 use gmt_dos_clients_io::gmt_m1::M1RigidBodyMotions;
 use gmt_dos_clients_lom::LinearOpticalModel;
-use interface::{Data, Read, Update, Write};
+use interface::{ClientHooks, Data, Read, Update, Write};
 use io::M1SegmentWfeRms;
 
 #[derive(Debug, Clone)]
@@ -33,6 +33,7 @@ impl Update for M1Lom {
         self.0.update();
     }
 }
+impl ClientHooks for M1Lom {}
 
 impl Read<M1RigidBodyMotions> for M1Lom {
     fn read(&mut self, data: Data<M1RigidBodyMotions>) {
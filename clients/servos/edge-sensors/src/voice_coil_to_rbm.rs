@@ -1,6 +1,6 @@
 use anyhow::{Context, Result};
 use gmt_dos_clients_io::gmt_m2::asm::M2ASMVoiceCoilsMotion;
-use interface::{Data, Read, UniqueIdentifier, Update, Write, filing::Codec};
+use interface::{ClientHooks, Data, Read, UniqueIdentifier, Update, Write, filing::Codec};
 use matio_rs::MatFile;
 use nalgebra::{DMatrix, DVector};
 use serde::{Deserialize, Serialize};
@@ -66,6 +66,7 @@ impl Update for VoiceCoilToRbm {
         );
     }
 }
+impl ClientHooks for VoiceCoilToRbm {}
 
 impl Read<M2ASMVoiceCoilsMotion> for VoiceCoilToRbm {
     fn read(&mut self, data: Data<M2ASMVoiceCoilsMotion>) {
@@ -23,7 +23,7 @@ use gmt_dos_clients_servos::{
 use gmt_fem::FEM;
 use interface::filing::Filing;
 use interface::{
-    Data, Read, UID, Update, Write,
+    ClientHooks, Data, Read, UID, Update, Write,
     units::{Mas, NM},
 };
 use matio_rs::MatFile;
@@ -348,6 +348,7 @@ impl Update for AsmsOffLoading {
         self.data = Arc::new(data);
     }
 }
+impl ClientHooks for AsmsOffLoading {}
 impl Read<M2RigidBodyMotions> for AsmsOffLoading {
     fn read(&mut self, data: Data<M2RigidBodyMotions>) {
         self.rbms = data.into_arc();
@@ -398,6 +399,7 @@ impl Update for AsmsPtt2TxRxRz {
         }
     }
 }
+impl ClientHooks for AsmsPtt2TxRxRz {}
 
 impl Read<M2ASMVoiceCoilsMotion> for AsmsPtt2TxRxRz {
     fn read(&mut self, data: Data<M2ASMVoiceCoilsMotion>) {
@@ -2,7 +2,7 @@ use std::ops::Deref;
 
 use gmt_dos_actors::prelude::*;
 use gmt_dos_clients_transceiver::{Monitor, Transceiver};
-use interface::{Data, Read, UID, Update};
+use interface::{ClientHooks, Data, Read, UID, Update};
 
 #[derive(UID)]
 #[uid(data = Vec<u8>)]
@@ -12,6 +12,7 @@ pub enum Packet {}
 pub struct Payload(usize);
 
 impl Update for Payload {}
+impl ClientHooks for Payload {}
 impl Read<Packet> for Payload {
     fn read(&mut self, data: Data<Packet>) {
         let _ = data.deref();
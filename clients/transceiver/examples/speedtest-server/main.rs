@@ -2,7 +2,7 @@ use std::env;
 
 use gmt_dos_actors::prelude::*;
 use gmt_dos_clients_transceiver::{Monitor, Transceiver};
-use interface::{Data, UID, Update, Write};
+use interface::{ClientHooks, Data, UID, Update, Write};
 
 #[derive(UID)]
 #[uid(data = Vec<u8>)]
@@ -24,6 +24,7 @@ impl Payload {
 }
 
 impl Update for Payload {}
+impl ClientHooks for Payload {}
 impl Write<Packet> for Payload {
     fn write(&mut self) -> Option<Data<Packet>> {
         let Self {
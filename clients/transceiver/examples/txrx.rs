@@ -1,4 +1,4 @@
-use interface::{Data, Read, UID, UniqueIdentifier, Update};
+use interface::{ClientHooks, Data, Read, UID, UniqueIdentifier, Update};
 use tracing::info;
 
 #[derive(UID)]
@@ -17,6 +17,7 @@ pub enum Blah {}
 pub struct Print;
 
 impl Update for Print {}
+impl ClientHooks for Print {}
 
 impl<U: UniqueIdentifier<DataType = Vec<f64>>> Read<U> for Print {
     fn read(&mut self, data: Data<U>) {
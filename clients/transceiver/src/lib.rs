@@ -12,22 +12,27 @@ The certificate and the private key are generated with
 cargo run --bin crypto
 `
 
+If the transmitter and the receiver are running on the same host,
+the data can be exchanged through [SharedMemory] instead (see the [shm] module).
+
 [Data]: https://docs.rs/gmt_dos-clients/latest/gmt_dos_clients/interface/struct.Data.html
 */
 
 mod crypto;
 mod monitor;
 mod receiver;
+pub mod shm;
 mod transmitter;
 
 use std::{any::type_name, marker::PhantomData, thread, time::Duration};
 
-use interface::{Data, Read, UniqueIdentifier, Update, Write, trim_type_name};
+use interface::{ClientHooks, Data, Read, UniqueIdentifier, Update, Write, trim_type_name};
 use quinn::Endpoint;
 
 pub use crypto::Crypto;
 pub use monitor::Monitor;
 pub use receiver::CompactRecvr;
+pub use shm::{Frame, Mapped, SharedMemory};
 pub use transmitter::TransmitterBuilder;
 
 #[derive(Debug, thiserror::Error)]
@@ -64,6 +69,10 @@ pub enum TransceiverError {
     BincodeDecode(#[from] bincode::error::DecodeError),
     #[error("")]
     Duration(#[from] quinn_proto::VarIntBoundsExceeded),
+    #[error("frame of {0} bytes larger than the shared memory frame size ({1} bytes)")]
+    FrameSize(usize, usize),
+    #[error("shared memory transport failed ({0})")]
+    SharedMemory(String),
 }
 pub type Result<T> = std::result::Result<T, TransceiverError>;
 
@@ -94,6 +103,7 @@ pub struct Transceiver<U: UniqueIdentifier, F = Unset, S = Off> {
     server_address: String,
     tx: Option<flume::Sender<Data<U>>>,
     pub rx: Option<flume::Receiver<Data<U>>>,
+    shared_memory: Option<shm::Transport<U>>,
     function: PhantomData<F>,
     state: PhantomData<S>,
 }
@@ -139,6 +149,29 @@ impl<U: UniqueIdentifier, F> Transceiver<U, F> {
             endpoint: Some(endpoint),
             tx: Some(tx),
             rx: Some(rx),
+            shared_memory: None,
+            function: PhantomData,
+            state: PhantomData,
+        }
+    }
+    /// Creates a new [Transceiver] exchanging data through shared memory
+    pub(crate) fn shared<S: Into<String>>(
+        crypto: Crypto,
+        server_address: S,
+        shared_memory: shm::Transport<U>,
+        inner_channel: InnerChannel,
+    ) -> Self {
+        let (tx, rx) = match inner_channel {
+            InnerChannel::Bounded(cap) => flume::bounded(cap),
+            InnerChannel::Unbounded => flume::unbounded(),
+        };
+        Self {
+            crypto,
+            server_address: server_address.into(),
+            endpoint: None,
+            tx: Some(tx),
+            rx: Some(rx),
+            shared_memory: Some(shared_memory),
             function: PhantomData,
             state: PhantomData,
         }
@@ -161,6 +194,10 @@ impl<U: UniqueIdentifier, F, S> std::fmt::Debug for Transceiver<U, F, S> {
             .field("server_address", &self.server_address)
             .field("tx", &self.tx)
             .field("rx", &self.rx)
+            .field(
+                "shared_memory",
+                &self.shared_memory.as_ref().map(|shm| shm.path()),
+            )
             .field("function", &self.function)
             .field("state", &self.state)
             .finish()
@@ -225,6 +262,7 @@ impl<U: UniqueIdentifier, F, S> std::fmt::Display for Transceiver<U, F, S> {
 } */
 
 impl<U: UniqueIdentifier, F: RxOrTx + Send + Sync> Update for Transceiver<U, F, On> {}
+impl<U: UniqueIdentifier, F: RxOrTx + Send + Sync> ClientHooks for Transceiver<U, F, On> {}
 
 impl<U: UniqueIdentifier> Read<U> for Transceiver<U, Transmitter, On> {
     fn read(&mut self, data: Data<U>) {
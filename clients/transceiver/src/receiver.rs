@@ -4,7 +4,10 @@ use interface::{Data, UniqueIdentifier};
 use quinn::Endpoint;
 use tracing::{debug, error, info};
 
-use crate::{Crypto, Monitor, On, Receiver, Transceiver, TransceiverError};
+use crate::{
+    Crypto, Monitor, On, Receiver, SharedMemory, Transceiver, TransceiverError,
+    shm::{self, Frame},
+};
 
 impl<U: UniqueIdentifier> Transceiver<U> {
     /// [Transceiver] receiver functionality
@@ -31,6 +34,7 @@ impl<U: UniqueIdentifier> Transceiver<U> {
             server_address: server_address.into(),
             client_address: client_address.into(),
             crypto: Default::default(),
+            shared_memory: None,
             uid: PhantomData,
         }
         .build()
@@ -43,6 +47,7 @@ impl<U: UniqueIdentifier> Transceiver<U> {
             server_address: server_address.into(),
             client_address: client_address.into(),
             crypto: Default::default(),
+            shared_memory: None,
             uid: PhantomData,
         }
     }
@@ -66,6 +71,7 @@ impl<U: UniqueIdentifier> Transceiver<U, Receiver> {
             server_address: server_address.into(),
             tx: Some(tx),
             rx: Some(rx),
+            shared_memory: None,
             function: PhantomData,
             state: PhantomData,
         })
@@ -114,14 +120,30 @@ impl<U: UniqueIdentifier + 'static> Transceiver<U, Receiver> {
             server_address,
             mut tx,
             rx,
+            mut shared_memory,
             function,
             ..
         } = self;
-        let endpoint = endpoint.take().unwrap();
         let tx = tx.take().unwrap();
+        let name = crate::trim(type_name::<U>());
+        if let Some(shared_memory) = shared_memory.take() {
+            monitor.push(tokio::task::spawn_blocking(move || {
+                shared_memory.receive(name, tx)
+            }));
+            return Transceiver::<U, Receiver, On> {
+                crypto,
+                endpoint: None,
+                server_address,
+                tx: None,
+                rx,
+                shared_memory: None,
+                function,
+                state: PhantomData,
+            };
+        }
+        let endpoint = endpoint.take().unwrap();
         let address = SocketAddr::new(server_address.parse().unwrap(), U::PORT as u16);
         let server_name: String = crypto.name.clone();
-        let name = crate::trim(type_name::<U>());
         let handle = tokio::spawn(async move {
             let stream = endpoint.connect(address, &server_name)?;
             let connection = stream.await.map_err(|e| {
@@ -200,26 +222,58 @@ impl<U: UniqueIdentifier + 'static> Transceiver<U, Receiver> {
             server_address,
             tx: None,
             rx,
+            shared_memory: None,
             function,
             state: PhantomData,
         }
     }
 }
 
-#[derive(Debug)]
 pub struct ReceiverBuilder<U: UniqueIdentifier> {
     server_address: String,
     client_address: String,
     crypto: Option<Crypto>,
+    shared_memory: Option<shm::Transport<U>>,
     uid: PhantomData<U>,
 }
+impl<U: UniqueIdentifier> std::fmt::Debug for ReceiverBuilder<U> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReceiverBuilder")
+            .field("server_address", &self.server_address)
+            .field("client_address", &self.client_address)
+            .field("crypto", &self.crypto)
+            .field(
+                "shared_memory",
+                &self.shared_memory.as_ref().map(|shm| shm.path()),
+            )
+            .finish()
+    }
+}
 impl<U: UniqueIdentifier> ReceiverBuilder<U> {
     pub fn crypto(mut self, crypto: Crypto) -> Self {
         self.crypto = Some(crypto);
         self
     }
+    /// Receives the data through [SharedMemory] instead of the network
+    ///
+    /// The number of frames and the frame size are set by the transmitter
+    pub fn shared_memory(mut self, shared_memory: SharedMemory) -> Self
+    where
+        <U as UniqueIdentifier>::DataType: Frame,
+    {
+        self.shared_memory = Some(shm::Transport::new(shared_memory));
+        self
+    }
     pub fn build(self) -> crate::Result<Transceiver<U, Receiver>> {
         let crypto = self.crypto.unwrap_or_default();
+        if let Some(shared_memory) = self.shared_memory {
+            return Ok(Transceiver::shared(
+                crypto,
+                self.server_address,
+                shared_memory,
+                crate::InnerChannel::Unbounded,
+            ));
+        }
         let client_config = crypto.client()?;
         let address = self.client_address.parse::<SocketAddr>()?;
         let mut endpoint = Endpoint::client(address)?;
@@ -268,6 +322,7 @@ impl CompactRecvr {
             server_address: server_address.into(),
             tx: Some(tx),
             rx: Some(rx),
+            shared_memory: None,
             function: PhantomData,
             state: PhantomData,
         })
@@ -0,0 +1,585 @@
+/*!
+# Shared-memory transport
+
+A [Transceiver](crate::Transceiver) transmitter and receiver running on the same host
+can exchange [Data] through a ring buffer memory-mapped in a file instead of the network.
+The data is copied as is into the ring buffer by the transmitter, without serialization.
+The receiver gives the clients of the receiving model the data in the frames of the ring buffer:
+with the [Mapped] data type, the data is read directly from the memory-mapped frame, without any copy,
+and the frame is released to the transmitter when the data is dropped,
+whereas a vector data type is copied out of the frame.
+The transmitter waits for the receiving clients to drop some [Mapped] data if all the frames are in use.
+On Linux, the file is best located in `/dev/shm`.
+
+Each transmission creates a new session in the memory-mapped file, the receiver only removes
+the file of the session it has received.
+The transmitter fails if the receiver is dropped or if the receiver does not free a frame
+of a full ring buffer within the [timeout](SharedMemory::timeout).
+
+```
+use gmt_dos_clients_transceiver::{Mapped, Monitor, SharedMemory, Transceiver};
+use interface::{Data, Read, UID, Write};
+
+#[derive(UID)]
+#[uid(data = Mapped<f64>, port = 5001)]
+pub enum Wavefront {}
+
+# tokio_test::block_on(async {
+let path = std::env::temp_dir().join("wavefront");
+let mut monitor = Monitor::new();
+// in the transmitting model
+let mut tx = Transceiver::<Wavefront>::transmitter_builder("127.0.0.1")
+    .shared_memory(SharedMemory::new(&path).frames(8))
+    .build()?
+    .run(&mut monitor);
+// in the receiving model
+let mut rx = Transceiver::<Wavefront>::receiver_builder("127.0.0.1", "127.0.0.1:0")
+    .shared_memory(SharedMemory::new(&path))
+    .build()?
+    .run(&mut monitor);
+
+for i in 0..10 {
+    <_ as Read<Wavefront>>::read(&mut tx, Data::new(vec![i as f64; 1024].into()));
+}
+drop(tx);
+let n = std::iter::from_fn(|| <_ as Write<Wavefront>>::write(&mut rx))
+    .enumerate()
+    .inspect(|(i, data)| assert_eq!(data[1023], *i as f64))
+    .count();
+assert_eq!(n, 10);
+monitor.join().await?;
+# Ok::<(), gmt_dos_clients_transceiver::TransceiverError>(())
+# });
+```
+
+[Data]: https://docs.rs/gmt_dos-clients/latest/gmt_dos_clients/interface/struct.Data.html
+*/
+
+use std::{
+    fmt,
+    fs::{self, File, OpenOptions},
+    io::Read,
+    marker::PhantomData,
+    ops::Deref,
+    path::{Path, PathBuf},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use interface::{Data, UniqueIdentifier};
+use memmap2::MmapMut;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use tracing::{debug, info};
+
+use crate::TransceiverError;
+
+/// Data that can be copied into the shared memory as a slice of bytes
+pub trait Frame {
+    /// Returns the data as bytes
+    fn as_bytes(&self) -> &[u8];
+    /// Creates the data from a frame of the ring buffer
+    fn from_frame(frame: FrameRef) -> Self;
+}
+impl<T: bytemuck::Pod> Frame for Vec<T> {
+    fn as_bytes(&self) -> &[u8] {
+        bytemuck::cast_slice(self)
+    }
+    /// Copies the frame, releasing it
+    fn from_frame(frame: FrameRef) -> Self {
+        bytemuck::pod_collect_to_vec(frame.as_bytes())
+    }
+}
+
+/// Frame of the ring buffer read by the receiver
+///
+/// The frame is released to the transmitter when it is dropped
+pub struct FrameRef {
+    ring: Arc<Ring>,
+    seq: u64,
+    offset: usize,
+    len: usize,
+}
+impl FrameRef {
+    /// Returns the bytes of the frame
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.ring.mmap[self.offset..self.offset + self.len]
+    }
+}
+impl Drop for FrameRef {
+    fn drop(&mut self) {
+        self.ring.release(self.seq);
+    }
+}
+impl fmt::Debug for FrameRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FrameRef")
+            .field("seq", &self.seq)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+/// Slice of data either in a frame of the ring buffer or in a vector
+///
+/// The receiver gives the data in the frame without copying it, provided that the frame
+/// is aligned for `T`, and the frame is released when the data is dropped.
+/// A [Mapped] vector is created from a [Vec] for the transmitter.
+pub enum Mapped<T> {
+    /// Data in a vector
+    Owned(Vec<T>),
+    /// Data in a frame of the ring buffer
+    Frame(FrameRef, PhantomData<T>),
+}
+impl<T> Default for Mapped<T> {
+    fn default() -> Self {
+        Self::Owned(Vec::new())
+    }
+}
+impl<T> From<Vec<T>> for Mapped<T> {
+    fn from(data: Vec<T>) -> Self {
+        Self::Owned(data)
+    }
+}
+impl<T: bytemuck::Pod> Deref for Mapped<T> {
+    type Target = [T];
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Owned(data) => data,
+            // the alignment and the size of the frame are checked when the data is created
+            Self::Frame(frame, _) => bytemuck::cast_slice(frame.as_bytes()),
+        }
+    }
+}
+impl<T: bytemuck::Pod + fmt::Debug> fmt::Debug for Mapped<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+impl<T: bytemuck::Pod> Frame for Mapped<T> {
+    fn as_bytes(&self) -> &[u8] {
+        bytemuck::cast_slice(self)
+    }
+    fn from_frame(frame: FrameRef) -> Self {
+        match bytemuck::try_cast_slice::<u8, T>(frame.as_bytes()) {
+            Ok(_) => Self::Frame(frame, PhantomData),
+            Err(_) => Self::Owned(bytemuck::pod_collect_to_vec(frame.as_bytes())),
+        }
+    }
+}
+impl<T: bytemuck::Pod + Serialize> Serialize for Mapped<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Mapped<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::deserialize(deserializer).map(Self::Owned)
+    }
+}
+
+/// Shared-memory transport configuration
+///
+/// The number of frames and the frame size are only used by the transmitter,
+/// the receiver reads them from the shared memory.
+/// If the frame size is not set, it is given by the size of the 1st frame.
+#[derive(Debug, Clone)]
+pub struct SharedMemory {
+    path: PathBuf,
+    n_frame: usize,
+    frame_size: Option<usize>,
+    timeout: Duration,
+}
+impl SharedMemory {
+    /// Creates a new shared-memory configuration from the path to the memory-mapped file
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            n_frame: 4,
+            frame_size: None,
+            timeout: Duration::from_secs(10),
+        }
+    }
+    /// Sets the number of frames in the ring buffer (default: 4)
+    pub fn frames(mut self, n_frame: usize) -> Self {
+        self.n_frame = n_frame.max(1);
+        self
+    }
+    /// Sets the maximum size of a frame in bytes
+    pub fn frame_size(mut self, frame_size: usize) -> Self {
+        self.frame_size = Some(frame_size);
+        self
+    }
+    /// Sets how long the transmitter waits for the receiver to free a frame
+    /// of the full ring buffer (default: 10s)
+    ///
+    /// The transmitter waits for the receiver to open the ring buffer without time limit
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+/// Shared-memory transport of the data `U`
+pub(crate) struct Transport<U: UniqueIdentifier> {
+    config: SharedMemory,
+    as_bytes: fn(&U::DataType) -> &[u8],
+    from_frame: fn(FrameRef) -> U::DataType,
+}
+impl<U: UniqueIdentifier> Transport<U> {
+    pub fn new(config: SharedMemory) -> Self
+    where
+        U::DataType: Frame,
+    {
+        Self {
+            config,
+            as_bytes: <U::DataType as Frame>::as_bytes,
+            from_frame: <U::DataType as Frame>::from_frame,
+        }
+    }
+    pub fn path(&self) -> &Path {
+        &self.config.path
+    }
+    /// Removes the memory-mapped file left over by a previous transmission
+    pub fn reset(&self) -> crate::Result<()> {
+        match fs::remove_file(&self.config.path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+    /// Copies the data received from the client into the ring buffer
+    ///
+    /// The ring buffer is closed when the client is dropped
+    pub fn transmit(self, name: String, rx: flume::Receiver<Data<U>>) -> crate::Result<()> {
+        let Self {
+            config, as_bytes, ..
+        } = self;
+        let mut ring = config
+            .frame_size
+            .map(|frame_size| Ring::create(&config.path, &name, frame_size, config.n_frame))
+            .transpose()?;
+        while let Ok(data) = rx.recv() {
+            let bytes = as_bytes(&data);
+            let ring = match ring.as_mut() {
+                Some(ring) => ring,
+                None => ring.insert(Ring::create(
+                    &config.path,
+                    &name,
+                    bytes.len(),
+                    config.n_frame,
+                )?),
+            };
+            ring.push(bytes, config.timeout)?;
+        }
+        info!("<{name}>: rx disconnected");
+        match ring {
+            Some(ring) => ring.close(),
+            None => Ring::create(&config.path, &name, 0, config.n_frame)?.close(),
+        }
+        Ok(())
+    }
+    /// Forwards the data in the ring buffer to the client
+    ///
+    /// The memory-mapped file is removed once the transmitter has closed the ring buffer,
+    /// unless the file has been replaced by a new transmission
+    pub fn receive(self, name: String, tx: flume::Sender<Data<U>>) -> crate::Result<()> {
+        let Self {
+            config, from_frame, ..
+        } = self;
+        let mut ring = loop {
+            if let Some(ring) = Ring::open(&config.path, &name)? {
+                break ring;
+            }
+            thread::sleep(Duration::from_millis(10));
+        };
+        ring.attach();
+        let ring = Arc::new(ring);
+        info!("<{}>: shared memory: {}", name, config.path.display());
+        let mut n_byte = 0;
+        let mut next = 0;
+        let now = Instant::now();
+        loop {
+            let closed = ring.is_closed();
+            match Ring::pop(&ring, &mut next)? {
+                Some(frame) => {
+                    let n = frame.len;
+                    n_byte += n;
+                    debug!("{} bytes received", n);
+                    if tx.send(Data::new(from_frame(frame))).is_err() {
+                        info!("<{name}>: tx disconnected");
+                        break;
+                    }
+                }
+                None if closed => break,
+                None => thread::sleep(Duration::from_micros(50)),
+            }
+        }
+        drop(tx);
+        if Ring::current_session(&config.path)? == Some(ring.session) {
+            fs::remove_file(&config.path)?;
+        }
+        // the mapping outlives the receiver as long as the clients hold some data in the frames
+        ring.detach();
+        drop(ring);
+        let rate = n_byte as f64 / now.elapsed().as_secs_f64();
+        info!(
+            "{}",
+            TransceiverError::StreamEnd(
+                name,
+                bytesize::ByteSize::b(n_byte as u64).to_string(),
+                humantime::format_duration(now.elapsed()).to_string(),
+                bytesize::ByteSize::b(rate as u64).to_string(),
+            )
+        );
+        Ok(())
+    }
+}
+
+const MAGIC: u64 = u64::from_le_bytes(*b"DOSSHMRB");
+const TAG_SIZE: usize = 64;
+/// Header words: magic, frame size, number of frames, head, tail, closed flag, tag length,
+/// session, receiver state
+const N_WORD: usize = 9;
+const HEADER_SIZE: usize = 8 * N_WORD + TAG_SIZE;
+/// Receiver states
+const ATTACHED: u64 = 1;
+const DETACHED: u64 = 2;
+
+/// Single producer single consumer ring buffer in a memory-mapped file
+///
+/// Each frame is made of the frame length followed by the frame bytes.
+/// The frames read by the receiver may be released in any order,
+/// the tail of the ring buffer moving past the frames that have been released
+struct Ring {
+    mmap: MmapMut,
+    frame_size: usize,
+    n_frame: usize,
+    session: u64,
+    receiver: bool,
+    released: Vec<AtomicBool>,
+    releasing: Mutex<()>,
+}
+impl Ring {
+    fn create(path: &Path, tag: &str, frame_size: usize, n_frame: usize) -> crate::Result<Self> {
+        let frame_size = frame_size.next_multiple_of(8);
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        file.set_len((HEADER_SIZE + n_frame * (8 + frame_size)) as u64)?;
+        let mut mmap = unsafe { MmapMut::map_mut(&file)? };
+        let tag = &tag.as_bytes()[..tag.len().min(TAG_SIZE)];
+        mmap[8 * N_WORD..8 * N_WORD + tag.len()].copy_from_slice(tag);
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |t| t.as_nanos() as u64);
+        let ring = Self {
+            mmap,
+            frame_size,
+            n_frame,
+            session: (nanos ^ ((std::process::id() as u64) << 32)) | 1,
+            receiver: false,
+            released: Vec::new(),
+            releasing: Mutex::new(()),
+        };
+        ring.word(1).store(frame_size as u64, Ordering::Relaxed);
+        ring.word(2).store(n_frame as u64, Ordering::Relaxed);
+        ring.word(6).store(tag.len() as u64, Ordering::Relaxed);
+        ring.word(7).store(ring.session, Ordering::Relaxed);
+        ring.word(0).store(MAGIC, Ordering::Release);
+        Ok(ring)
+    }
+    /// Opens the ring buffer
+    ///
+    /// Returns `None` if it is not yet initialized or if it has already been opened by a receiver
+    fn open(path: &Path, tag: &str) -> crate::Result<Option<Self>> {
+        let Ok(file) = OpenOptions::new().read(true).write(true).open(path) else {
+            return Ok(None);
+        };
+        let len = file.metadata()?.len() as usize;
+        if len < HEADER_SIZE {
+            return Ok(None);
+        }
+        let mmap = unsafe { MmapMut::map_mut(&file)? };
+        let mut ring = Self {
+            mmap,
+            frame_size: 0,
+            n_frame: 0,
+            session: 0,
+            receiver: false,
+            released: Vec::new(),
+            releasing: Mutex::new(()),
+        };
+        if ring.word(0).load(Ordering::Acquire) != MAGIC
+            || ring.word(8).load(Ordering::Acquire) != 0
+        {
+            return Ok(None);
+        }
+        ring.frame_size = ring.word(1).load(Ordering::Relaxed) as usize;
+        ring.n_frame = ring.word(2).load(Ordering::Relaxed) as usize;
+        ring.session = ring.word(7).load(Ordering::Relaxed);
+        if ring.n_frame == 0
+            || !ring.frame_size.is_multiple_of(8)
+            || len < HEADER_SIZE + ring.n_frame * (8 + ring.frame_size)
+        {
+            return Err(TransceiverError::SharedMemory(format!(
+                "invalid header in {}",
+                path.display()
+            )));
+        }
+        let tag_len = (ring.word(6).load(Ordering::Relaxed) as usize).min(TAG_SIZE);
+        let expected = &tag.as_bytes()[..tag.len().min(TAG_SIZE)];
+        let found = &ring.mmap[8 * N_WORD..8 * N_WORD + tag_len];
+        if found != expected {
+            return Err(TransceiverError::DataMismatch(
+                tag.to_string(),
+                String::from_utf8_lossy(found).into_owned(),
+            ));
+        }
+        ring.released = (0..ring.n_frame).map(|_| AtomicBool::new(false)).collect();
+        Ok(Some(ring))
+    }
+    /// Returns the session of the ring buffer in the file, if any
+    fn current_session(path: &Path) -> crate::Result<Option<u64>> {
+        let Ok(mut file) = File::open(path) else {
+            return Ok(None);
+        };
+        let mut header = [0u8; 8 * N_WORD];
+        if file.read_exact(&mut header).is_err() {
+            return Ok(None);
+        }
+        let word = |i: usize| u64::from_le_bytes(header[8 * i..8 * (i + 1)].try_into().unwrap());
+        Ok((word(0) == MAGIC).then(|| word(7)))
+    }
+    /// Flags the ring buffer as being read by the receiver
+    fn attach(&mut self) {
+        self.receiver = true;
+        self.word(8).store(ATTACHED, Ordering::Release);
+    }
+    /// Flags the ring buffer as no longer being read by the receiver
+    fn detach(&self) {
+        self.word(8).store(DETACHED, Ordering::Release);
+    }
+    fn word(&self, i: usize) -> &AtomicU64 {
+        // the mapping is page aligned and the header words are 8 bytes aligned
+        unsafe { &*(self.mmap.as_ptr().add(8 * i) as *const AtomicU64) }
+    }
+    fn offset(&self, seq: u64) -> usize {
+        HEADER_SIZE + (seq as usize % self.n_frame) * (8 + self.frame_size)
+    }
+    /// Copies a frame into the ring buffer, waiting for a free slot
+    ///
+    /// Fails if the receiver has been dropped or if, once the receiver is attached,
+    /// no slot has been freed within `timeout`
+    fn push(&mut self, bytes: &[u8], timeout: Duration) -> crate::Result<()> {
+        if bytes.len() > self.frame_size {
+            return Err(TransceiverError::FrameSize(bytes.len(), self.frame_size));
+        }
+        let head = self.word(3).load(Ordering::Relaxed);
+        let mut waiting: Option<Instant> = None;
+        while head - self.word(4).load(Ordering::Acquire) >= self.n_frame as u64 {
+            match self.word(8).load(Ordering::Acquire) {
+                DETACHED => {
+                    return Err(TransceiverError::SharedMemory(
+                        "the receiver has been dropped".into(),
+                    ));
+                }
+                ATTACHED if waiting.get_or_insert_with(Instant::now).elapsed() > timeout => {
+                    return Err(TransceiverError::SharedMemory(format!(
+                        "the ring buffer has been full for {}",
+                        humantime::format_duration(timeout)
+                    )));
+                }
+                _ => thread::sleep(Duration::from_micros(50)),
+            }
+        }
+        let offset = self.offset(head);
+        let frame = &mut self.mmap[offset..offset + 8 + bytes.len()];
+        frame[..8].copy_from_slice(&(bytes.len() as u64).to_le_bytes());
+        frame[8..].copy_from_slice(bytes);
+        self.word(3).store(head + 1, Ordering::Release);
+        Ok(())
+    }
+    /// Reads the frame `next`, returns `None` if the frame has not been written yet
+    ///
+    /// Fails if the frame length is larger than the frame size
+    fn pop(ring: &Arc<Self>, next: &mut u64) -> crate::Result<Option<FrameRef>> {
+        if *next == ring.word(3).load(Ordering::Acquire) {
+            return Ok(None);
+        }
+        let offset = ring.offset(*next);
+        let mut len = [0u8; 8];
+        len.copy_from_slice(&ring.mmap[offset..offset + 8]);
+        let len = u64::from_le_bytes(len) as usize;
+        if len > ring.frame_size {
+            return Err(TransceiverError::FrameSize(len, ring.frame_size));
+        }
+        let frame = FrameRef {
+            ring: Arc::clone(ring),
+            seq: *next,
+            offset: offset + 8,
+            len,
+        };
+        *next += 1;
+        Ok(Some(frame))
+    }
+    /// Releases the frame `seq` and frees the released frames at the tail of the ring buffer
+    fn release(&self, seq: u64) {
+        self.released[seq as usize % self.n_frame].store(true, Ordering::Release);
+        let _releasing = self.releasing.lock().unwrap();
+        let mut tail = self.word(4).load(Ordering::Relaxed);
+        while self.released[tail as usize % self.n_frame].swap(false, Ordering::AcqRel) {
+            tail += 1;
+        }
+        self.word(4).store(tail, Ordering::Release);
+    }
+    fn close(&self) {
+        self.word(5).store(1, Ordering::Release);
+    }
+    fn is_closed(&self) -> bool {
+        self.word(5).load(Ordering::Acquire) == 1
+    }
+}
+impl Drop for Ring {
+    fn drop(&mut self) {
+        if self.receiver {
+            self.word(8).store(DETACHED, Ordering::Release);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn release_out_of_order() -> crate::Result<()> {
+        let path = std::env::temp_dir().join("gmt_dos-clients_transceiver-release");
+        let mut tx = Ring::create(&path, "test", 8, 2)?;
+        let mut rx = Ring::open(&path, "test")?.unwrap();
+        rx.attach();
+        let rx = Arc::new(rx);
+        let mut next = 0;
+        tx.push(&1f64.to_le_bytes(), Duration::ZERO)?;
+        tx.push(&2f64.to_le_bytes(), Duration::ZERO)?;
+        let first = Mapped::<f64>::from_frame(Ring::pop(&rx, &mut next)?.unwrap());
+        let second = Mapped::<f64>::from_frame(Ring::pop(&rx, &mut next)?.unwrap());
+        assert!(matches!(first, Mapped::Frame(..)));
+        assert_eq!((first[0], second[0]), (1., 2.));
+        // the ring buffer is full until the 1st frame is released
+        drop(second);
+        assert!(tx.push(&3f64.to_le_bytes(), Duration::ZERO).is_err());
+        drop(first);
+        tx.push(&3f64.to_le_bytes(), Duration::ZERO)?;
+        tx.push(&4f64.to_le_bytes(), Duration::ZERO)?;
+        let third = Vec::<f64>::from_frame(Ring::pop(&rx, &mut next)?.unwrap());
+        assert_eq!(third, vec![3.]);
+        fs::remove_file(path)?;
+        Ok(())
+    }
+}
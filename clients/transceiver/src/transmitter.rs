@@ -6,7 +6,10 @@ use quinn::Endpoint;
 use tokio::task::JoinHandle;
 use tracing::{error, info};
 
-use crate::{Crypto, InnerChannel, Monitor, On, Transceiver, TransceiverError, Transmitter};
+use crate::{
+    Crypto, InnerChannel, Monitor, On, SharedMemory, Transceiver, TransceiverError, Transmitter,
+    shm::{self, Frame},
+};
 
 impl<U: UniqueIdentifier> Transceiver<U> {
     /// [Transceiver] transmitter functionality
@@ -81,12 +84,36 @@ impl<U: UniqueIdentifier + 'static> Transceiver<U, Transmitter> {
             server_address,
             tx,
             mut rx,
+            mut shared_memory,
             function,
             ..
         } = self;
-        let endpoint = endpoint.take().unwrap();
         let rx = rx.take().unwrap();
         let name = crate::trim(type_name::<U>());
+        if let Some(shared_memory) = shared_memory.take() {
+            if let Err(e) = shared_memory.reset() {
+                error!(
+                    "<{name}>: failed to reset {}",
+                    shared_memory.path().display()
+                );
+                monitor.push(tokio::spawn(async { Err(e) }));
+            } else {
+                monitor.push(tokio::task::spawn_blocking(move || {
+                    shared_memory.transmit(name, rx)
+                }));
+            }
+            return Transceiver::<U, Transmitter, On> {
+                crypto,
+                endpoint: None,
+                server_address,
+                tx,
+                rx: None,
+                shared_memory: None,
+                function,
+                state: PhantomData,
+            };
+        }
+        let endpoint = endpoint.take().unwrap();
         let handle: JoinHandle<Result<(), TransceiverError>> = tokio::spawn(async move {
             // info!("<{name}>: waiting for receiver to connect");
             let stream = endpoint
@@ -172,25 +199,40 @@ impl<U: UniqueIdentifier + 'static> Transceiver<U, Transmitter> {
             server_address,
             tx,
             rx: None,
+            shared_memory: None,
             function,
             state: PhantomData,
         }
     }
 }
 
-#[derive(Debug)]
 pub struct TransmitterBuilder<U: UniqueIdentifier> {
     server_address: String,
     inner_channel: InnerChannel,
     crypto: Option<Crypto>,
+    shared_memory: Option<shm::Transport<U>>,
     uid: PhantomData<U>,
 }
+impl<U: UniqueIdentifier> Debug for TransmitterBuilder<U> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TransmitterBuilder")
+            .field("server_address", &self.server_address)
+            .field("inner_channel", &self.inner_channel)
+            .field("crypto", &self.crypto)
+            .field(
+                "shared_memory",
+                &self.shared_memory.as_ref().map(|shm| shm.path()),
+            )
+            .finish()
+    }
+}
 impl<U: UniqueIdentifier> Default for TransmitterBuilder<U> {
     fn default() -> Self {
         Self {
             server_address: Default::default(),
             inner_channel: Default::default(),
             crypto: Default::default(),
+            shared_memory: None,
             uid: PhantomData,
         }
     }
@@ -210,8 +252,24 @@ impl<U: UniqueIdentifier> TransmitterBuilder<U> {
         self.inner_channel = InnerChannel::Bounded(capacity);
         self
     }
+    /// Sends the data through [SharedMemory] instead of the network
+    pub fn shared_memory(mut self, shared_memory: SharedMemory) -> Self
+    where
+        <U as UniqueIdentifier>::DataType: Frame,
+    {
+        self.shared_memory = Some(shm::Transport::new(shared_memory));
+        self
+    }
     pub fn build(self) -> crate::Result<Transceiver<U, Transmitter>> {
         let crypto = self.crypto.unwrap_or_default();
+        if let Some(shared_memory) = self.shared_memory {
+            return Ok(Transceiver::shared(
+                crypto,
+                self.server_address,
+                shared_memory,
+                self.inner_channel,
+            ));
+        }
         let server_config = crypto.server()?;
         // let address = self.server_address.parse::<SocketAddr>()?;
         let address = SocketAddr::new(self.server_address.parse()?, U::PORT as u16);
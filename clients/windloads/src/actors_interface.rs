@@ -1,9 +1,10 @@
 use gmt_dos_clients_io::cfd_wind_loads::{CFDM1WindLoads, CFDM2WindLoads, CFDMountWindLoads};
-use interface::{Data, Size, UID, Update, Write};
+use interface::{ClientHooks, Data, Size, UID, Update, Write};
 
 use crate::{CfdLoads, FOH, ZOH};
 
 impl Update for CfdLoads<ZOH> {}
+impl ClientHooks for CfdLoads<ZOH> {}
 impl Update for CfdLoads<FOH> {
     fn update(&mut self) {
         if self.step > self.max_step {
@@ -13,6 +14,7 @@ impl Update for CfdLoads<FOH> {
         self.step += 1;
     }
 }
+impl ClientHooks for CfdLoads<FOH> {}
 
 #[derive(UID)]
 pub enum MountLoads {}
@@ -64,6 +64,7 @@ impl Iterator for CfdLoads {
 }
 
 impl crate::Update for CfdLoads {}
+impl crate::ClientHooks for CfdLoads {}
 #[cfg(feature = "fem")]
 impl crate::io::Write<Vec<f64>, fem::fem_io::OSSDTAWind6F> for CfdLoads {
     fn write(
@@ -2,7 +2,7 @@ use std::ops::{Deref, DerefMut};
 
 use gmt_dos_clients::smooth::{Smooth, Weight};
 use gmt_dos_clients_io::cfd_wind_loads::CFDM1WindLoads;
-use interface::{Read, UniqueIdentifier, Update, Write};
+use interface::{ClientHooks, Read, UniqueIdentifier, Update, Write};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,6 +33,7 @@ impl Update for M1Smoother {
         <Smooth as Update>::update(self);
     }
 }
+impl ClientHooks for M1Smoother {}
 
 impl Read<Weight> for M1Smoother {
     fn read(&mut self, data: interface::Data<Weight>) {
@@ -2,7 +2,7 @@ use std::ops::{Deref, DerefMut};
 
 use gmt_dos_clients::smooth::{Smooth, Weight};
 use gmt_dos_clients_io::cfd_wind_loads::CFDM2WindLoads;
-use interface::{Read, UniqueIdentifier, Update, Write};
+use interface::{ClientHooks, Read, UniqueIdentifier, Update, Write};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,6 +33,7 @@ impl Update for M2Smoother {
         <Smooth as Update>::update(self);
     }
 }
+impl ClientHooks for M2Smoother {}
 
 impl Read<Weight> for M2Smoother {
     fn read(&mut self, data: interface::Data<Weight>) {
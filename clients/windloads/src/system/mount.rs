@@ -2,7 +2,7 @@ use std::ops::{Deref, DerefMut};
 
 use gmt_dos_clients::smooth::{Smooth, Weight};
 use gmt_dos_clients_io::cfd_wind_loads::CFDMountWindLoads;
-use interface::{Read, UniqueIdentifier, Update, Write};
+use interface::{ClientHooks, Read, UniqueIdentifier, Update, Write};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,6 +33,7 @@ impl Update for MountSmoother {
         <Smooth as Update>::update(self);
     }
 }
+impl ClientHooks for MountSmoother {}
 
 impl Read<Weight> for MountSmoother {
     fn read(&mut self, data: interface::Data<Weight>) {
@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use gmt_dos_clients_io::optics::WfeRms;
 use gmt_dos_clients_lom::LinearOpticalModel;
-use interface::{Data, Read, UID, UniqueIdentifier, Update, Write};
+use interface::{ClientHooks, Data, Read, UID, UniqueIdentifier, Update, Write};
 
 #[derive(UID)]
 #[alias(name = WfeRms<-6>, port = 55991, client = LinearOpticalModel, traits = Write)]
@@ -38,6 +38,7 @@ impl Multiplex {
 }
 
 impl Update for Multiplex {}
+impl ClientHooks for Multiplex {}
 impl<U: UniqueIdentifier<DataType = Vec<f64>>> Read<U> for Multiplex {
     fn read(&mut self, data: Data<U>) {
         self.data = data.into_arc();
@@ -0,0 +1,203 @@
+/*!
+# Asynchronous client interface
+
+Clients that spend most of their time waiting on I/O (network, object store, ...)
+can implement [AsyncUpdate], [AsyncRead] and [AsyncWrite] instead of [Update](crate::Update),
+[Read](crate::Read) and [Write](crate::Write).
+Wrapped into [Async], they are awaited by the actor without blocking the runtime.
+The errors returned by the asynchronous methods are propagated by [Async] to the actor.
+
+```
+use std::convert::Infallible;
+
+use gmt_dos_actors_clients_interface::{
+    Async, AsyncRead, AsyncUpdate, AsyncWrite, ClientHooks, Data, UniqueIdentifier,
+};
+
+pub enum Request {}
+impl UniqueIdentifier for Request {
+    type DataType = Vec<f64>;
+}
+pub enum Reply {}
+impl UniqueIdentifier for Reply {
+    type DataType = Vec<f64>;
+}
+
+#[derive(Default)]
+struct Echo(Vec<f64>);
+impl AsyncUpdate for Echo {
+    type Error = std::io::Error;
+    async fn update(&mut self) -> Result<(), Self::Error> {
+        // await some I/O
+        Ok(())
+    }
+}
+impl ClientHooks for Echo {}
+impl AsyncRead<Request> for Echo {
+    type Error = Infallible;
+    async fn read(&mut self, data: Data<Request>) -> Result<(), Infallible> {
+        self.0 = data.into();
+        Ok(())
+    }
+}
+impl AsyncWrite<Reply> for Echo {
+    type Error = Infallible;
+    async fn write(&mut self) -> Result<Option<Data<Reply>>, Infallible> {
+        Ok(Some(self.0.clone().into()))
+    }
+}
+
+let client = Async::new(Echo::default());
+```
+*/
+
+use std::{
+    future::Future,
+    ops::{Deref, DerefMut},
+    pin::pin,
+    sync::Arc,
+    task::{Context, Poll, Wake},
+    thread::{self, Thread},
+};
+
+use crate::{
+    BoxError, BoxFuture, ClientHooks, Data, TryRead, TryUpdate, TryWrite, UniqueIdentifier,
+};
+
+/// Actor client state asynchronous update interface
+pub trait AsyncUpdate: ClientHooks + Send + Sync {
+    type Error: std::error::Error + Send + Sync;
+    fn update(&mut self) -> impl Future<Output = std::result::Result<(), Self::Error>> + Send {
+        async { Ok(()) }
+    }
+}
+/// Client input data asynchronous reader interface
+pub trait AsyncRead<U: UniqueIdentifier>: AsyncUpdate {
+    type Error: std::error::Error + Send + Sync;
+    /// Read data from an input
+    fn read(
+        &mut self,
+        data: Data<U>,
+    ) -> impl Future<Output = std::result::Result<(), <Self as AsyncRead<U>>::Error>> + Send;
+}
+/// Client output data asynchronous writer interface
+pub trait AsyncWrite<U: UniqueIdentifier>: AsyncUpdate {
+    type Error: std::error::Error + Send + Sync;
+    fn write(
+        &mut self,
+    ) -> impl Future<Output = std::result::Result<Option<Data<U>>, <Self as AsyncWrite<U>>::Error>> + Send;
+}
+
+/// Asynchronous client wrapper
+///
+/// [Async] implements [TryUpdate], [TryRead] and [TryWrite] for a client
+/// implementing [AsyncUpdate], [AsyncRead] and [AsyncWrite].
+/// The actor awaits the asynchronous methods of the client,
+/// the synchronous methods are only invoked outside of an actor and they block the current thread.
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Async<C>(C);
+impl<C> Async<C> {
+    /// Wraps an asynchronous client
+    pub fn new(client: C) -> Self {
+        Self(client)
+    }
+    /// Returns the asynchronous client
+    pub fn into_inner(self) -> C {
+        self.0
+    }
+}
+impl<C> Deref for Async<C> {
+    type Target = C;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl<C> DerefMut for Async<C> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl<C> From<C> for Async<C> {
+    fn from(client: C) -> Self {
+        Self(client)
+    }
+}
+
+impl<C: ClientHooks> ClientHooks for Async<C> {
+    fn on_shutdown(&mut self) {
+        self.0.on_shutdown();
+    }
+    fn provenance(&self) -> Vec<(String, String)> {
+        self.0.provenance()
+    }
+    fn set_provenance(&mut self, provenance: &[(String, String)]) {
+        self.0.set_provenance(provenance);
+    }
+    fn set_inputs_rate(&mut self, rate: usize) {
+        self.0.set_inputs_rate(rate);
+    }
+}
+impl<C: AsyncUpdate> TryUpdate for Async<C> {
+    type Error = <C as AsyncUpdate>::Error;
+    fn try_update(&mut self) -> std::result::Result<&mut Self, Self::Error> {
+        block_on(self.0.update())?;
+        Ok(self)
+    }
+    fn async_try_update(&mut self) -> BoxFuture<'_, std::result::Result<(), BoxError>>
+    where
+        <Self as TryUpdate>::Error: 'static,
+    {
+        Box::pin(async move { self.0.update().await.map_err(|e| e.into()) })
+    }
+}
+impl<U: UniqueIdentifier + 'static, C: AsyncRead<U>> TryRead<U> for Async<C> {
+    type Error = <C as AsyncRead<U>>::Error;
+    fn try_read(
+        &mut self,
+        data: Data<U>,
+    ) -> std::result::Result<&mut Self, <Self as TryRead<U>>::Error> {
+        block_on(self.0.read(data))?;
+        Ok(self)
+    }
+    fn async_try_read(&mut self, data: Data<U>) -> BoxFuture<'_, std::result::Result<(), BoxError>>
+    where
+        <Self as TryRead<U>>::Error: 'static,
+        U: 'static,
+    {
+        Box::pin(async move { self.0.read(data).await.map_err(|e| e.into()) })
+    }
+}
+impl<U: UniqueIdentifier + 'static, C: AsyncWrite<U>> TryWrite<U> for Async<C> {
+    type Error = <C as AsyncWrite<U>>::Error;
+    fn try_write(&mut self) -> std::result::Result<Option<Data<U>>, <Self as TryWrite<U>>::Error> {
+        block_on(self.0.write())
+    }
+    fn async_try_write(&mut self) -> BoxFuture<'_, std::result::Result<Option<Data<U>>, BoxError>>
+    where
+        <Self as TryWrite<U>>::Error: 'static,
+        U: 'static,
+    {
+        Box::pin(async move { self.0.write().await.map_err(|e| e.into()) })
+    }
+}
+
+/// Runs a future to completion on the current thread
+fn block_on<F: Future>(future: F) -> F::Output {
+    struct Unpark(Thread);
+    impl Wake for Unpark {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+    let waker = Arc::new(Unpark(thread::current())).into();
+    let mut context = Context::from_waker(&waker);
+    let mut future = pin!(future);
+    loop {
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(output) => break output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use std::{marker::PhantomData, ops::Deref, sync::Arc};
 
-use crate::{Data, Read, UniqueIdentifier, Update, Write};
+use crate::{ClientHooks, Data, Read, UniqueIdentifier, Update, Write};
 
 pub trait UidTuple {}
 
@@ -158,6 +158,12 @@ where
     U2: UniqueIdentifier,
 {
 }
+impl<U1, U2, const ID: usize> ClientHooks for Get<U1, U2, ID>
+where
+    U1: UniqueIdentifier,
+    U2: UniqueIdentifier,
+{
+}
 
 // READ
 impl<U1, U2, const ID: usize> Read<Doublet<U1, U2>> for Get<U1, U2, ID>
@@ -9,13 +9,20 @@ Data is passed from the client to the [actor] by invoking [Write::write] from th
 
 The client state may be updated by invoking [Update::update] from the client
 
+Clients that are I/O bound may instead implement the asynchronous interface [AsyncRead], [AsyncUpdate] and [AsyncWrite]
+and be given to the [actor] wrapped into [Async].
+
 The macro [chain] conveniently allows to invoke the sequence of [Read], [Update] and [Write] traits to a series of clients.
 
 [actor]: https://docs.rs/gmt_dos-actors
 */
 
-use std::{any::type_name, convert::Infallible, marker::PhantomData, sync::Arc};
+use std::{
+    any::type_name, convert::Infallible, future::Future, marker::PhantomData, pin::Pin, sync::Arc,
+};
 
+mod asynchronous;
+pub use asynchronous::{Async, AsyncRead, AsyncUpdate, AsyncWrite};
 mod data;
 pub mod doublet;
 pub use data::Data;
@@ -29,6 +36,11 @@ pub mod filing;
 
 pub type Assoc<U> = <U as UniqueIdentifier>::DataType;
 
+/// Boxed client error
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+/// Boxed future returned by the asynchronous methods of the client interface
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
 /// Marker to allow the UID data to be either left or right added or substracted with the [Operator](https://docs.rs/gmt_dos-clients/latest/gmt_dos_clients/operator/index.html) client
 pub trait OperatorLeftRight {
     const LEFT: bool;
@@ -80,12 +92,38 @@ impl UniqueIdentifier for () {
     type DataType = ();
 }
 
+/// Actor client hooks
+///
+/// The hooks are invoked by the actor the client belongs to, whatever the client update interface:
+/// [Update], [TryUpdate] or [AsyncUpdate]
+pub trait ClientHooks {
+    /// Invoked when the actor loop is cancelled or fails
+    ///
+    /// This is the place to flush any data buffered by the client
+    fn on_shutdown(&mut self) {}
+    /// Returns the key/value pairs identifying the client configuration
+    ///
+    /// They are gathered into the provenance of the model the client belongs to
+    fn provenance(&self) -> Vec<(String, String)> {
+        Vec::new()
+    }
+    /// Receives the provenance of the model the client belongs to
+    ///
+    /// Clients writing data to files should store it alongside the data
+    fn set_provenance(&mut self, _provenance: &[(String, String)]) {}
+    /// Receives the rate of the inputs of the actor the client belongs to
+    ///
+    /// The rate is the number of samples, at the simulation sampling frequency,
+    /// between 2 consecutive reads of the inputs.
+    /// It is given to the client when the model is checked
+    fn set_inputs_rate(&mut self, _rate: usize) {}
+}
 /// Actor client state update interface
-pub trait Update: Send + Sync {
+pub trait Update: ClientHooks + Send + Sync {
     fn update(&mut self) {}
 }
 /// Actor client state update fallible interface
-pub trait TryUpdate: Send + Sync {
+pub trait TryUpdate: ClientHooks + Send + Sync {
     type Error: std::error::Error + Send + Sync;
     fn try_update(&mut self) -> std::result::Result<&mut Self, Self::Error>;
     fn boxed_try_update<'a, 'b>(
@@ -96,6 +134,17 @@ pub trait TryUpdate: Send + Sync {
     {
         self.try_update().map_err(|e| e.into())
     }
+    /// Updates the client state from within the actor loop
+    ///
+    /// The default implementation invokes [TryUpdate::try_update],
+    /// it is overridden by [Async] to await [AsyncUpdate::update]
+    fn async_try_update(&mut self) -> BoxFuture<'_, std::result::Result<(), BoxError>>
+    where
+        <Self as TryUpdate>::Error: 'static,
+    {
+        let result = self.try_update().map(|_| ()).map_err(|e| e.into());
+        Box::pin(std::future::ready(result))
+    }
 }
 impl<C: Update> TryUpdate for C {
     type Error = Infallible;
@@ -127,6 +176,18 @@ pub trait TryRead<U: UniqueIdentifier>: TryUpdate {
     {
         self.try_read(data).map_err(move |e| e.into())
     }
+    /// Reads data from an input within the actor loop
+    ///
+    /// The default implementation invokes [TryRead::try_read],
+    /// it is overridden by [Async] to await [AsyncRead::read]
+    fn async_try_read(&mut self, data: Data<U>) -> BoxFuture<'_, std::result::Result<(), BoxError>>
+    where
+        <Self as TryRead<U>>::Error: 'static,
+        U: 'static,
+    {
+        let result = self.try_read(data).map(|_| ()).map_err(|e| e.into());
+        Box::pin(std::future::ready(result))
+    }
 }
 impl<U: UniqueIdentifier, C: Read<U>> TryRead<U> for C {
     type Error = Infallible;
@@ -155,6 +216,17 @@ pub trait TryWrite<U: UniqueIdentifier>: TryUpdate {
     {
         self.try_write().map_err(move |e| e.into())
     }
+    /// Writes data to an output within the actor loop
+    ///
+    /// The default implementation invokes [TryWrite::try_write],
+    /// it is overridden by [Async] to await [AsyncWrite::write]
+    fn async_try_write(&mut self) -> BoxFuture<'_, std::result::Result<Option<Data<U>>, BoxError>>
+    where
+        <Self as TryWrite<U>>::Error: 'static,
+        U: 'static,
+    {
+        Box::pin(std::future::ready(self.try_write().map_err(|e| e.into())))
+    }
 }
 impl<U: UniqueIdentifier, C: Write<U>> TryWrite<U> for C {
     type Error = Infallible;
@@ -170,6 +242,7 @@ pub trait Size<U: UniqueIdentifier>: Update {
 
 pub struct NoneClient<U, V = U>(PhantomData<U>, PhantomData<V>);
 impl<U: UniqueIdentifier, V: UniqueIdentifier> Update for NoneClient<U, V> {}
+impl<U: UniqueIdentifier, V: UniqueIdentifier> ClientHooks for NoneClient<U, V> {}
 impl<U: UniqueIdentifier, V: UniqueIdentifier> Read<U> for NoneClient<U, V> {
     fn read(&mut self, _: Data<U>) {}
 }
@@ -16,7 +16,7 @@ Vector element selection type
 
 # Example
 ```
-use gmt_dos_actors_clients_interface::{Data, UniqueIdentifier, Update, Write,
+use gmt_dos_actors_clients_interface::{ClientHooks, Data, UniqueIdentifier, Update, Write,
     select::{Select, Selector}};
 pub enum TTT {}
 impl UniqueIdentifier for TTT {
@@ -47,6 +47,7 @@ impl<U: UniqueIdentifier, const IDX: usize> UniqueIdentifier for Select<U, IDX>
 }
 
 impl<U: UniqueIdentifier, const IDX: usize> Update for Select<U, IDX> {}
+impl<U: UniqueIdentifier, const IDX: usize> ClientHooks for Select<U, IDX> {}
 
 impl<T, C, U, const IDX: usize> Write<Select<U, IDX>> for C
 where
@@ -66,7 +67,7 @@ where
 
 #[cfg(test)]
 mod tests {
-    use crate::{Data, UniqueIdentifier, Update, Write};
+    use crate::{ClientHooks, Data, UniqueIdentifier, Update, Write};
 
     use super::{Select, Selector};
 
@@ -80,6 +81,7 @@ mod tests {
     }
 
     impl Update for Client {}
+    impl ClientHooks for Client {}
 
     impl Write<TTT> for Client {
         fn write(&mut self) -> Option<crate::Data<TTT>> {
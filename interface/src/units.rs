@@ -138,7 +138,7 @@ pub trait UnitsConversion {
 
 #[cfg(test)]
 mod tests {
-    use crate::Update;
+    use crate::{ClientHooks, Update};
 
     use super::*;
 
@@ -151,6 +151,7 @@ mod tests {
         pub data: Vec<f64>,
     }
     impl Update for Client {}
+    impl ClientHooks for Client {}
     impl Write<W> for Client {
         fn write(&mut self) -> Option<Data<W>> {
             Some(vec![1e-9, 2e-6, 3e-3].into())
@@ -162,6 +163,7 @@ mod tests {
         pub data: Vec<f64>,
     }
     impl Update for ClientAngle {}
+    impl ClientHooks for ClientAngle {}
     impl Write<W> for ClientAngle {
         fn write(&mut self) -> Option<Data<W>> {
             Some(vec![1., 1e-3].into())
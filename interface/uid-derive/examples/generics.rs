@@ -1,7 +1,7 @@
 use std::marker::PhantomData;
 
 use dos_uid_derive::UID;
-use interface::{Data, Update, Write};
+use interface::{ClientHooks, Data, Update, Write};
 
 struct Q<R>(PhantomData<R>);
 
@@ -11,6 +11,7 @@ struct TU<T: Sync + Send>(PhantomData<T>);
 
 struct Client {}
 impl Update for Client {}
+impl ClientHooks for Client {}
 impl<T: Sync + Send> Write<TU<T>> for Client {
     fn write(&mut self) -> Option<Data<TU<T>>> {
         None
@@ -26,6 +27,7 @@ enum W<const ID: u8> {}
 
 struct ClientW {}
 impl Update for ClientW {}
+impl ClientHooks for ClientW {}
 impl<const ID: u8> Write<W<ID>> for ClientW {
     fn write(&mut self) -> Option<Data<W<ID>>> {
         None
@@ -1,7 +1,7 @@
 use std::marker::PhantomData;
 
 use dos_uid_derive::UID;
-use interface::{Data, Read, Size, Update, Write};
+use interface::{ClientHooks, Data, Read, Size, Update, Write};
 
 struct Q<T>(PhantomData<T>);
 
@@ -14,6 +14,7 @@ enum TU {}
 struct Client {}
 
 impl Update for Client {}
+impl ClientHooks for Client {}
 impl Write<TU> for Client {
     fn write(&mut self) -> Option<Data<TU>> {
         None
@@ -28,7 +28,7 @@ enum TU {}
 
 An alias is a type that implements the [Read], [Write] or [Size] trait of another type that implements the same traits for the same client:
 ```
-use interface::{UID, Data, Read, Size, Update, Write};
+use interface::{ClientHooks, UID, Data, Read, Size, Update, Write};
 # struct Q<T>(std::marker::PhantomData<T>);
 # enum ID {}
 # #[derive(UID)]
@@ -37,6 +37,7 @@ use interface::{UID, Data, Read, Size, Update, Write};
 
 struct Client {}
 impl Update for Client {}
+impl ClientHooks for Client {}
 impl Write<TU> for Client {
     fn write(&mut self) -> Option<Data<TU>> {
         None
@@ -19,7 +19,7 @@ use gmt_dos_systems_agws::{
     builder::shack_hartmann::ShackHartmannBuilder,
     qp::{ActiveOptics, QP},
 };
-use interface::{Read, Tick, UniqueIdentifier, Update, optics::OpticsState};
+use interface::{ClientHooks, Read, Tick, UniqueIdentifier, Update, optics::OpticsState};
 
 const N_MODE: usize = 271;
 const M1_BM: usize = 27;
@@ -33,6 +33,7 @@ const SH24_RATE: usize = 5;
 
 pub struct Void;
 impl Update for Void {}
+impl ClientHooks for Void {}
 impl<U: UniqueIdentifier> Read<U> for Void {
     fn read(&mut self, _: interface::Data<U>) {}
 }
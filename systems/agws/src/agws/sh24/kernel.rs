@@ -12,7 +12,7 @@ use gmt_dos_clients_io::{
     gmt_m2::{M2RigidBodyMotions, fsm::M2FSMFsmCommand},
     optics::{Dev, Frame, SensorData},
 };
-use interface::{TryRead, TryUpdate, TryWrite, UniqueIdentifier};
+use interface::{ClientHooks, TryRead, TryUpdate, TryWrite, UniqueIdentifier};
 
 use crate::kernels::{Kernel, KernelError, KernelSpecs};
 
@@ -46,6 +46,7 @@ where
         Ok(self)
     }
 }
+impl<T: KernelSpecs> ClientHooks for Sh24Kern<T> where Kernel<T>: TryUpdate {}
 
 impl<T: KernelSpecs, U: UniqueIdentifier> TryRead<U> for Sh24Kern<T>
 where
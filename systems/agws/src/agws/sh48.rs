@@ -12,7 +12,7 @@ use std::{
 
 use gmt_dos_clients_crseo::{OpticalModel, sensors::Camera};
 use gmt_dos_clients_io::optics::SensorData;
-use interface::{Data, Read, TryWrite, UniqueIdentifier, Update, Write};
+use interface::{ClientHooks, Data, Read, TryWrite, UniqueIdentifier, Update, Write};
 
 use crate::kernels::{Kernel, KernelSpecs};
 
@@ -44,6 +44,7 @@ impl<const I: usize> Update for Sh48<I> {
         self.0.update();
     }
 }
+impl<const I: usize> ClientHooks for Sh48<I> {}
 
 impl<const I: usize> TryWrite<SensorData> for Kernel<Sh48<I>> {
     type Error = Infallible;
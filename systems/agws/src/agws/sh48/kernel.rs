@@ -15,7 +15,7 @@ use gmt_dos_clients_io::{
     Estimate,
     optics::{Dev, Frame, SensorData},
 };
-use interface::{TryRead, TryUpdate, TryWrite, UniqueIdentifier};
+use interface::{ClientHooks, TryRead, TryUpdate, TryWrite, UniqueIdentifier};
 
 use crate::kernels::{Kernel, KernelError, KernelSpecs};
 
@@ -49,6 +49,7 @@ where
         Ok(self)
     }
 }
+impl<T: KernelSpecs> ClientHooks for Sh48Kern<T> where Kernel<T>: TryUpdate {}
 
 impl<T: KernelSpecs, U: UniqueIdentifier> TryRead<U> for Sh48Kern<T>
 where
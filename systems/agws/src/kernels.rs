@@ -1,7 +1,7 @@
 use std::{any::type_name, fmt::Display, marker::PhantomData};
 
 use gmt_dos_clients_crseo::{OpticalModel, OpticalModelBuilder, crseo::FromBuilder};
-use interface::{Data, TryRead, TryUpdate, TryWrite, UniqueIdentifier, Write};
+use interface::{ClientHooks, Data, TryRead, TryUpdate, TryWrite, UniqueIdentifier, Write};
 
 pub mod error;
 #[doc(inline)]
@@ -244,6 +244,24 @@ where
         Ok(self)
     }
 }
+impl<T> ClientHooks for Kernel<T>
+where
+    T: KernelSpecs,
+    <T as KernelSpecs>::Data: UniqueIdentifier,
+    <T as KernelSpecs>::Processor: TryWrite<<T as KernelSpecs>::Data>,
+    <T as KernelSpecs>::Estimator: TryRead<<T as KernelSpecs>::Data>,
+    <T as KernelSpecs>::Output: UniqueIdentifier,
+    <T as KernelSpecs>::Estimator: TryWrite<<T as KernelSpecs>::Output>,
+    <T as KernelSpecs>::Controller: TryRead<<T as KernelSpecs>::Output>,
+    <<T as KernelSpecs>::Processor as TryUpdate>::Error: 'static,
+    <<T as KernelSpecs>::Processor as TryWrite<<T as KernelSpecs>::Data>>::Error: 'static,
+    <<T as KernelSpecs>::Estimator as TryRead<<T as KernelSpecs>::Data>>::Error: 'static,
+    <<T as KernelSpecs>::Estimator as TryUpdate>::Error: 'static,
+    <<T as KernelSpecs>::Estimator as TryWrite<<T as KernelSpecs>::Output>>::Error: 'static,
+    <<T as KernelSpecs>::Controller as TryRead<<T as KernelSpecs>::Output>>::Error: 'static,
+    <<T as KernelSpecs>::Controller as TryUpdate>::Error: 'static,
+{
+}
 
 impl<T> TryWrite<<T as KernelSpecs>::Output> for Kernel<T>
 where
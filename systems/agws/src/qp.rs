@@ -13,7 +13,7 @@ use gmt_dos_clients_io::{
     optics::{Dev, Frame, SensorData},
 };
 use gmt_dos_clients_optics_state::{MirrorState, OpticalState, OpticsState};
-use interface::{Data, Read, Right, TryWrite, UniqueIdentifier, Update, Write};
+use interface::{ClientHooks, Data, Read, Right, TryWrite, UniqueIdentifier, Update, Write};
 use std::{
     convert::Infallible,
     io::{self},
@@ -149,6 +149,7 @@ impl Estimate2OpticsState {
 }
 
 impl Update for Estimate2OpticsState {}
+impl ClientHooks for Estimate2OpticsState {}
 impl Read<Estimate> for Estimate2OpticsState {
     fn read(&mut self, data: Data<Estimate>) {
         self.u = data.into_arc();
@@ -3,7 +3,7 @@ use std::{convert::Infallible, fmt::Display, fs::File};
 use gmt_dos_clients_crseo::calibration::{Calib, MixedMirrorMode, algebra::CalibProps};
 use gmt_dos_clients_io::{Estimate, optics::SensorData};
 use gmt_dos_clients_optics_state::{MirrorState, OpticalState, OpticsState};
-use interface::{Data, TryRead, TryUpdate, TryWrite};
+use interface::{ClientHooks, Data, TryRead, TryUpdate, TryWrite};
 use nalgebra::{DMatrix, DVector, SMatrix};
 use osqp::{CscMatrix, Problem};
 
@@ -222,6 +222,10 @@ impl<const M1_RBM: usize, const M2_RBM: usize, const M1_BM: usize, const N_MODE:
         Ok(self)
     }
 }
+impl<const M1_RBM: usize, const M2_RBM: usize, const M1_BM: usize, const N_MODE: usize> ClientHooks
+    for ActiveOptics<M1_RBM, M2_RBM, M1_BM, N_MODE>
+{
+}
 impl<const M1_RBM: usize, const M2_RBM: usize, const M1_BM: usize, const N_MODE: usize>
     TryRead<SensorData> for ActiveOptics<M1_RBM, M2_RBM, M1_BM, N_MODE>
 {
@@ -15,7 +15,7 @@ use gmt_dos_clients_io::{
     },
 };
 use gmt_dos_clients_optics_state::{M1State, SegmentState};
-use interface::{Data, Left, Read, Right, UniqueIdentifier, Update, Write};
+use interface::{ClientHooks, Data, Left, Read, Right, UniqueIdentifier, Update, Write};
 
 use super::NA;
 
@@ -78,6 +78,7 @@ impl DispatchIn {
 impl Assembly for DispatchIn {}
 
 impl Update for DispatchIn {}
+impl ClientHooks for DispatchIn {}
 
 impl Read<M1State> for DispatchIn {
     fn read(&mut self, data: Data<M1State>) {
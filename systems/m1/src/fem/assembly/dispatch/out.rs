@@ -8,7 +8,7 @@ use gmt_dos_clients_io::{
     Assembly,
     gmt_m1::{assembly::M1ActuatorAppliedForces, segment::ActuatorAppliedForces},
 };
-use interface::{Data, Read, Size, Update, Write, WriteFlatten};
+use interface::{ClientHooks, Data, Read, Size, Update, Write, WriteFlatten};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Default, Serialize, Deserialize)]
@@ -35,6 +35,7 @@ impl DispatchOut {
 }
 impl Assembly for DispatchOut {}
 impl Update for DispatchOut {}
+impl ClientHooks for DispatchOut {}
 
 impl<const ID: u8> Read<ActuatorAppliedForces<ID>> for DispatchOut {
     fn read(&mut self, data: Data<ActuatorAppliedForces<ID>>) {
@@ -7,7 +7,7 @@ use gmt_dos_clients_io::{
         segment::{AsmCommand, FluidDampingForces, VoiceCoilsForces, VoiceCoilsMotion},
     },
 };
-use interface::{Data, Read, Size, Update, Write, WriteFlatten};
+use interface::{ClientHooks, Data, Read, Size, Update, Write, WriteFlatten};
 use serde::{Deserialize, Serialize};
 
 impl Assembly for DispatchIn {}
@@ -87,7 +87,9 @@ impl DispatchOut {
 }
 
 impl Update for DispatchIn {}
+impl ClientHooks for DispatchIn {}
 impl Update for DispatchOut {}
+impl ClientHooks for DispatchOut {}
 
 impl Read<M2ASMVoiceCoilsMotion> for DispatchIn {
     fn read(&mut self, data: Data<M2ASMVoiceCoilsMotion>) {
@@ -7,7 +7,7 @@ use gmt_dos_clients_io::{
         segment::{FsmCommand, PiezoForces, PiezoNodes},
     },
 };
-use interface::{Data, Read, Size, Update, Write, WriteFlatten};
+use interface::{ClientHooks, Data, Read, Size, Update, Write, WriteFlatten};
 use serde::{Deserialize, Serialize};
 
 impl Assembly for DispatchIn {}
@@ -58,7 +58,9 @@ where
 }
 
 impl Update for DispatchIn {}
+impl ClientHooks for DispatchIn {}
 impl Update for DispatchOut {}
+impl ClientHooks for DispatchOut {}
 
 impl Read<M2FSMPiezoNodes> for DispatchIn {
     fn read(&mut self, data: Data<M2FSMPiezoNodes>) {